@@ -0,0 +1,137 @@
+//! Provider-agnostic abstractions used to combine schedule data from multiple leagues into labeled rails.
+
+use futures::future::{FutureExt, LocalBoxFuture};
+use log::{debug, error};
+use std::panic::AssertUnwindSafe;
+
+/// The error type returned by a provider that could not retrieve any data.
+pub type ProviderError = Box<dyn std::error::Error>;
+
+/// A container for information used by the client to display a single entry in a rail.
+#[derive(Clone, Debug)]
+pub struct RailEntry {
+    pub title: String,
+    pub image: Option<Vec<u8>>,
+    pub summary: String,
+}
+
+/// A container for a labeled row of entries.
+#[derive(Clone, Debug)]
+pub struct Rail {
+    pub label: String,
+    pub entries: Vec<RailEntry>,
+}
+
+/// A source of schedule data that can be registered with an Aggregator.
+pub trait ScheduleProvider {
+    /// The display name of the provider, used to label its rails and failures.
+    fn name(&self) -> &str;
+
+    /// Retrieves the rails offered by this provider, ordered as they should be displayed.
+    ///
+    /// # Errors
+    /// If the provider was unable to retrieve any data.
+    fn fetch(&self) -> LocalBoxFuture<'_, Result<Vec<Rail>, ProviderError>>;
+}
+
+/// A container for the rails produced by a provider during aggregation.
+#[derive(Clone, Debug)]
+pub struct ProviderRails {
+    /// The index of the provider in the order providers were registered.
+    pub index: usize,
+    pub provider: String,
+    pub rails: Vec<Rail>,
+}
+
+/// A container for a provider that failed to produce rails during aggregation.
+#[derive(Clone, Debug)]
+pub struct ProviderFailure {
+    /// The index of the provider in the order providers were registered.
+    pub index: usize,
+    pub provider: String,
+    pub message: String,
+}
+
+/// A container for the results of all registered providers, each in the order providers were registered.
+#[derive(Clone, Debug, Default)]
+pub struct AggregateResult {
+    pub provided: Vec<ProviderRails>,
+    pub failures: Vec<ProviderFailure>,
+}
+
+impl AggregateResult {
+    /// Merges the rails of every provider that produced rails, each labeled with the name of its provider.
+    pub fn merged(&self) -> Vec<Rail> {
+        self.provided
+            .iter()
+            .flat_map(|provided| {
+                provided.rails.iter().map(move |rail| Rail {
+                    label: format!("{} - {}", provided.provider, rail.label),
+                    entries: rail.entries.clone(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Fans out to each registered provider concurrently and merges their results.
+#[derive(Default)]
+pub struct Aggregator {
+    providers: Vec<Box<dyn ScheduleProvider>>,
+}
+
+impl Aggregator {
+    /// Initializes an aggregator with no registered providers.
+    pub fn new() -> Self {
+        Aggregator { providers: vec![] }
+    }
+
+    /// Registers the given provider. Rails are merged in the order providers are registered.
+    pub fn register(&mut self, provider: Box<dyn ScheduleProvider>) {
+        self.providers.push(provider);
+    }
+
+    /// Retrieves rails from every registered provider. A provider that errors or panics is reported as a failure
+    /// without affecting the rails of the other providers.
+    pub async fn fetch_all(&self) -> AggregateResult {
+        debug!("starting fetch_all");
+        let mut futures = Vec::with_capacity(self.providers.len());
+        for provider in &self.providers {
+            futures.push(AssertUnwindSafe(provider.fetch()).catch_unwind());
+        }
+
+        let mut result = AggregateResult::default();
+        let outcomes = futures::future::join_all(futures).await;
+        for (index, (provider, outcome)) in self.providers.iter().zip(outcomes).enumerate() {
+            let name = provider.name();
+            match outcome {
+                Ok(Ok(rails)) => {
+                    debug!("{} provided {} rails", name, rails.len());
+                    result.provided.push(ProviderRails {
+                        index,
+                        provider: name.to_owned(),
+                        rails,
+                    });
+                }
+                Ok(Err(ex)) => {
+                    error!("Error while retrieving {} data:\n{}", name, ex);
+                    result.failures.push(ProviderFailure {
+                        index,
+                        provider: name.to_owned(),
+                        message: ex.to_string(),
+                    });
+                }
+                Err(_) => {
+                    error!("Provider {} panicked while retrieving data", name);
+                    result.failures.push(ProviderFailure {
+                        index,
+                        provider: name.to_owned(),
+                        message: "provider panicked".to_owned(),
+                    });
+                }
+            }
+        }
+        debug!("ending fetch_all");
+        result
+    }
+}
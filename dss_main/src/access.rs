@@ -0,0 +1,257 @@
+//! Screen reader support. The focus tree of the UI (the menu, the rails of the grid, and the titles, summaries, and
+//! statuses of their items) is exposed through AccessKit, so that platform screen readers announce the focused element
+//! as the focus moves. The tree is exposed on the AT-SPI bus of Linux desktops, through UI Automation on Windows, and
+//! through the accessibility protocol of the window's view on macOS; elsewhere it is built but not exposed.
+
+use accesskit::{ActionHandler, ActionRequest, NodeBuilder, NodeClassSet, NodeId, Role, Tree, TreeUpdate};
+use glium::glutin::window::Window;
+use log::{debug, info};
+
+/// The name of the application announced by screen readers.
+const APP_NAME: &str = "JDN DSS Solution";
+/// The identifier of the window, the root of every tree.
+const ROOT_ID: u64 = 1;
+
+/// A container for the description of an element of the UI announced by screen readers.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccessNode {
+    pub id: u64,
+    pub role: Role,
+    pub name: String,
+    /// A longer description announced after the name, such as the summary of a game.
+    pub description: Option<String>,
+    /// The state announced after the description, such as the status and score of a game.
+    pub value: Option<String>,
+    pub children: Vec<u64>,
+}
+
+impl AccessNode {
+    /// Initializes a node with the given identifier, role, and name, without a description, value, or children.
+    pub fn new(id: u64, role: Role, name: String) -> Self {
+        AccessNode {
+            id,
+            role,
+            name,
+            description: None,
+            value: None,
+            children: Vec::new(),
+        }
+    }
+
+    /// Initializes the window, the root of every tree, with the given children.
+    pub fn window(children: Vec<u64>) -> Self {
+        AccessNode {
+            children,
+            ..AccessNode::new(ROOT_ID, Role::Window, APP_NAME.to_owned())
+        }
+    }
+
+    /// Converts the node to its AccessKit representation, sharing property classes through the given set.
+    fn build(&self, classes: &mut NodeClassSet) -> accesskit::Node {
+        let mut builder = NodeBuilder::new(self.role);
+        builder.set_name(self.name.clone());
+        if let Some(description) = &self.description {
+            builder.set_description(description.clone());
+        }
+        if let Some(value) = &self.value {
+            builder.set_value(value.clone());
+        }
+        builder.set_children(self.children.iter().map(|id| NodeId(*id)).collect::<Vec<_>>());
+        builder.build(classes)
+    }
+}
+
+/// A container for the elements of the UI, with the window first, and the element that has the focus.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccessTree {
+    pub nodes: Vec<AccessNode>,
+    pub focus: u64,
+}
+
+/// The adapter of the AT-SPI bus, which is not tied to a window.
+#[cfg(target_os = "linux")]
+struct Adapter(accesskit_unix::Adapter);
+
+#[cfg(target_os = "linux")]
+impl Adapter {
+    /// Exposes the tree of the given initial state on the AT-SPI bus, or returns None if there is no bus.
+    fn new(
+        _window: &Window,
+        initial_state: impl 'static + FnOnce() -> TreeUpdate,
+        is_window_focused: bool,
+        action_handler: Box<dyn ActionHandler + Send>,
+    ) -> Option<Self> {
+        accesskit_unix::Adapter::new(initial_state, is_window_focused, action_handler).map(Adapter)
+    }
+
+    fn update(&self, update: TreeUpdate) {
+        self.0.update(update);
+    }
+
+    fn update_window_focus_state(&self, is_focused: bool) {
+        self.0.update_window_focus_state(is_focused);
+    }
+}
+
+/// The UI Automation adapter, which subclasses the window to answer the requests of screen readers.
+#[cfg(target_os = "windows")]
+struct Adapter(accesskit_windows::SubclassingAdapter);
+
+#[cfg(target_os = "windows")]
+impl Adapter {
+    /// Exposes the tree of the given initial state through the given window, which must not have been shown yet.
+    fn new(
+        window: &Window,
+        initial_state: impl 'static + FnOnce() -> TreeUpdate,
+        _is_window_focused: bool,
+        action_handler: Box<dyn ActionHandler + Send>,
+    ) -> Option<Self> {
+        use glium::glutin::platform::windows::WindowExtWindows;
+        let hwnd = accesskit_windows::HWND(window.hwnd() as isize);
+        Some(Adapter(accesskit_windows::SubclassingAdapter::new(
+            hwnd,
+            initial_state,
+            action_handler,
+        )))
+    }
+
+    fn update(&self, update: TreeUpdate) {
+        self.0.update(update).raise();
+    }
+
+    /// Does nothing, as UI Automation tracks the focus of the window itself.
+    fn update_window_focus_state(&self, _is_focused: bool) {}
+}
+
+/// The adapter of the accessibility protocol, which subclasses the view of the window to answer the requests of
+/// screen readers.
+#[cfg(target_os = "macos")]
+struct Adapter(accesskit_macos::SubclassingAdapter);
+
+#[cfg(target_os = "macos")]
+impl Adapter {
+    /// Exposes the tree of the given initial state through the view of the given window, which must not have been
+    /// shown yet.
+    fn new(
+        window: &Window,
+        initial_state: impl 'static + FnOnce() -> TreeUpdate,
+        is_window_focused: bool,
+        action_handler: Box<dyn ActionHandler + Send>,
+    ) -> Option<Self> {
+        use glium::glutin::platform::macos::WindowExtMacOS;
+        // the view of a window that exists is valid, and the adapter retains it
+        let adapter =
+            unsafe { accesskit_macos::SubclassingAdapter::new(window.ns_view(), initial_state, action_handler) };
+        let adapter = Adapter(adapter);
+        adapter.update_window_focus_state(is_window_focused);
+        Some(adapter)
+    }
+
+    fn update(&self, update: TreeUpdate) {
+        self.0.update(update).raise();
+    }
+
+    fn update_window_focus_state(&self, is_focused: bool) {
+        if let Some(events) = self.0.update_view_focus_state(is_focused) {
+            events.raise();
+        }
+    }
+}
+
+/// A stand-in for the adapter of platforms whose screen readers are not supported, to which the tree is never exposed.
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+struct Adapter;
+
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+impl Adapter {
+    fn new(
+        _window: &Window,
+        _initial_state: impl 'static + FnOnce() -> TreeUpdate,
+        _is_window_focused: bool,
+        _action_handler: Box<dyn ActionHandler + Send>,
+    ) -> Option<Self> {
+        None
+    }
+
+    fn update(&self, _update: TreeUpdate) {}
+
+    fn update_window_focus_state(&self, _is_focused: bool) {}
+}
+
+/// Ignores the requests of screen readers, whose users navigate with the keys bound to the UI.
+struct IgnoreActions;
+
+impl ActionHandler for IgnoreActions {
+    fn do_action(&mut self, request: ActionRequest) {
+        debug!("ignoring accessibility request {:?}", request.action);
+    }
+}
+
+/// The manager of the tree exposed to screen readers, which sends the tree to the platform only when it changes.
+pub struct Accessibility {
+    adapter: Option<Adapter>,
+    classes: NodeClassSet,
+    last: Option<AccessTree>,
+}
+
+impl Accessibility {
+    /// Exposes a tree containing only the given window to the platform's screen readers, if any are listening. The
+    /// window must not have been shown yet, as the adapters of some platforms take over its handling of their requests.
+    pub fn init(window: &Window) -> Self {
+        let adapter = Adapter::new(
+            window,
+            || TreeUpdate {
+                nodes: vec![(
+                    NodeId(ROOT_ID),
+                    AccessNode::window(Vec::new()).build(&mut NodeClassSet::new()),
+                )],
+                tree: Some(Tree {
+                    app_name: Some(APP_NAME.to_owned()),
+                    ..Tree::new(NodeId(ROOT_ID))
+                }),
+                focus: NodeId(ROOT_ID),
+            },
+            true,
+            Box::new(IgnoreActions),
+        );
+        match adapter {
+            Some(_) => info!("accessibility tree exposed"),
+            None => info!("no accessibility bus, screen readers are not supported"),
+        }
+        Accessibility {
+            adapter,
+            classes: NodeClassSet::new(),
+            last: None,
+        }
+    }
+
+    /// Replaces the exposed tree with the given tree, if it differs from the last one, which screen readers announce
+    /// if its focus has moved.
+    pub fn update(&mut self, tree: AccessTree) {
+        if self.last.as_ref() == Some(&tree) {
+            return;
+        }
+        let update = TreeUpdate {
+            nodes: tree
+                .nodes
+                .iter()
+                .map(|node| (NodeId(node.id), node.build(&mut self.classes)))
+                .collect(),
+            tree: None,
+            focus: NodeId(tree.focus),
+        };
+        if let Some(adapter) = &self.adapter {
+            adapter.update(update);
+        }
+        debug!("accessibility tree updated with focus {}", tree.focus);
+        self.last = Some(tree);
+    }
+
+    /// Records whether the window has the focus, outside of which screen readers do not announce the tree.
+    pub fn set_window_focused(&self, focused: bool) {
+        if let Some(adapter) = &self.adapter {
+            adapter.update_window_focus_state(focused);
+        }
+        debug!("accessibility window focus set to {}", focused);
+    }
+}
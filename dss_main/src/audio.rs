@@ -0,0 +1,109 @@
+//! Playback of ambient audio behind the UI, such as a music loop or a radio stream. Decoding is delegated to an `ffmpeg`
+//! process, as for videos, writing raw samples to a worker thread that queues them on the default output device.
+
+use log::debug;
+use rodio::buffer::SamplesBuffer;
+use rodio::Sink;
+use std::io::{self, Read};
+use std::process::{Child, Command, Stdio};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// The number of channels to which audio is mixed by the decoder.
+const AUDIO_CHANNELS: u16 = 2;
+/// The rate in samples per second per channel to which audio is resampled by the decoder.
+const AUDIO_SAMPLE_RATE: u32 = 44_100;
+/// The number of samples per channel in each buffer queued for playback, a tenth of a second.
+const BUFFER_FRAMES: usize = 4_410;
+/// The number of buffers that may be queued for playback before the decoder is blocked.
+const QUEUED_BUFFERS: usize = 4;
+/// The change in volume of each step up or down.
+const VOLUME_STEP: f32 = 0.1;
+
+/// Ambient audio being decoded in the background and played on the default output device.
+pub struct AmbientAudio {
+    decoder: Child,
+    sink: Arc<Sink>,
+}
+
+impl AmbientAudio {
+    /// Starts playing the audio at the given path or URL at the given volume between 0 and 1, from the start again each
+    /// time it ends. Streams that do not end, such as radio, are played live.
+    ///
+    /// # Errors
+    /// * If there is no audio output device.
+    /// * If the decoder process cannot be started.
+    pub fn start(url: &str, volume: f32) -> io::Result<Self> {
+        debug!("starting ambient audio {}", url);
+        let device = rodio::default_output_device().ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))?;
+        let mut decoder = Command::new("ffmpeg")
+            .args(&["-loglevel", "error", "-stream_loop", "-1", "-i", url, "-vn"])
+            .args(&[
+                "-ac",
+                &AUDIO_CHANNELS.to_string(),
+                "-ar",
+                &AUDIO_SAMPLE_RATE.to_string(),
+            ])
+            .args(&["-f", "s16le", "-"])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+        let mut stdout = decoder
+            .stdout
+            .take()
+            .ok_or_else(|| io::Error::from(io::ErrorKind::BrokenPipe))?;
+        let sink = Arc::new(Sink::new(&device));
+        sink.set_volume(volume.clamp(0.0, 1.0));
+        let thread_sink = Arc::clone(&sink);
+        thread::Builder::new().name("audio".to_owned()).spawn(move || {
+            let buffer_duration = Duration::from_secs(1) * BUFFER_FRAMES as u32 / AUDIO_SAMPLE_RATE;
+            let mut bytes = vec![0; BUFFER_FRAMES * usize::from(AUDIO_CHANNELS) * 2];
+            loop {
+                // the decoder is blocked while enough is queued, including while playback is paused
+                while thread_sink.len() >= QUEUED_BUFFERS {
+                    thread::sleep(buffer_duration / 2);
+                }
+                if stdout.read_exact(&mut bytes).is_err() {
+                    break;
+                }
+                let samples: Vec<i16> = bytes
+                    .chunks_exact(2)
+                    .map(|sample| i16::from_le_bytes([sample[0], sample[1]]))
+                    .collect();
+                thread_sink.append(SamplesBuffer::new(AUDIO_CHANNELS, AUDIO_SAMPLE_RATE, samples));
+            }
+            debug!("ending ambient audio");
+        })?;
+        Ok(AmbientAudio { decoder, sink })
+    }
+
+    /// Pauses the audio if it is playing, or resumes it if paused.
+    pub fn toggle_pause(&self) {
+        if self.sink.is_paused() {
+            self.sink.play();
+        } else {
+            self.sink.pause();
+        }
+    }
+
+    /// Raises the volume by a step if the given value is true, otherwise lowers it, between silent and full volume.
+    /// Returns the new volume between 0 and 1.
+    pub fn step_volume(&self, up: bool) -> f32 {
+        let step = if up { VOLUME_STEP } else { -VOLUME_STEP };
+        let volume = (self.sink.volume() + step).clamp(0.0, 1.0);
+        self.sink.set_volume(volume);
+        volume
+    }
+}
+
+impl Drop for AmbientAudio {
+    fn drop(&mut self) {
+        self.sink.stop();
+        if let Err(ex) = self.decoder.kill() {
+            debug!("Could not stop audio decoder:\n{}", ex);
+        }
+        let _ = self.decoder.wait();
+    }
+}
@@ -0,0 +1,133 @@
+//! The configuration file of the application, which customizes a deployment without changes to the code. Every setting
+//! may be omitted, in which case its default is used; settings given on the command line take precedence.
+
+use crate::keymap::KeymapConfig;
+use crate::lirc::LircConfig;
+use crate::locale;
+use crate::logging::LoggingConfig;
+use crate::theme::ThemeConfig;
+use serde_derive::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The name of the directory, within the platform configuration directory, holding the configuration file.
+const CONFIG_DIR_NAME: &str = "jdn-dss";
+/// The name of the configuration file.
+const CONFIG_FILE_NAME: &str = "config.toml";
+
+/// A container for the settings read from the configuration file.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Config {
+    /// The number of days of games to display, ending with today.
+    pub days: u32,
+    /// Whether to display in a window rather than fullscreen.
+    pub windowed: bool,
+    /// Whether to run unattended: exit keys are ignored, the cursor is hidden while still, the loader is restarted
+    /// after repeated failures, and the display is recreated if its context is lost.
+    pub kiosk: bool,
+    /// The index of the monitor on which to display, or None for the primary monitor.
+    pub monitor: Option<usize>,
+    /// The level at which messages are logged (off, error, warn, info, debug, or trace).
+    pub log_level: String,
+    /// The destinations of logged messages and the rolling over of the log file.
+    pub logging: LoggingConfig,
+    /// The directory to which retrieved data is persisted, or None to only cache in memory.
+    pub cache_dir: Option<PathBuf>,
+    /// The number of seconds a retrieved day that had ended, or whose games were all final, is kept before it is
+    /// retrieved again, or None to keep it until it is refreshed.
+    pub cache_complete_secs: Option<u64>,
+    /// The number of seconds a retrieved day with games still to be played is kept before it is retrieved again.
+    pub cache_pending_secs: u64,
+    /// The number of seconds a retrieved day with a game in progress is kept before it is retrieved again.
+    pub cache_live_secs: u64,
+    /// The names of teams whose games are highlighted.
+    pub favorite_teams: Vec<String>,
+    /// The number of seconds between refreshes of today's games, or 0 to never refresh.
+    pub refresh_secs: u64,
+    /// Whether to synchronize frames with the refresh rate of the display.
+    pub vsync: bool,
+    /// The number of samples per pixel with which edges are antialiased, or 0 to not antialias.
+    pub multisampling: u16,
+    /// The largest number of frames drawn per second, or 0 to not limit the frame rate.
+    pub max_fps: u32,
+    /// The aspect ratio of the area in which content is laid out, centered in the window (e.g. "16:9"), or "fill" to
+    /// lay out content across the window.
+    pub aspect_ratio: String,
+    /// The percentage of each dimension of the window by which its edges are inset before content is laid out, for TVs
+    /// that overscan.
+    pub safe_area: f32,
+    /// The path or URL of audio played behind the UI, such as a music loop or a radio stream, which requires `ffmpeg`.
+    pub ambient_audio: Option<String>,
+    /// The percentage of full volume at which ambient audio is played.
+    pub ambient_volume: u32,
+    /// The port on which remotes, such as the browser of a phone, can control the UI over HTTP, or None to not accept
+    /// remotes.
+    pub remote_port: Option<u16>,
+    /// The colors and font of the UI.
+    pub theme: ThemeConfig,
+    /// The keys bound to each action of the UI.
+    pub keys: KeymapConfig,
+    /// The reading of infrared remotes through LIRC and the keys their buttons press.
+    pub lirc: LircConfig,
+    /// The locale in which text is displayed (e.g. "en-US"), whose catalog is read from this file's directory.
+    pub locale: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            days: 3,
+            windowed: false,
+            kiosk: false,
+            monitor: None,
+            log_level: "info".to_owned(),
+            logging: LoggingConfig::default(),
+            cache_dir: None,
+            cache_complete_secs: None,
+            cache_pending_secs: 300,
+            cache_live_secs: 30,
+            favorite_teams: Vec::new(),
+            refresh_secs: 60,
+            vsync: true,
+            multisampling: 4,
+            max_fps: 60,
+            aspect_ratio: "16:9".to_owned(),
+            safe_area: 0.0,
+            ambient_audio: None,
+            ambient_volume: 50,
+            remote_port: None,
+            theme: ThemeConfig::default(),
+            keys: KeymapConfig::default(),
+            lirc: LircConfig::default(),
+            locale: locale::DEFAULT_LOCALE.to_owned(),
+        }
+    }
+}
+
+impl Config {
+    /// Determines the path of the configuration file within the platform configuration directory, if there is one.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join(CONFIG_DIR_NAME).join(CONFIG_FILE_NAME))
+    }
+
+    /// Loads the configuration file at the given path. If there is no file at the path, one is written with the
+    /// default settings so that they can be edited.
+    ///
+    /// # Errors
+    /// * If the file cannot be read.
+    /// * If the file is not a valid configuration.
+    /// * If the default file cannot be written.
+    pub fn load_or_create(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        if path.exists() {
+            let text = fs::read_to_string(path)?;
+            return Ok(toml::from_str(&text)?);
+        }
+        let config = Config::default();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, toml::to_string_pretty(&config)?)?;
+        Ok(config)
+    }
+}
@@ -0,0 +1,92 @@
+//! The capture of crash reports when the application panics, so that unattended crashes can be diagnosed, and the
+//! notice of a crash on the next launch.
+
+use crate::logging::{self, LoggingConfig};
+use chrono::Local;
+use log::error;
+use std::backtrace::Backtrace;
+use std::fmt::Write;
+use std::fs;
+use std::panic::PanicInfo;
+use std::path::{Path, PathBuf};
+
+/// The name of the file, within the crash directory, naming the report of a crash not yet noticed on launch.
+const PENDING_FILE_NAME: &str = "pending";
+/// The number of lines at the end of the log file included in a crash report.
+const LOG_TAIL_LINES: usize = 100;
+
+/// Installs a panic hook that writes a crash report to the given directory, with the tail of the log file written
+/// according to the given settings, before the default hook prints the panic. The report is named in the directory for
+/// `take_pending` to find on the next launch.
+pub fn install(dir: PathBuf, logging: LoggingConfig) {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let report = describe(info, &logging::current_path(&logging));
+        match save(&dir, &report) {
+            Ok(path) => error!("Crash report written to {}", path.display()),
+            Err(ex) => error!("Could not write crash report to {}:\n{}", dir.display(), ex),
+        }
+        default_hook(info);
+    }));
+}
+
+/// Takes the path of the report of a crash not yet noticed from the given directory, so that each crash is noticed on
+/// only one launch. Returns None if the previous run did not crash.
+pub fn take_pending(dir: &Path) -> Option<PathBuf> {
+    let pending = dir.join(PENDING_FILE_NAME);
+    let name = fs::read_to_string(&pending).ok()?;
+    if let Err(ex) = fs::remove_file(&pending) {
+        error!("Could not remove {}:\n{}", pending.display(), ex);
+    }
+    Some(dir.join(name.trim()))
+}
+
+/// Describes the given panic for a crash report: the version of the application, the time and thread of the panic, its
+/// message and location, a backtrace, and the last lines of the log file at the given path.
+fn describe(info: &PanicInfo, log_path: &Path) -> String {
+    let message = match info.payload().downcast_ref::<&str>() {
+        Some(message) => (*message).to_owned(),
+        None => match info.payload().downcast_ref::<String>() {
+            Some(message) => message.clone(),
+            None => "Box<dyn Any>".to_owned(),
+        },
+    };
+    let location = match info.location() {
+        Some(location) => format!("{}:{}:{}", location.file(), location.line(), location.column()),
+        None => "unknown".to_owned(),
+    };
+    let mut report = String::new();
+    let _ = writeln!(report, "JDN DSS Solution {}", env!("CARGO_PKG_VERSION"));
+    let _ = writeln!(report, "Time: {}", Local::now().format("%Y-%m-%d %H:%M:%S"));
+    let _ = writeln!(report, "Thread: {}", std::thread::current().name().unwrap_or("unnamed"));
+    let _ = writeln!(report, "Panic: {}", message);
+    let _ = writeln!(report, "Location: {}", location);
+    let _ = writeln!(report, "\nBacktrace:\n{}", Backtrace::force_capture());
+    let _ = writeln!(report, "\nLog ({}):", log_path.display());
+    match fs::read_to_string(log_path) {
+        Ok(log) => {
+            let lines: Vec<&str> = log.lines().collect();
+            for line in &lines[lines.len().saturating_sub(LOG_TAIL_LINES)..] {
+                let _ = writeln!(report, "{}", line);
+            }
+        }
+        Err(ex) => {
+            let _ = writeln!(report, "unavailable: {}", ex);
+        }
+    }
+    report
+}
+
+/// Writes the given crash report to a new file in the given directory, named with the current time, and names it as
+/// the pending report. Returns the path of the report.
+///
+/// # Errors
+/// * If the directory or either file cannot be written.
+fn save(dir: &Path, report: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    fs::create_dir_all(dir)?;
+    let name = format!("crash-{}.txt", Local::now().format("%Y%m%d-%H%M%S"));
+    let path = dir.join(&name);
+    fs::write(&path, report)?;
+    fs::write(dir.join(PENDING_FILE_NAME), name)?;
+    Ok(path)
+}
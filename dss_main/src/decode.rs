@@ -0,0 +1,84 @@
+//! Decoding of retrieved images into pixels ready to be uploaded as textures, so that the decoding can be done away from
+//! the render thread, which only uploads the pixels.
+
+use crate::gl_utils;
+use dss_mlb::{MlbImage, MlbImageFormat};
+use glium::backend::Facade;
+use glium::texture::{RawImage2d, SrgbTexture2d};
+use image::imageops::{self, FilterType};
+use image::{ImageFormat, ImageResult};
+use log::error;
+
+/// A container for the pixels of a decoded image, as rows of RGBA bytes from the bottom of the image up, as OpenGL
+/// expects them.
+#[derive(Clone)]
+pub struct DecodedImage {
+    rgba: Vec<u8>,
+    dimensions: (u32, u32),
+}
+
+impl std::fmt::Debug for DecodedImage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DecodedImage({}x{})", self.dimensions.0, self.dimensions.1)
+    }
+}
+
+impl DecodedImage {
+    /// Decodes the given bytes, in the given format if known and otherwise guessing the format from the bytes. If a
+    /// maximum size in pixels is given, an image wider or taller than it is downscaled to at most that width and height.
+    ///
+    /// # Errors
+    /// * If the bytes are not an image of a supported format.
+    pub fn decode(bytes: &[u8], format: Option<ImageFormat>, max_size: Option<(u32, u32)>) -> ImageResult<Self> {
+        let decoded = match format {
+            Some(format) => image::load_from_memory_with_format(bytes, format),
+            None => image::load_from_memory(bytes),
+        }?;
+        let mut rgba = decoded.into_rgba();
+        if let Some((max_width, max_height)) = max_size {
+            let (width, height) = rgba.dimensions();
+            // the image is stretched over its displayed area, so each dimension is reduced independently
+            let (new_width, new_height) = (width.min(max_width), height.min(max_height));
+            if (new_width, new_height) != (width, height) && new_width > 0 && new_height > 0 {
+                rgba = imageops::resize(&rgba, new_width, new_height, FilterType::Triangle);
+            }
+        }
+        imageops::flip_vertical_in_place(&mut rgba);
+        Ok(DecodedImage {
+            dimensions: rgba.dimensions(),
+            rgba: rgba.into_raw(),
+        })
+    }
+
+    /// Decodes the given retrieved image in its detected format, downscaling it to the given maximum size if any.
+    ///
+    /// # Errors
+    /// * If the bytes of the image are not an image of a supported format.
+    pub fn decode_retrieved(image: &MlbImage, max_size: Option<(u32, u32)>) -> ImageResult<Self> {
+        DecodedImage::decode(&image.bytes, image_format(image.format), max_size)
+    }
+
+    /// Uploads the pixels of the image to a new mipmapped texture.
+    ///
+    /// # Errors
+    /// Panics if the given display cannot be used to create the texture.
+    pub fn into_texture(self, display: &dyn Facade) -> SrgbTexture2d {
+        let raw = RawImage2d::from_raw_rgba(self.rgba, self.dimensions);
+        gl_utils::mipmapped_texture(display, raw).unwrap_or_else(|ex| {
+            let msg = "Could not create image texture";
+            error!("{}:\n{}", msg, ex);
+            panic!("{}.", msg);
+        })
+    }
+}
+
+/// Converts the given detected image format to the equivalent decoder format, if the format was recognized.
+fn image_format(format: MlbImageFormat) -> Option<ImageFormat> {
+    match format {
+        MlbImageFormat::Jpeg => Some(ImageFormat::Jpeg),
+        MlbImageFormat::Png => Some(ImageFormat::Png),
+        MlbImageFormat::Webp => Some(ImageFormat::WebP),
+        MlbImageFormat::Gif => Some(ImageFormat::Gif),
+        MlbImageFormat::Unknown => None,
+    }
+}
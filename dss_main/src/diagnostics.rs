@@ -0,0 +1,80 @@
+//! The measurements displayed by the diagnostics overlay, for tuning the renderer and the retrieval of data.
+
+use crate::loader::LoaderStats;
+use glium::texture::SrgbTexture2d;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// The number of recent frames over which the frame time and rate are averaged.
+const FRAME_SAMPLES: usize = 60;
+/// The number of bytes of each texel of an RGBA texture.
+const TEXEL_BYTES: u64 = 4;
+
+/// A container for the measurements displayed by the diagnostics overlay, other than those of the textures of the UI.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Diagnostics {
+    /// The average time taken to draw and present a recent frame.
+    pub frame_time: Duration,
+    /// The average number of frames drawn per second recently.
+    pub fps: f32,
+    /// The measurements of the retrievals of the loader.
+    pub loader: LoaderStats,
+}
+
+/// A container for the number of textures held by the UI and an estimate of the video memory they occupy.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TextureStats {
+    pub count: usize,
+    pub bytes: u64,
+}
+
+impl TextureStats {
+    /// Counts the given texture, estimating its size from the dimensions of each of its mipmap levels.
+    pub fn add(&mut self, texture: &SrgbTexture2d) {
+        let (width, height) = (u64::from(texture.width()), u64::from(texture.height()));
+        self.count += 1;
+        self.bytes += (0..texture.get_mipmap_levels())
+            .map(|level| (width >> level).max(1) * (height >> level).max(1) * TEXEL_BYTES)
+            .sum::<u64>();
+    }
+}
+
+/// A record of when recent frames were drawn and how long each took.
+#[derive(Debug, Default)]
+pub struct FrameTimer {
+    frames: VecDeque<(Instant, Duration)>,
+}
+
+impl FrameTimer {
+    /// Initializes a timer that has not recorded any frames.
+    pub fn new() -> Self {
+        FrameTimer::default()
+    }
+
+    /// Records a frame started at the given time and finished now.
+    pub fn record(&mut self, started_at: Instant) {
+        if self.frames.len() == FRAME_SAMPLES {
+            self.frames.pop_front();
+        }
+        self.frames.push_back((started_at, started_at.elapsed()));
+    }
+
+    /// Determines the average time taken to draw a recent frame.
+    pub fn frame_time(&self) -> Duration {
+        match self.frames.len() {
+            0 => Duration::default(),
+            count => self.frames.iter().map(|(_, taken)| *taken).sum::<Duration>() / count as u32,
+        }
+    }
+
+    /// Determines the average number of frames drawn per second between the recent frames, or 0 if fewer than two
+    /// have been drawn.
+    pub fn fps(&self) -> f32 {
+        match (self.frames.front(), self.frames.back()) {
+            (Some((first, _)), Some((last, _))) if last > first => {
+                (self.frames.len() - 1) as f32 / last.duration_since(*first).as_secs_f32()
+            }
+            _ => 0.0,
+        }
+    }
+}
@@ -0,0 +1,133 @@
+# The user-visible text of the application in English, which is used for any message missing from the catalog of the
+# configured locale. Date and time formats use the chrono syntax, where %A, %B and %p are replaced by the weekday,
+# month and period names below.
+
+## Dates and times
+
+day-label-format = %A, %B %-d
+story-date-format = %B %-d
+clock-format = %-I:%M %p
+clock-freshness = Data as of { $time }
+time-am = AM
+time-pm = PM
+weekday-1 = Monday
+weekday-2 = Tuesday
+weekday-3 = Wednesday
+weekday-4 = Thursday
+weekday-5 = Friday
+weekday-6 = Saturday
+weekday-7 = Sunday
+month-1 = January
+month-2 = February
+month-3 = March
+month-4 = April
+month-5 = May
+month-6 = June
+month-7 = July
+month-8 = August
+month-9 = September
+month-10 = October
+month-11 = November
+month-12 = December
+
+## Menu and tabs
+
+menu-home = Home
+menu-scores = Scores
+menu-standings = Standings
+menu-settings = Settings
+mlb-tab = MLB
+search-label = Search:
+
+## Grid
+
+day-label-upcoming = { $day } · Upcoming
+probable-pitchers = Probable: { $away } vs. { $home }
+probable-pitcher-tbd = TBD
+
+badge-live = LIVE
+badge-score = { $away }-{ $home }
+badge-final = { $away }-{ $home } F
+no-games = No games scheduled
+rail-collapsed = { $label } ({ $count ->
+    [one] 1 game
+   *[other] { $count } games
+})
+no-matches = No matching games
+games-failed = Games could not be retrieved
+stories-label = Top Stories
+story-summary = Recap, { $date }
+standings-label = Standings
+standings-leader = { $team } { $wins }-{ $losses }
+standings-team = { $team } { $wins }-{ $losses } ({ $games-back } GB)
+league-loading = Loading { $league } schedule…
+league-empty = No { $league } games scheduled
+league-unavailable = { $league } schedule could not be retrieved
+date-picker-title = Go to date
+date-picker-format = %A, %B %-d, %Y
+date-picker-hint = Left/Right: day · Up/Down: week · Page Up/Down: month · Enter: go · Escape: cancel
+
+## Screen readers
+
+access-game-status = { $state }, { $away-team } { $away }, { $home-team } { $home }
+
+## Loading and errors
+
+loading-games = Loading games
+loading-games-progress = Loading games ({ $completed } of { $total })
+banner-unreachable = Couldn't reach MLB — showing cached data
+banner-hint = Press R to retry
+banner-crashed = The last session ended unexpectedly
+banner-crash-report = A report was saved to { $path }
+
+## Diagnostics
+
+diagnostics-frame = Frame: { $ms } ms ({ $fps } fps)
+diagnostics-textures = Textures: { $count } (~{ $mb } MB)
+diagnostics-cache = Cache: { $rate }% hits ({ $hits } of { $total } days)
+diagnostics-cache-unused = Cache: no days requested
+diagnostics-fetch = Last fetch: { $ms } ms
+diagnostics-fetch-none = Last fetch: none
+
+## Detail
+
+detail-loading = Loading...
+detail-unavailable = Details are unavailable.
+box-score-runs = R
+box-score-hits = H
+box-score-errors = E
+
+## Sections
+
+no-scores = No games today
+no-standings = Standings are not available
+settings-hint = Settings are changed in the configuration file and applied on restart
+setting-days = Days displayed
+setting-favorites = Favorite teams
+setting-refresh = Refresh
+setting-display = Display
+setting-ambient-audio = Ambient audio
+setting-remote = Remote control
+setting-remote-port = Port { $port }
+setting-cache-dir = Cache directory
+setting-config-file = Configuration file
+setting-log-level = Log level
+setting-locale = Language
+setting-none = None
+setting-never = Never
+setting-refresh-interval =
+    { $seconds ->
+        [one] Every second
+       *[other] Every { $seconds } seconds
+    }
+setting-vsync = V-sync
+setting-antialiasing = Antialiasing
+setting-frame-rate = Frame rate
+setting-on = On
+setting-off = Off
+setting-frame-rate-limit = Up to { $fps } frames per second
+setting-antialiasing-samples = { $samples }x multisampling
+setting-unlimited = Unlimited
+setting-windowed = Windowed
+setting-fullscreen = Fullscreen
+setting-memory-only = Memory only
@@ -0,0 +1,93 @@
+//! Navigation with gamepads. Gamepads are polled on a background thread, and their input is delivered to the event
+//! loop as `UiEvent::Gamepad`, so that the UI can be driven from a couch like a streaming device.
+
+use crate::gl_utils::FocusDirection;
+use crate::loader::UiEvent;
+use gilrs::{Axis, Button, EventType, Gilrs};
+use glium::glutin::event_loop::EventLoopProxy;
+use log::{debug, error};
+use std::thread;
+use std::time::Duration;
+
+/// The time between polls of the connected gamepads.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+/// The deflection of a stick, from -1 to 1, beyond which it is considered pushed in that direction.
+const STICK_THRESHOLD: f32 = 0.5;
+
+/// An enumeration of the actions a gamepad can perform on the UI.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GamepadInput {
+    /// The D-pad or left stick was pushed in the given direction.
+    Move(FocusDirection),
+    /// The A button was pressed.
+    Select,
+    /// The B button was pressed.
+    Back,
+}
+
+/// Starts the background thread, which delivers the input of every connected gamepad to the event loop of the given
+/// proxy. Failures to access gamepads are logged, leaving the UI usable without them.
+pub fn spawn(proxy: EventLoopProxy<UiEvent>) {
+    let spawned = thread::Builder::new().name("gamepad".to_owned()).spawn(move || {
+        let mut gilrs = match Gilrs::new() {
+            Ok(gilrs) => gilrs,
+            Err(ex) => {
+                error!("Could not access gamepads:\n{}", ex);
+                return;
+            }
+        };
+        let mut stick_x = None;
+        let mut stick_y = None;
+        loop {
+            while let Some(event) = gilrs.next_event() {
+                let input = match event.event {
+                    EventType::ButtonPressed(Button::DPadUp, _) => Some(GamepadInput::Move(FocusDirection::Up)),
+                    EventType::ButtonPressed(Button::DPadDown, _) => Some(GamepadInput::Move(FocusDirection::Down)),
+                    EventType::ButtonPressed(Button::DPadLeft, _) => Some(GamepadInput::Move(FocusDirection::Left)),
+                    EventType::ButtonPressed(Button::DPadRight, _) => Some(GamepadInput::Move(FocusDirection::Right)),
+                    EventType::ButtonPressed(Button::South, _) => Some(GamepadInput::Select),
+                    EventType::ButtonPressed(Button::East, _) => Some(GamepadInput::Back),
+                    EventType::AxisChanged(Axis::LeftStickX, value, _) => {
+                        push_stick(&mut stick_x, value, FocusDirection::Left, FocusDirection::Right)
+                    }
+                    EventType::AxisChanged(Axis::LeftStickY, value, _) => {
+                        push_stick(&mut stick_y, value, FocusDirection::Down, FocusDirection::Up)
+                    }
+                    _ => None,
+                };
+                if let Some(input) = input {
+                    debug!("gamepad {} input {:?}", event.id, input);
+                    if proxy.send_event(UiEvent::Gamepad(input)).is_err() {
+                        debug!("ending gamepad");
+                        return;
+                    }
+                }
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    });
+    if let Err(ex) = spawned {
+        error!("Could not create gamepad thread:\n{}", ex);
+    }
+}
+
+/// Records the given deflection of a stick axis, whose previous direction is given, and determines the movement it
+/// causes. A movement is only caused when the stick is first pushed past `STICK_THRESHOLD`, so that holding the stick
+/// moves the focus once.
+fn push_stick(
+    previous: &mut Option<FocusDirection>,
+    value: f32,
+    negative: FocusDirection,
+    positive: FocusDirection,
+) -> Option<GamepadInput> {
+    let direction = if value > STICK_THRESHOLD {
+        Some(positive)
+    } else if value < -STICK_THRESHOLD {
+        Some(negative)
+    } else {
+        None
+    };
+    let pushed = if direction != *previous { direction } else { None };
+    *previous = direction;
+    pushed.map(GamepadInput::Move)
+}
@@ -1,66 +1,331 @@
 //! OpenGL implementation of the MLB UI.
 
+use crate::access::{AccessNode, AccessTree};
+use crate::decode::DecodedImage;
+use crate::diagnostics::{Diagnostics, TextureStats};
 use crate::gl_utils;
-use crate::gl_utils::{FocusDirection, GlyphBrush, ImageVertex, Vertex};
-use dss_mlb::MlbGameClientInfo;
+use crate::gl_utils::{
+    Align, Arrangement, Bounds, FocusDirection, FocusEdge, FocusManager, Focusable, GlyphBrush, ImageVertex, Length,
+    NinePatch, RichText, RoundedRectPainter, SpriteBatch, SpriteBatcher, TextStyle, Transform, Vertex, Widget,
+};
+use crate::league::{self, LeagueState, LeagueUiInfo};
+use crate::loader::UiEvent;
+use crate::locale::Strings;
+use crate::menu::{self, MenuSection, MENU_TOP, SECTION_TOP};
+use crate::options::Options;
+use crate::rail::{
+    self, calc_tile_bounds, calc_tile_location_percentage, fit_aspect, text_scale, BadgeKind, Caption, GridFocus, Rail,
+    RailGrid, RailPainter, TileBadge, BADGE_TEXT_SIZE, FOCUSED_TILE_SCALE, LEFT_INDENT, REFERENCE_ASPECT, TILE_SCALE,
+    TILE_Y_PADDING, TOP_INDENT, X_PAGE_SIZE, Y_PAGE_SIZE,
+};
+use crate::session::Session;
+use crate::shaders;
+use crate::standings::StandingsRail;
+use crate::stories::StoriesRail;
+use crate::theme::{Color, Theme};
+use crate::tween::{self, Easing, FrameClock, Tween};
+use crate::video::VideoPlayer;
+use accesskit::Role;
+use chrono::{DateTime, Datelike, Local, NaiveDate, Timelike};
+use dss_core::AggregateResult;
+use dss_mlb::detail::MlbGameDetail;
+use dss_mlb::standings::MlbDivisionStandings;
+use dss_mlb::{MlbGameClientInfo, MlbGameState, MlbLoadProgress};
+use glium::backend::Facade;
+use glium::glutin::event_loop::EventLoopProxy;
 use glium::index::{NoIndices, PrimitiveType};
-use glium::texture::{RawImage2d, Texture2d};
-use glium::{Display, DrawParameters, Frame, Program, Surface, VertexBuffer};
-use glyph_brush::{Section, Text};
+use glium::texture::{RawImage2d, SrgbTexture2d};
+use glium::{DrawParameters, Frame, Program, Surface, VertexBuffer};
+use glyph_brush::ab_glyph::Rect;
+use glyph_brush::{HorizontalAlign, Layout, Section, Text, VerticalAlign};
 use log::{debug, error};
+use std::fs;
+use std::ops::{Deref, DerefMut};
+use std::path::Path;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
 
 /// The bytes for the image to use for a game if one cannot be retrieved.
 const DEFAULT_RAW: &[u8; 22931] = include_bytes!("default.jpg");
-/// The number of games to display at a time for each day.
-const X_PAGE_SIZE: usize = 5;
+/// The height in pixels of the screen at which text is displayed at its nominal size.
+const REFERENCE_HEIGHT: f32 = 1080.0;
+/// The smallest ratio of displayed to nominal text size per unit of the window scale factor, which keeps text legible in
+/// small windows.
+const MIN_TEXT_RATIO: f32 = 0.5;
+/// The number of days after today that can be added to the grid by moving up past its most recent day.
+const UPCOMING_DAYS: i64 = 7;
+/// The number of pages beyond those displayed in each direction for which game images are retrieved in advance.
+const IMAGE_PREFETCH_PAGES: usize = 1;
+/// The percentage from the left of the screen of the vertical paging indicator.
+const INDICATOR_LEFT: f32 = 0.975;
+/// The percentage of the screen taken up by the width of the vertical paging indicator.
+const INDICATOR_WIDTH: f32 = 0.005;
+/// The nominal size in pixels of each dot showing the pages of games in a row with more games than fit on the screen.
+const PAGE_DOT_SIZE: f32 = 8.0;
+/// The nominal size in pixels of the spacing between the page dots of a row.
+const PAGE_DOT_GAP: f32 = 6.0;
+/// The percentage of the screen between the bottom of the focused games of a row and the center of its page dots.
+const PAGE_DOT_OFFSET: f32 = 0.0125;
 /// The percentage of the screen taken up by the border of a focused game.
 const BORDER_SCALE: f32 = 0.175;
-/// The percentage of the screen taken up by a line in the border of a focused game.
+/// The fraction of the size of the border of a focused game taken up by its line.
 const BORDER_LINE_SCALE: f32 = 0.015;
 /// The percentage of the screen for horizontal and vertical padding from the focused game image.
 const BORDER_OFFSET: f32 = 0.025;
-/// The percentage from the left of the screen at which to start displaying game images.
-const LEFT_INDENT: f32 = 0.05;
-/// The percentage from the top of the screen at which to start displaying game images.
-const TOP_INDENT: f32 = 0.24;
-/// The percentage of the screen taken up by a focused game image.
-const FOCUSED_GAME_SCALE: f32 = 0.15;
-/// The percentage of the screen for horizontal spacing between game images (assuming both are focused).
-const GAME_X_PADDING: f32 = 0.0375;
-/// The percentage of the screen for vertical spacing between game images (assuming both are focused).
-const GAME_Y_PADDING: f32 = 0.05;
-/// The percentage of the screen taken up by a non-focused game image.
-const GAME_SCALE: f32 = 0.10;
-/// The percentage of the screen added to horizontal and vertical padding to account for non-focused images.
-const NON_FOCUSED_OFFSET: f32 = 0.025;
-
-/// The manager of the MLB UI responsible for rendering implementation and ownership of the backing data.
-pub struct MlbGlUi {
+/// The nominal distance in pixels that the background pans from its center as the focus moves to an edge of the screen.
+const PARALLAX_DISTANCE: f32 = 12.0;
+/// The time over which the badge overlaid on games in progress fades out and back in.
+const LIVE_PULSE_PERIOD: Duration = Duration::from_millis(1500);
+/// The smallest opacity of the badge overlaid on games in progress, relative to its theme color.
+const LIVE_PULSE_MIN_OPACITY: f32 = 0.35;
+/// The nominal size in pixels of the space between the text of a badge and its edges, and between a badge and the edges
+/// of its game.
+const BADGE_PADDING: f32 = 6.0;
+/// The largest number of lines of the summary below the focused game, which only takes the lines that fit above the
+/// label of the next rail.
+const SUMMARY_LINES: usize = 2;
+/// The percentage of the screen height taken up by the label above each rail.
+const RAIL_LABEL_HEIGHT: f32 = 0.05;
+/// The nominal size in pixels of the horizontal spacing between the label of a rail and the spinner beside it, which is
+/// scaled with the text.
+const DATE_LABEL_GAP: f32 = 24.0;
+/// The time for which the banner is displayed after games could not be retrieved.
+const BANNER_DURATION: Duration = Duration::from_secs(6);
+/// The time for which the banner is displayed on launch after the previous run crashed.
+const CRASH_BANNER_DURATION: Duration = Duration::from_secs(12);
+/// The time over which the banner slides into and out of view.
+const BANNER_SLIDE_DURATION: Duration = Duration::from_millis(300);
+/// The percentage from the top of the screen at which the banner ends.
+const BANNER_BOTTOM: f32 = 0.92;
+/// The nominal size in pixels of the spinner displayed beside the label of a day being retried.
+const SPINNER_SIZE: f32 = 18.0;
+/// The time taken by the spinner displayed beside the label of a day being retried to complete a turn.
+const SPINNER_PERIOD: Duration = Duration::from_millis(1000);
+/// The character displayed after the search text while it is being typed.
+const SEARCH_CURSOR: &str = "_";
+/// The number of games displayed at a time in the scores section.
+const SCORES_PAGE_SIZE: usize = 14;
+/// The percentage of the screen taken up by each team column of the scores section.
+const SCORES_TEAM_WIDTH: f32 = 0.25;
+/// The percentage of the screen taken up by each score column of the scores section.
+const SCORES_SCORE_WIDTH: f32 = 0.05;
+/// The percentage of the screen taken up by each row of the scores section.
+const SCORES_ROW_HEIGHT: f32 = 0.045;
+/// The percentage from the top of the screen at which to display the current time.
+const CLOCK_TOP: f32 = 0.05;
+/// The percentage from the top of the screen at which to display the tabs of each league.
+const TAB_BAR_TOP: f32 = 0.12;
+/// The identifier of the menu in the tree exposed to screen readers, followed by the identifier of each section.
+const ACCESS_MENU_ID: u64 = 2;
+/// The identifier of the content of the displayed screen in the tree exposed to screen readers, followed by the
+/// identifier of each of its items.
+const ACCESS_CONTENT_ID: u64 = 16;
+/// The spacing between the identifiers of rails in the tree exposed to screen readers, each followed by the identifier
+/// of each of its items.
+const ACCESS_RAIL_STRIDE: u64 = 1 << 20;
+/// The percentage from the left of the screen at which to display the loading progress bar.
+const PROGRESS_LEFT: f32 = 0.3;
+/// The percentage from the top of the screen at which to display the loading progress bar.
+const PROGRESS_TOP: f32 = 0.6;
+/// The percentage of the screen taken up by the width of the loading progress bar.
+const PROGRESS_WIDTH: f32 = 0.4;
+/// The percentage of the screen taken up by the height of the loading progress bar.
+const PROGRESS_HEIGHT: f32 = 0.01;
+/// The percentage from the top of the screen at which to display the loading progress bar below the grid.
+const GRID_PROGRESS_TOP: f32 = 0.95;
+/// The percentage of the screen taken up by the game image in the detail view.
+const DETAIL_IMAGE_SCALE: f32 = 0.4;
+/// The percentage from the left of the screen at which to start displaying text in the detail view.
+const DETAIL_TEXT_INDENT: f32 = 0.5;
+/// The percentage from the top of the screen at which to start displaying the box score in the detail view.
+const BOX_SCORE_TOP: f32 = 0.7;
+/// The percentage of the screen taken up by the team column of the box score.
+const BOX_SCORE_TEAM_WIDTH: f32 = 0.15;
+/// The percentage of the screen taken up by each inning and total column of the box score.
+const BOX_SCORE_COLUMN_WIDTH: f32 = 0.025;
+/// The percentage of the screen taken up by each row of the box score.
+const BOX_SCORE_ROW_HEIGHT: f32 = 0.04;
+
+/// An enumeration of the retrieval states of the detail of a game.
+enum DetailState {
+    /// The detail has not been requested.
+    NotLoaded,
+    /// The detail has been requested and not yet delivered.
+    Loading,
+    /// The detail has been retrieved.
+    Loaded(MlbGameDetail),
+    /// The detail could not be retrieved.
+    Unavailable,
+}
+
+/// An enumeration of the parts of a panel of text, which identify them in its arrangement.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum PanelPart {
+    /// The background behind the whole panel.
+    Background,
+    /// The line of text at the given index.
+    Line(usize),
+}
+
+/// An enumeration of the messages the banner can display.
+enum Banner {
+    /// Games could not be retrieved.
+    Unreachable,
+    /// The previous run crashed, with a report saved to the given path.
+    Crashed(String),
+}
+
+/// An enumeration of the screens the MLB UI can display.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum MlbScreen {
+    /// The splash displayed until the days being retrieved are known.
+    Loading,
+    /// The grid of games for each day.
+    Grid,
+    /// The full-screen detail view of the focused game.
+    Detail,
+    /// The full-screen recap video of the focused game.
+    Video,
+    /// The scoreboard of today's games.
+    Scores,
+    /// The full standings of every division.
+    Standings,
+    /// The settings the application is running with.
+    Settings,
+}
+
+impl MlbScreen {
+    /// Determines the name by which the screen is described in input replay scripts.
+    fn name(self) -> &'static str {
+        match self {
+            MlbScreen::Loading => "loading",
+            MlbScreen::Grid => "grid",
+            MlbScreen::Detail => "detail",
+            MlbScreen::Video => "video",
+            MlbScreen::Scores => "scores",
+            MlbScreen::Standings => "standings",
+            MlbScreen::Settings => "settings",
+        }
+    }
+}
+
+/// A container for the position of the focus and the paging of the grid, against which input replay scripts are
+/// verified.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FocusState {
+    /// The name of the displayed screen.
+    pub screen: &'static str,
+    /// The focused section of the menu, or None if the menu is not focused.
+    pub menu: Option<MenuSection>,
+    pub tab: usize,
+    pub focused_row: usize,
+    /// The index of the focused item within its rail, counting items paged out of view.
+    pub focused_index: usize,
+    pub begin_row: usize,
+    /// The index of the first displayed item of the focused rail.
+    pub begin_index: usize,
+}
+
+/// The state of the MLB UI: the data it displays, the displayed screen, and the position of the focus. It is navigated
+/// without an OpenGL context, so that input can be replayed against it without rendering. The grids of other leagues
+/// are displayed in tabs beside the MLB grid, each keeping its data and focus while another is displayed.
+pub struct MlbUiState {
     ui_info: MlbUiInfo,
+    leagues: Vec<LeagueUiInfo>,
+    /// The displayed tab: 0 for the MLB grid, otherwise the grid of the league before it in `leagues`.
+    tab: usize,
+    theme: Theme,
+    strings: Strings,
+    scale_factor: f32,
+    text_ratio: f32,
+    screen: MlbScreen,
+    /// The screens to return to from the displayed screen, most recent last.
+    history: Vec<MlbScreen>,
+    /// The focus among the sections of the menu, none of which is focused unless the menu is.
+    menu_focus: FocusManager<MenuSection>,
+    /// The focus among the games of the scoreboard.
+    scores_focus: FocusManager<usize>,
+    settings: Vec<(String, String)>,
+    load_progress: Option<MlbLoadProgress>,
+    detail_scroll: usize,
+    searching: bool,
+    /// The date being chosen in the date picker, or None if the picker is closed.
+    date_picker: Option<NaiveDate>,
+    banner: Banner,
+    /// The fraction of the banner in view, animated as it is displayed and dismissed.
+    banner_slide: Tween,
+    video: Option<VideoPlayer>,
+    image_requests: Vec<(u64, String)>,
+    /// The days added to the grid whose games are yet to be requested.
+    day_requests: Vec<NaiveDate>,
+    /// The largest size in pixels at which the image of a game is displayed, to which retrieved images are downscaled,
+    /// or None before the first call to draw.
+    image_size: Option<(u32, u32)>,
+    proxy: Option<EventLoopProxy<UiEvent>>,
+    border: Tween<(f32, f32)>,
+    clock: FrameClock,
+    animating: bool,
+    /// The time between frames drawn while an animation is in progress.
+    frame_interval: Duration,
+    /// The aspect ratio of the area in which content is laid out, or None to lay out content across the window.
+    content_aspect: Option<f32>,
+    /// The fraction of each dimension of the window by which its edges are inset before content is laid out.
+    safe_area: f32,
+    /// The area of the window in which content was laid out by the last call to draw.
+    viewport: glium::Rect,
+    /// The height of the window in pixels as of the last call to draw.
+    window_height: u32,
+    started: Instant,
+    /// The time displayed as the current time and as the time games were retrieved, or None to display the time as it
+    /// passes.
+    fixed_time: Option<DateTime<Local>>,
+    /// The saved session being restored as the days it refers to are retrieved.
+    restoring: Option<Session>,
+    /// The measurements displayed by the diagnostics overlay, or None if it is hidden.
+    diagnostics: Option<Diagnostics>,
+}
+
+/// The manager of the MLB UI responsible for rendering its state, which it dereferences to, with OpenGL.
+pub struct MlbGlUi {
+    state: MlbUiState,
     image_program: Program,
     image_square_vertices: VertexBuffer<ImageVertex>,
-    background_texture: Texture2d,
+    background_texture: SrgbTexture2d,
+    /// The video looped in place of the background texture, once its first frame has been decoded.
+    background_video: Option<VideoPlayer>,
     rect_program: Program,
-    border_vertices: VertexBuffer<Vertex>,
+    rounded_rect: RoundedRectPainter,
+    sprites: SpriteBatcher,
+    /// The nine-patch drawn over the focused game in place of the focus border, if the theme has one.
+    focus_frame: Option<NinePatch>,
+    indicator_track_vertices: VertexBuffer<Vertex>,
+    indicator_thumb_vertices: VertexBuffer<Vertex>,
+}
+
+impl Deref for MlbGlUi {
+    type Target = MlbUiState;
+
+    fn deref(&self) -> &MlbUiState {
+        &self.state
+    }
+}
+
+impl DerefMut for MlbGlUi {
+    fn deref_mut(&mut self) -> &mut MlbUiState {
+        &mut self.state
+    }
 }
 
 impl MlbGlUi {
-    /// Initializes the MLB UI manager with the given information.
+    /// Initializes the MLB UI manager, rendering the given state with the given display. The background is decoded from
+    /// the given bytes, or the embedded background if None or they cannot be decoded, and is replaced by the background
+    /// video of the theme of the state, if any, while it plays.
     ///
     /// # Errors
     /// Panics if the given display cannot be used to create UI elements.
-    pub fn init(ui_info: MlbUiInfo, display: &Display) -> Self {
-        let image_program = Program::from_source(
-            display,
-            gl_utils::IMAGE_VERTEX_SHADER_SRC,
-            gl_utils::IMAGE_FRAGMENT_SHADER_SRC,
-            None,
-        )
-        .unwrap_or_else(|ex| {
-            let msg = "Could not create OpenGL image program";
-            error!("{}:\n{}", msg, ex);
-            panic!("{}.", msg);
-        });
+    pub fn init(display: &dyn Facade, state: MlbUiState, background: Option<Vec<u8>>) -> Self {
+        let theme = &state.theme;
+        let image_program = shaders::IMAGE.compile(display);
         let image_square_shape = vec![
             ImageVertex {
                 position: [-1.0, -1.0],
@@ -85,145 +350,58 @@ impl MlbGlUi {
             panic!("{}.", msg);
         });
 
-        let background_rgba = image::load_from_memory(include_bytes!("background.jpg"))
-            .unwrap_or_else(|ex| {
-                let msg = "Could not load background image";
-                error!("{}:\n{}", msg, ex);
-                panic!("{}.", msg);
+        let background_rgba = background
+            .and_then(|bytes| {
+                image::load_from_memory(&bytes)
+                    .map_err(|ex| error!("Could not decode background image, using default:\n{}", ex))
+                    .ok()
+            })
+            .unwrap_or_else(|| {
+                image::load_from_memory(include_bytes!("background.jpg")).unwrap_or_else(|ex| {
+                    let msg = "Could not load background image";
+                    error!("{}:\n{}", msg, ex);
+                    panic!("{}.", msg);
+                })
             })
             .into_rgba();
         let background_dimensions = background_rgba.dimensions();
         let background_image = RawImage2d::from_raw_rgba_reversed(&background_rgba.into_raw(), background_dimensions);
-        let background_texture = Texture2d::new(display, background_image).unwrap_or_else(|ex| {
+        let background_texture = SrgbTexture2d::new(display, background_image).unwrap_or_else(|ex| {
             let msg = "Could not create background texture";
             error!("{}:\n{}", msg, ex);
             panic!("{}.", msg);
         });
-        let rect_program = Program::from_source(
-            display,
-            gl_utils::RECT_VERTEX_SHADER_SRC,
-            gl_utils::RECT_FRAGMENT_SHADER_SRC,
-            None,
-        )
-        .unwrap_or_else(|ex| {
-            let msg = "Could not create OpenGL rect program";
-            error!("{}:\n{}", msg, ex);
-            panic!("{}.", msg);
-        });
-        let border_shape = vec![
-            // left edge
-            Vertex {
-                position: [-1.0, -1.0],
-                color: [0.5, 0.5, 0.5, 1.0],
-            },
-            Vertex {
-                position: [-1.0, 1.0],
-                color: [0.5, 0.5, 0.5, 1.0],
-            },
-            Vertex {
-                position: [-1.0 + BORDER_LINE_SCALE * 2.0, 1.0],
-                color: [0.5, 0.5, 0.5, 1.0],
-            },
-            Vertex {
-                position: [-1.0 + BORDER_LINE_SCALE * 2.0, -1.0],
-                color: [0.5, 0.5, 0.5, 1.0],
-            },
-            Vertex {
-                position: [-1.0 + BORDER_LINE_SCALE * 2.0, 1.0],
-                color: [0.5, 0.5, 0.5, 1.0],
-            },
-            Vertex {
-                position: [-1.0, -1.0],
-                color: [0.5, 0.5, 0.5, 1.0],
-            },
-            // top edge
-            Vertex {
-                position: [-1.0, 1.0 - BORDER_LINE_SCALE * 2.0],
-                color: [0.5, 0.5, 0.5, 1.0],
-            },
-            Vertex {
-                position: [-1.0, 1.0],
-                color: [0.5, 0.5, 0.5, 1.0],
-            },
-            Vertex {
-                position: [1.0, 1.0],
-                color: [0.5, 0.5, 0.5, 1.0],
-            },
-            Vertex {
-                position: [1.0, 1.0 - BORDER_LINE_SCALE * 2.0],
-                color: [0.5, 0.5, 0.5, 1.0],
-            },
-            Vertex {
-                position: [1.0, 1.0],
-                color: [0.5, 0.5, 0.5, 1.0],
-            },
-            Vertex {
-                position: [-1.0, 1.0 - BORDER_LINE_SCALE * 2.0],
-                color: [0.5, 0.5, 0.5, 1.0],
-            },
-            // right edge
-            Vertex {
-                position: [1.0 - BORDER_LINE_SCALE * 2.0, -1.0],
-                color: [0.5, 0.5, 0.5, 1.0],
-            },
-            Vertex {
-                position: [1.0 - BORDER_LINE_SCALE * 2.0, 1.0],
-                color: [0.5, 0.5, 0.5, 1.0],
-            },
-            Vertex {
-                position: [1.0, 1.0],
-                color: [0.5, 0.5, 0.5, 1.0],
-            },
-            Vertex {
-                position: [1.0, -1.0],
-                color: [0.5, 0.5, 0.5, 1.0],
-            },
-            Vertex {
-                position: [1.0, 1.0],
-                color: [0.5, 0.5, 0.5, 1.0],
-            },
-            Vertex {
-                position: [1.0 - BORDER_LINE_SCALE * 2.0, -1.0],
-                color: [0.5, 0.5, 0.5, 1.0],
-            },
-            // bottom edge
-            Vertex {
-                position: [-1.0, -1.0],
-                color: [0.5, 0.5, 0.5, 1.0],
-            },
-            Vertex {
-                position: [-1.0, -1.0 + BORDER_LINE_SCALE * 2.0],
-                color: [0.5, 0.5, 0.5, 1.0],
-            },
-            Vertex {
-                position: [1.0, -1.0 + BORDER_LINE_SCALE * 2.0],
-                color: [0.5, 0.5, 0.5, 1.0],
-            },
-            Vertex {
-                position: [-1.0, -1.0],
-                color: [0.5, 0.5, 0.5, 1.0],
-            },
-            Vertex {
-                position: [1.0, -1.0 + BORDER_LINE_SCALE * 2.0],
-                color: [0.5, 0.5, 0.5, 1.0],
-            },
-            Vertex {
-                position: [1.0, -1.0],
-                color: [0.5, 0.5, 0.5, 1.0],
-            },
-        ];
-        let border_vertices = VertexBuffer::new(display, &border_shape).unwrap_or_else(|ex| {
-            let msg = "Could not create border vertices";
-            error!("{}:\n{}", msg, ex);
-            panic!("{}.", msg);
+        let rect_program = shaders::RECT.compile(display);
+        let background_video = match (&theme.background_video, &state.proxy) {
+            (Some(url), Some(proxy)) => VideoPlayer::start(url, true, proxy.clone())
+                .map_err(|ex| error!("Could not play background video {}, using image:\n{}", url, ex))
+                .ok(),
+            _ => None,
+        };
+        let rounded_rect = RoundedRectPainter::init(display);
+        let sprites = SpriteBatcher::init(display);
+        let focus_frame = theme.focus_frame.as_ref().and_then(|path| {
+            fs::read(path)
+                .map_err(|ex| ex.to_string())
+                .and_then(|bytes| DecodedImage::decode(&bytes, None, None).map_err(|ex| ex.to_string()))
+                .map(|image| NinePatch::init(display, image.into_texture(display), theme.focus_frame_inset))
+                .map_err(|ex| error!("Could not load focus frame {}, using border:\n{}", path.display(), ex))
+                .ok()
         });
+        let indicator_track_vertices = rect_vertices(display, theme.track_color);
+        let indicator_thumb_vertices = rect_vertices(display, theme.thumb_color);
         MlbGlUi {
-            ui_info,
+            state,
             image_program,
             image_square_vertices,
             background_texture,
+            background_video,
             rect_program,
-            border_vertices,
+            rounded_rect,
+            sprites,
+            focus_frame,
+            indicator_track_vertices,
+            indicator_thumb_vertices,
         }
     }
 
@@ -231,19 +409,69 @@ impl MlbGlUi {
     ///
     /// # Errors
     /// Panics if the given target cannot be used to render the MLB UI.
-    pub fn draw(&mut self, display: &Display, target: &mut Frame, text_brush_option: Option<&mut GlyphBrush>) {
-        let screen_dims = display.get_framebuffer_dimensions();
-        let screen_width = screen_dims.0 as f32;
-        let screen_height = screen_dims.1 as f32;
+    pub fn draw(&mut self, display: &dyn Facade, target: &mut Frame, text_brush_option: Option<&mut GlyphBrush>) {
+        self.draw_at(display, target, text_brush_option, Instant::now());
+    }
+
+    /// Draws the MLB UI with the given parameters, animating as of the given time.
+    ///
+    /// # Errors
+    /// Panics if the given target cannot be used to render the MLB UI.
+    pub fn draw_at(
+        &mut self,
+        display: &dyn Facade,
+        target: &mut Frame,
+        text_brush_option: Option<&mut GlyphBrush>,
+        now: Instant,
+    ) {
+        // content is laid out within the viewport, letterboxed by the background that covers the rest of the window
+        let window_dims = target.get_dimensions();
+        let window_width = window_dims.0 as f32;
+        let window_height = window_dims.1 as f32;
+        self.state.window_height = window_dims.1;
+        self.state.viewport = gl_utils::content_area(window_dims, self.state.content_aspect, self.state.safe_area);
+        let screen_width = self.state.viewport.width as f32;
+        let screen_height = self.state.viewport.height as f32;
+        self.state.animating = false;
+        self.state.clock.tick(now);
+        self.state.banner_slide.advance(self.state.clock.delta());
+        self.state.animating |= !self.state.banner_slide.is_finished();
+        self.state.text_ratio = calc_text_ratio(screen_height, self.state.scale_factor);
+        let (image_x_scale, image_y_scale) =
+            fit_aspect(DETAIL_IMAGE_SCALE.max(FOCUSED_TILE_SCALE), screen_width, screen_height);
+        self.state.image_size = Some((
+            (image_x_scale * screen_width).ceil() as u32,
+            (image_y_scale * screen_height).ceil() as u32,
+        ));
 
+        let background_texture = match self
+            .background_video
+            .as_mut()
+            .and_then(|video| video.get_texture(display))
+        {
+            Some(texture) => texture,
+            None => &self.background_texture,
+        };
+        let (background_width, background_height) = background_texture.dimensions();
+        let (background_x_scale, background_y_scale) = cover_scale(
+            background_width as f32 / background_height as f32,
+            window_width / window_height,
+        );
+        // the background pans opposite to the focus, enlarged so that its edges stay off screen
+        let parallax = 2.0 * text_scale(PARALLAX_DISTANCE, self.state.text_ratio);
+        let parallax_scale = 1.0 + parallax / window_width.min(window_height);
+        let (border_x, border_y) = self.state.border.value();
+        let focus_x = (border_x + BORDER_SCALE / 2.0) * 2.0 - 1.0;
+        let focus_y = (border_y + BORDER_SCALE / 2.0) * 2.0 - 1.0;
         let background_uniforms = uniform! {
-            matrix: [
-                [1.0, 0.0, 0.0, 0.0],
-                [0.0, 1.0, 0.0, 0.0],
-                [0.0, 0.0, 1.0, 0.0],
-                [0.0 , 0.0, 0.0, 1.0f32],
-            ],
-            tex: &self.background_texture,
+            matrix: Transform::new()
+                .scale(background_x_scale * parallax_scale, background_y_scale * parallax_scale)
+                .translate_pixels(-focus_x * parallax / 2.0, -focus_y * parallax / 2.0, window_width, window_height)
+                .matrix(),
+            tex: background_texture,
+            alpha: 1.0f32,
+            size: [0.0, 0.0f32],
+            radius: 0.0f32,
         };
         target
             .draw(
@@ -260,248 +488,2937 @@ impl MlbGlUi {
             });
         debug!("MLB background drawn");
 
-        let focused_day = self.ui_info.focused_day;
-        let focused_index = self.ui_info.focused_index;
-        let (focused_translate_x, focused_translate_y) =
-            calc_game_location_percentage(true, focused_index as f32, focused_day as f32);
-        let x_offset = -1.0 + (focused_translate_x + BORDER_SCALE / 2.0) * 2.0 - BORDER_OFFSET;
-        let y_offset = 1.0 - (focused_translate_y + BORDER_SCALE / 2.0) * 2.0 + BORDER_OFFSET;
-        let border_uniforms = uniform! {
-            matrix: [
-                [BORDER_SCALE, 0.0, 0.0, 0.0],
-                [0.0, BORDER_SCALE, 0.0, 0.0],
-                [0.0, 0.0, BORDER_SCALE, 0.0],
-                [x_offset, y_offset, 0.0, 1.0f32],
-            ]
-        };
-        target
-            .draw(
-                &self.border_vertices,
-                &NoIndices(PrimitiveType::TrianglesList),
-                &self.rect_program,
-                &border_uniforms,
-                &DrawParameters::default(),
-            )
-            .unwrap_or_else(|ex| {
-                let msg = "Target could not draw selected border";
-                error!("{}:\n{}", msg, ex);
-                panic!("{}.", msg);
-            });
-        debug!("MLB focused border drawn");
-
-        for (row, day) in self.ui_info.days.iter_mut().enumerate() {
-            for i in day.begin_index..(day.begin_index + X_PAGE_SIZE) {
-                let col = i - day.begin_index;
-                if let Some(game) = day.games.get_mut(i) {
-                    let x = col as f32;
-                    let y = row as f32;
-                    let (game_scale, translate_x, translate_y) = if row == focused_day && col == focused_index {
-                        let game_scale = FOCUSED_GAME_SCALE;
-                        let (translate_x, translate_y) = calc_game_location_percentage(true, x, y);
-                        (game_scale, translate_x, translate_y)
-                    } else {
-                        let game_scale = GAME_SCALE;
-                        let (translate_x, translate_y) = calc_game_location_percentage(false, x, y);
-                        (game_scale, translate_x, translate_y)
-                    };
-
-                    let x_offset = -1.0 + (translate_x + game_scale / 2.0) * 2.0;
-                    let y_offset = 1.0 - (translate_y + game_scale / 2.0) * 2.0;
-                    let game_uniforms = uniform! {
-                        matrix: [
-                            [game_scale, 0.0, 0.0, 0.0],
-                            [0.0, game_scale, 0.0, 0.0],
-                            [0.0, 0.0, game_scale, 0.0],
-                            [x_offset, y_offset, 0.0, 1.0f32],
-                        ],
-                        tex: game.get_texture(&display),
-                    };
-                    target
-                        .draw(
-                            &self.image_square_vertices,
-                            &NoIndices(PrimitiveType::TriangleStrip),
-                            &self.image_program,
-                            &game_uniforms,
-                            &DrawParameters::default(),
-                        )
-                        .unwrap_or_else(|ex| {
-                            let msg = "Target could not draw game";
-                            error!("{}:\n{}", msg, ex);
-                            panic!("{}.", msg);
-                        });
-                    debug!("MLB game at {}, {} drawn", col, row);
-                }
+        let mut text_brush_option = text_brush_option;
+        if let Some(text_brush) = text_brush_option.as_deref_mut() {
+            text_brush.set_viewport(Some(self.state.viewport));
+        }
+        let text_brush = text_brush_option.as_deref_mut();
+        match self.state.screen {
+            MlbScreen::Loading => self.draw_loading(display, target, text_brush, screen_width, screen_height),
+            MlbScreen::Grid => self.draw_grid(display, target, text_brush, screen_width, screen_height, now),
+            MlbScreen::Detail => self.draw_detail(display, target, text_brush, screen_width, screen_height),
+            MlbScreen::Video => self.draw_video(display, target, screen_width, screen_height),
+            MlbScreen::Scores | MlbScreen::Standings | MlbScreen::Settings => {
+                self.state
+                    .draw_section(display, target, text_brush, screen_width, screen_height)
             }
         }
-        debug!("MLB games drawn");
-
         if let Some(text_brush) = text_brush_option {
-            let focused_day_info = &self.ui_info.days[focused_day];
-            if let Some(focused_game) = focused_day_info.games.get(focused_index + focused_day_info.begin_index) {
-                let focused_game = &focused_game.info;
-                let bounds = (FOCUSED_GAME_SCALE * screen_width, 0.05 * screen_height);
-                let x_offset = focused_translate_x * screen_width;
-                let y_offset = (focused_translate_y - 0.05) * screen_height;
-                let text_top_left = (x_offset, y_offset);
-                text_brush.queue(Section {
-                    text: vec![Text::new(&focused_game.title)
-                        .with_color([1.0, 1.0, 1.0, 1.0f32])
-                        .with_scale(32.0)],
-                    screen_position: text_top_left,
-                    bounds,
-                    ..Section::default()
-                });
-                let x_offset = focused_translate_x * screen_width;
-                let y_offset = (focused_translate_y + FOCUSED_GAME_SCALE + 0.025) * screen_height;
-                let text_top_left = (x_offset, y_offset);
-                text_brush.queue(Section {
-                    text: vec![Text::new(&focused_game.summary)
-                        .with_color([1.0, 1.0, 1.0, 1.0f32])
-                        .with_scale(32.0)],
-                    screen_position: text_top_left,
-                    bounds,
-                    ..Section::default()
-                });
-                text_brush.draw_queued(display, target);
-                debug!("MLB text drawn");
+            if let Some(date) = self.state.date_picker {
+                self.draw_date_picker(display, target, text_brush, date, screen_width, screen_height);
+            }
+            if self.state.screen != MlbScreen::Video {
+                self.draw_banner(display, target, text_brush, screen_width, screen_height);
+            }
+            if let Some(diagnostics) = &self.state.diagnostics {
+                self.draw_diagnostics(display, target, text_brush, diagnostics, screen_width, screen_height);
             }
         }
     }
 
-    /// Moves the focus in the given direction. The visual representation will be updated on the next call to draw.
-    pub fn move_focus(&mut self, direction: FocusDirection) {
-        let info = &mut self.ui_info;
-        let day = &mut info.days[info.focused_day];
-        match direction {
-            FocusDirection::Left => {
-                if info.focused_index > 0 {
-                    info.focused_index -= 1;
-                } else if day.begin_index > 0 {
-                    day.begin_index -= 1;
+    /// Counts the textures held by the UI and estimates the video memory they occupy.
+    fn texture_stats(&self) -> TextureStats {
+        let mut stats = TextureStats::default();
+        stats.add(&self.background_texture);
+        if let Some(focus_frame) = &self.focus_frame {
+            stats.add(focus_frame.texture());
+        }
+        let game_textures = self
+            .ui_info
+            .days
+            .iter()
+            .flat_map(|day| &day.games)
+            .flat_map(|game| game.texture.iter().chain(game.replaced_texture.iter()));
+        for texture in game_textures {
+            stats.add(texture);
+        }
+        for league in &self.state.leagues {
+            league.count_textures(&mut stats);
+        }
+        for video in self.state.video.iter().chain(self.background_video.iter()) {
+            video.count_textures(&mut stats);
+        }
+        stats
+    }
+
+    /// Draws the grid of rails with the given parameters, animating focus changes as of the given time.
+    ///
+    /// # Errors
+    /// Panics if the given target cannot be used to render the grid.
+    fn draw_grid(
+        &mut self,
+        display: &dyn Facade,
+        target: &mut Frame,
+        text_brush_option: Option<&mut GlyphBrush>,
+        screen_width: f32,
+        screen_height: f32,
+        now: Instant,
+    ) {
+        let info = self.state.grid();
+        let grid = info.grid();
+        let focused_row = grid.focused_row;
+        let focused_index = grid.focused_index;
+        let offsets = info.row_offsets();
+        let begin_offset = offsets[grid.begin_row];
+        let focused_collapsed = info.rails().get(focused_row).map(|rail| rail.is_collapsed()) == Some(true);
+        self.state.border.advance(self.state.clock.delta());
+        self.state.border.set_target(calc_tile_location_percentage(
+            true,
+            focused_index as f32,
+            offsets[focused_row] - begin_offset,
+        ));
+        let (focused_translate_x, focused_translate_y) = self.state.border.value();
+        self.state.animating |= !self.state.border.is_finished();
+        let grid_focused = self.state.menu_focus.focused().is_none();
+        let (border_x_scale, border_y_scale) = fit_aspect(BORDER_SCALE, screen_width, screen_height);
+        let border = Bounds::new(
+            focused_translate_x + (BORDER_SCALE - BORDER_OFFSET - border_x_scale) / 2.0,
+            focused_translate_y + (BORDER_SCALE - BORDER_OFFSET - border_y_scale) / 2.0,
+            border_x_scale,
+            border_y_scale,
+        );
+        if grid_focused && !focused_collapsed {
+            match &self.focus_frame {
+                Some(focus_frame) => {
+                    let inset = text_scale(self.state.theme.focus_frame_inset, self.state.text_ratio);
+                    focus_frame.draw(target, &self.image_program, self.state.viewport, border, inset);
                 }
+                None => self.rounded_rect.draw_rounded_outline(
+                    target,
+                    self.state.viewport,
+                    border,
+                    text_scale(self.state.theme.corner_radius, self.state.text_ratio) * BORDER_SCALE
+                        / FOCUSED_TILE_SCALE,
+                    BORDER_LINE_SCALE * (border_x_scale * screen_width).min(border_y_scale * screen_height),
+                    self.state.theme.border_color,
+                ),
+            }
+            debug!("MLB focused border drawn");
+        }
+
+        let mut painter = RailPainter {
+            display,
+            theme: &self.state.theme,
+            strings: &self.state.strings,
+            batch: SpriteBatch::new(screen_width, screen_height),
+            screen_width,
+            screen_height,
+            text_ratio: self.state.text_ratio,
+            delta: self.state.clock.delta(),
+            badges: Vec::new(),
+            animating: false,
+        };
+        let grid = active_grid_mut(self.state.tab, &mut self.state.ui_info, &mut self.state.leagues);
+        let row_scroll = painter.animate(&mut grid.grid_mut().row_scroll, begin_offset);
+        for (row, rail) in grid.rails_mut().into_iter().enumerate() {
+            let y = offsets[row] - row_scroll;
+            if y <= -1.0 || y >= Y_PAGE_SIZE as f32 || rail.is_collapsed() {
+                continue;
+            }
+            let focused = if grid_focused && row == focused_row {
+                Some(rail.begin_index() + focused_index)
+            } else {
+                None
+            };
+            rail.draw(&mut painter, y, focused);
+        }
+        let batch = std::mem::replace(&mut painter.batch, SpriteBatch::new(screen_width, screen_height));
+        self.sprites.flush(display, target, self.state.viewport, batch);
+        debug!("MLB rails drawn");
+
+        let rails = self.state.grid().rails();
+        for (row, rail) in rails.iter().enumerate() {
+            let y = offsets[row] - row_scroll;
+            if y <= -1.0 || y >= Y_PAGE_SIZE as f32 || rail.is_collapsed() || rail.len() <= X_PAGE_SIZE {
+                continue;
+            }
+            self.draw_page_dots(target, rail.len(), rail.begin_index(), y, screen_width, screen_height);
+        }
+
+        let rows_height = offsets[offsets.len() - 1];
+        if rows_height > Y_PAGE_SIZE as f32 {
+            self.draw_indicator(target, rows_height, row_scroll);
+        }
+        if self.state.tab == 0 && self.state.ui_info.days.iter().any(|day| !day.loaded) {
+            self.draw_progress_bar(target, GRID_PROGRESS_TOP);
+        }
+
+        if painter.badges.iter().any(|badge| matches!(badge.kind, BadgeKind::Live)) {
+            painter.animating = true;
+        }
+
+        let rails = self.state.grid().rails();
+        if rails.iter().any(|rail| rail.is_busy()) {
+            painter.animating = true;
+        }
+
+        if let Some(text_brush) = text_brush_option {
+            let live_color = self.state.live_badge_color(now);
+            for badge in &painter.badges {
+                self.draw_badge(target, text_brush, badge, live_color, screen_width, screen_height);
             }
-            FocusDirection::Right => {
-                if info.focused_index < X_PAGE_SIZE - 1 {
-                    info.focused_index += 1;
-                } else if day.begin_index + X_PAGE_SIZE < day.games.len() {
-                    day.begin_index += 1;
+            debug!("MLB badges drawn");
+
+            for (row, rail) in rails.iter().enumerate() {
+                let y = offsets[row] - row_scroll;
+                if y <= -1.0 || y >= Y_PAGE_SIZE as f32 {
+                    continue;
+                }
+                let (_, row_top) = calc_tile_location_percentage(true, 0.0, y);
+                let mut label = rail.label(&self.state.strings);
+                let mut label_color = self.state.theme.secondary_text_color;
+                if rail.is_collapsed() {
+                    label = self.state.strings.format(
+                        "rail-collapsed",
+                        &[("label", label.into()), ("count", rail.len().into())],
+                    );
+                    if grid_focused && row == focused_row {
+                        label_color = self.state.theme.border_color;
+                    }
+                }
+                let section = Section {
+                    text: vec![Text::new(&label)
+                        .with_color(label_color)
+                        .with_scale(text_scale(32.0, self.state.text_ratio))],
+                    screen_position: (
+                        LEFT_INDENT * screen_width,
+                        (row_top - RAIL_LABEL_HEIGHT) * screen_height,
+                    ),
+                    bounds: (0.3 * screen_width, RAIL_LABEL_HEIGHT * screen_height),
+                    ..Section::default()
+                };
+                if rail.is_busy() {
+                    if let Some(label_bounds) = text_brush.glyph_bounds(&section) {
+                        let gap = text_scale(DATE_LABEL_GAP, self.state.text_ratio);
+                        let size = text_scale(SPINNER_SIZE, self.state.text_ratio);
+                        let center = (
+                            label_bounds.max.x + gap + size / 2.0,
+                            (label_bounds.min.y + label_bounds.max.y) / 2.0,
+                        );
+                        self.draw_spinner(target, center, size, now, screen_width, screen_height);
+                    }
                 }
+                text_brush.queue(section);
+                if rail.is_collapsed() {
+                    continue;
+                }
+                let focused = if grid_focused && row == focused_row {
+                    Some(rail.begin_index() + focused_index)
+                } else {
+                    None
+                };
+                rail.queue_text(&painter, text_brush, y, focused);
             }
-            FocusDirection::Up => {
-                if info.focused_day > 0 {
-                    info.focused_day -= 1;
+            debug!("MLB rail labels queued");
+
+            let caption = rails
+                .get(focused_row)
+                .filter(|rail| grid_focused && !rail.is_collapsed())
+                .and_then(|rail| rail.caption(rail.begin_index() + focused_index, &self.state.strings));
+            if let Some(caption) = caption {
+                let title_color = if caption.accent {
+                    self.state.theme.favorite_color
+                } else {
+                    self.state.theme.text_color
+                };
+                // the title takes the line below the border of the tile, as the line above it is taken by the label
+                let scale = text_scale(32.0, self.state.text_ratio);
+                let title_style = TextStyle::new(title_color, scale);
+                let x_offset = focused_translate_x * screen_width;
+                let y_offset = border.bottom * screen_height;
+                let text_top_left = (x_offset, y_offset);
+                let bounds = (
+                    FOCUSED_TILE_SCALE * screen_width,
+                    text_brush.line_height(title_style.font_id(), scale),
+                );
+                let title = text_brush.fit_text(&caption.title, scale, bounds);
+                let filter = if self.state.tab == 0 {
+                    self.state.ui_info.filter.as_str()
+                } else {
+                    ""
+                };
+                let title_text =
+                    highlight_matches(&title, filter)
+                        .into_iter()
+                        .fold(RichText::new(), |text, (part, matched)| {
+                            let color = if matched {
+                                self.state.theme.highlight_color
+                            } else {
+                                title_color
+                            };
+                            text.push(part, title_style.with_color(color))
+                        });
+                text_brush.queue(title_text.section(text_top_left, bounds));
+                let y_offset = y_offset + bounds.1;
+                // the summary takes the lines that fit above the label of the next rail, if it is displayed
+                let next_y = offsets[focused_row + 1] - row_scroll;
+                let bottom = if focused_row + 1 < rails.len() && next_y < Y_PAGE_SIZE as f32 {
+                    calc_tile_location_percentage(true, 0.0, next_y).1 - RAIL_LABEL_HEIGHT
+                } else {
+                    1.0
+                };
+                let lines = ((bottom * screen_height - y_offset) / bounds.1).max(0.0) as usize;
+                if lines > 0 {
+                    let summary = text_brush
+                        .bounded_section(
+                            &caption.summary,
+                            TextStyle::new(self.state.theme.text_color, scale),
+                            FOCUSED_TILE_SCALE * screen_width,
+                            lines.min(SUMMARY_LINES),
+                        )
+                        .with_screen_position((x_offset, y_offset));
+                    text_brush.queue(summary.to_borrowed());
                 }
             }
-            FocusDirection::Down => {
-                if info.focused_day < info.days.len() - 1 {
-                    info.focused_day += 1;
+            if rails.is_empty() {
+                if let Some(status) = self.state.league_status() {
+                    text_brush.queue(Section {
+                        text: vec![Text::new(&status)
+                            .with_color(self.state.theme.secondary_text_color)
+                            .with_scale(text_scale(32.0, self.state.text_ratio))],
+                        screen_position: (LEFT_INDENT * screen_width, TOP_INDENT * screen_height),
+                        bounds: (0.9 * screen_width, 0.1 * screen_height),
+                        ..Section::default()
+                    });
                 }
             }
+            self.state.queue_menu(text_brush, screen_width, screen_height);
+            if !self.state.leagues.is_empty() {
+                self.state.queue_tabs(text_brush, screen_width, screen_height);
+            }
+            if self.state.tab == 0 && (self.state.searching || !self.state.ui_info.filter.is_empty()) {
+                let cursor = if self.state.searching { SEARCH_CURSOR } else { "" };
+                let style = TextStyle::new(self.state.theme.text_color, text_scale(36.0, self.state.text_ratio));
+                let label = format!("{} ", self.state.strings.get("search-label"));
+                let search = RichText::new()
+                    .push(&label, style.with_color(self.state.theme.secondary_text_color))
+                    .push(
+                        &self.state.ui_info.filter,
+                        style.with_color(self.state.theme.highlight_color),
+                    )
+                    .push(cursor, style);
+                text_brush.queue(search.section(
+                    (0.5 * screen_width, TAB_BAR_TOP * screen_height),
+                    (0.3 * screen_width, 0.1 * screen_height),
+                ));
+            }
+            self.state.queue_clock(text_brush, screen_width, screen_height);
+            text_brush.draw_queued(display, target);
+            debug!("MLB text drawn");
         }
+        self.state.animating |= painter.animating;
+        self.state.request_nearby_images();
     }
-}
 
-/// Calculates the percentage of the screen (assuming (0, 0) is the top-left corner) at which the top-left of the
-/// game entry at the given indices should be rendered.
-fn calc_game_location_percentage(focused: bool, x: f32, y: f32) -> (f32, f32) {
-    if focused {
-        let translate_x = LEFT_INDENT + (FOCUSED_GAME_SCALE * x) + (GAME_X_PADDING * x);
-        let translate_y = TOP_INDENT + (FOCUSED_GAME_SCALE * y) + (GAME_Y_PADDING * 2.0 * y);
-        (translate_x, translate_y)
-    } else {
-        let translate_x = LEFT_INDENT
-            + (NON_FOCUSED_OFFSET * (x + 1.0))
-            + (GAME_SCALE * x)
-            + (NON_FOCUSED_OFFSET * x)
-            + (GAME_X_PADDING * x);
-        let translate_y = TOP_INDENT
-            + (NON_FOCUSED_OFFSET * (y + 1.0))
-            + (GAME_SCALE * y)
-            + (NON_FOCUSED_OFFSET * y)
-            + (GAME_Y_PADDING * 2.0 * y);
-        (translate_x, translate_y)
-    }
-}
+    /// Draws the loading splash, with a progress bar filled according to the last reported progress.
+    ///
+    /// # Errors
+    /// Panics if the given target cannot be used to render the splash.
+    fn draw_loading(
+        &self,
+        display: &dyn Facade,
+        target: &mut Frame,
+        text_brush_option: Option<&mut GlyphBrush>,
+        screen_width: f32,
+        screen_height: f32,
+    ) {
+        self.draw_progress_bar(target, PROGRESS_TOP);
 
-/// A container for backing information for a single game.
-struct MlbGameGlInfo {
-    info: MlbGameClientInfo,
-    texture: Option<Texture2d>,
-}
+        if let Some(text_brush) = text_brush_option {
+            let status = match self.state.load_progress {
+                Some(progress) if progress.total > 0 => self.state.strings.format(
+                    "loading-games-progress",
+                    &[
+                        ("completed", progress.completed.into()),
+                        ("total", progress.total.into()),
+                    ],
+                ),
+                _ => self.state.strings.get("loading-games"),
+            };
+            text_brush.queue(Section {
+                text: vec![Text::new(&status)
+                    .with_color(self.state.theme.text_color)
+                    .with_scale(text_scale(36.0, self.state.text_ratio))],
+                screen_position: (0.5 * screen_width, (PROGRESS_TOP - 0.06) * screen_height),
+                bounds: (PROGRESS_WIDTH * screen_width, 0.05 * screen_height),
+                layout: Layout::default().h_align(HorizontalAlign::Center),
+            });
+            text_brush.draw_queued(display, target);
+            debug!("MLB loading text drawn");
+        }
+    }
 
-impl MlbGameGlInfo {
-    /// Lazily initializes the texture for the game represented by this container.
-    fn get_texture(&mut self, display: &Display) -> &Texture2d {
-        if self.texture.is_none() {
-            let image_raw = if let Some(image) = &self.info.image {
-                image.as_slice()
-            } else {
-                DEFAULT_RAW
+    /// Draws a progress bar at the given percentage from the top of the screen, filled according to the last reported
+    /// progress.
+    ///
+    /// # Errors
+    /// Panics if the given target cannot be used to render the progress bar.
+    fn draw_progress_bar(&self, target: &mut Frame, top: f32) {
+        let fraction = match self.state.load_progress {
+            Some(progress) if progress.total > 0 => progress.completed as f32 / progress.total as f32,
+            _ => 0.0,
+        };
+        let bars = [
+            (&self.indicator_track_vertices, PROGRESS_WIDTH),
+            (&self.indicator_thumb_vertices, PROGRESS_WIDTH * fraction),
+        ];
+        for (vertices, width) in bars.iter().filter(|(_, width)| *width > 0.0) {
+            let progress_uniforms = uniform! {
+                matrix: Transform::onto(Bounds::new(PROGRESS_LEFT, top, *width, PROGRESS_HEIGHT)).matrix(),
             };
-            let game_rgba = image::load_from_memory_with_format(image_raw, image::ImageFormat::Jpeg)
+            target
+                .draw(
+                    *vertices,
+                    &NoIndices(PrimitiveType::TriangleStrip),
+                    &self.rect_program,
+                    &progress_uniforms,
+                    &gl_utils::viewport_parameters(self.state.viewport),
+                )
                 .unwrap_or_else(|ex| {
-                    let msg = "Could not create game image from bytes";
+                    let msg = "Target could not draw progress bar";
                     error!("{}:\n{}", msg, ex);
                     panic!("{}.", msg);
-                })
-                .into_rgba();
-            let game_dimensions = game_rgba.dimensions();
-            let game_image = RawImage2d::from_raw_rgba_reversed(&game_rgba.into_raw(), game_dimensions);
-            let game_texture = Texture2d::new(display, game_image).unwrap_or_else(|ex| {
-                let msg = "Could not create game texture";
+                });
+        }
+        debug!("MLB progress bar drawn");
+    }
+
+    /// Draws the given badge over the corner of its game, and queues its text. Badges of games in progress are drawn in
+    /// the given color.
+    ///
+    /// # Errors
+    /// Panics if the given target cannot be used to render the badge.
+    fn draw_badge(
+        &self,
+        target: &mut Frame,
+        text_brush: &mut GlyphBrush,
+        badge: &TileBadge,
+        live_color: Color,
+        screen_width: f32,
+        screen_height: f32,
+    ) {
+        let padding = text_scale(BADGE_PADDING, self.state.text_ratio) * badge.size;
+        let live_text;
+        let (text, color, screen_position, layout) = match &badge.kind {
+            BadgeKind::Score(score) => (
+                score.as_str(),
+                self.state.theme.badge_color,
+                (
+                    badge.bottom_right.0 * screen_width - 2.0 * padding,
+                    badge.bottom_right.1 * screen_height - 2.0 * padding,
+                ),
+                Layout::default_single_line()
+                    .h_align(HorizontalAlign::Right)
+                    .v_align(VerticalAlign::Bottom),
+            ),
+            BadgeKind::Live => (
+                {
+                    live_text = self.state.strings.get("badge-live");
+                    live_text.as_str()
+                },
+                live_color,
+                (
+                    badge.top_left.0 * screen_width + 2.0 * padding,
+                    badge.top_left.1 * screen_height + 2.0 * padding,
+                ),
+                Layout::default_single_line(),
+            ),
+        };
+        let section = Section {
+            text: vec![Text::new(text)
+                .with_color(self.state.theme.text_color)
+                .with_scale(text_scale(BADGE_TEXT_SIZE, self.state.text_ratio) * badge.size)],
+            screen_position,
+            layout,
+            ..Section::default()
+        };
+        if let Some(bounds) = text_brush.glyph_bounds(&section) {
+            self.draw_text_background(target, color, bounds, padding, padding);
+        }
+        text_brush.queue(section);
+    }
+
+    /// Draws a square of the given size in pixels around the given center in pixels, rotated according to the time
+    /// elapsed at the given time.
+    ///
+    /// # Errors
+    /// Panics if the given target cannot be used to render the spinner.
+    fn draw_spinner(
+        &self,
+        target: &mut Frame,
+        center: (f32, f32),
+        size: f32,
+        now: Instant,
+        screen_width: f32,
+        screen_height: f32,
+    ) {
+        let turns = now.saturating_duration_since(self.state.started).as_secs_f32() / SPINNER_PERIOD.as_secs_f32();
+        let spinner_uniforms = uniform! {
+            matrix: Transform::new()
+                .rotate(turns * 2.0 * std::f32::consts::PI)
+                .scale(size / screen_width, size / screen_height)
+                .center_at(center.0 / screen_width, center.1 / screen_height)
+                .matrix(),
+        };
+        target
+            .draw(
+                &self.indicator_thumb_vertices,
+                &NoIndices(PrimitiveType::TriangleStrip),
+                &self.rect_program,
+                &spinner_uniforms,
+                &gl_utils::viewport_parameters(self.state.viewport),
+            )
+            .unwrap_or_else(|ex| {
+                let msg = "Target could not draw spinner";
                 error!("{}:\n{}", msg, ex);
                 panic!("{}.", msg);
             });
-            self.texture = Some(game_texture);
+    }
+
+    /// Draws a rect of the given color, with corners of the given radius in pixels, behind text with the given pixel
+    /// bounds, extending past the text by the given padding.
+    ///
+    /// # Errors
+    /// Panics if the given target cannot be used to render the rect.
+    fn draw_text_background(&self, target: &mut Frame, color: Color, bounds: Rect, padding: f32, radius: f32) {
+        let (screen_width, screen_height) = (self.state.viewport.width as f32, self.state.viewport.height as f32);
+        let rect = Bounds {
+            left: (bounds.min.x - padding) / screen_width,
+            top: (bounds.min.y - padding) / screen_height,
+            right: (bounds.max.x + padding) / screen_width,
+            bottom: (bounds.max.y + padding) / screen_height,
+        };
+        self.rounded_rect
+            .draw_rounded_rect(target, self.state.viewport, rect, radius, color);
+    }
+
+    /// Draws the background and the given lines of text of a panel declared by `panel`, with the given arrangement.
+    ///
+    /// # Errors
+    /// Panics if the given target cannot be used to render the background.
+    fn draw_panel(
+        &self,
+        target: &mut Frame,
+        text_brush: &mut GlyphBrush,
+        lines: &[(&str, Color, f32)],
+        arrangement: &Arrangement<PanelPart>,
+        screen_width: f32,
+        screen_height: f32,
+    ) {
+        if let Some(background) = arrangement.bounds(PanelPart::Background) {
+            let radius = text_scale(self.state.theme.corner_radius, self.state.text_ratio);
+            self.rounded_rect.draw_rounded_rect(
+                target,
+                self.state.viewport,
+                background,
+                radius,
+                self.state.theme.badge_color,
+            );
+        }
+        for (index, (text, color, size)) in lines.iter().enumerate() {
+            if let Some(line) = arrangement.bounds(PanelPart::Line(index)) {
+                text_brush.queue(Section {
+                    text: vec![Text::new(text)
+                        .with_color(*color)
+                        .with_scale(text_scale(*size, self.state.text_ratio))],
+                    screen_position: (line.left * screen_width, line.top * screen_height),
+                    bounds: (screen_width, screen_height),
+                    ..Section::default()
+                });
+            }
         }
-        self.texture.as_ref().unwrap()
     }
-}
 
-impl From<MlbGameClientInfo> for MlbGameGlInfo {
-    fn from(orig: MlbGameClientInfo) -> Self {
-        MlbGameGlInfo {
-            info: orig,
-            texture: None,
+    /// Draws the banner reporting that games could not be retrieved or that the previous run crashed, sliding up from
+    /// the bottom of the screen as it is displayed and dismissed.
+    ///
+    /// # Errors
+    /// Panics if the given target cannot be used to render the banner.
+    fn draw_banner(
+        &self,
+        display: &dyn Facade,
+        target: &mut Frame,
+        text_brush: &mut GlyphBrush,
+        screen_width: f32,
+        screen_height: f32,
+    ) {
+        let shown = self.state.banner_slide.value();
+        if shown <= 0.0 {
+            return;
         }
+        let (banner_text, banner_hint) = match &self.state.banner {
+            Banner::Unreachable => (
+                self.state.strings.get("banner-unreachable"),
+                self.state.strings.get("banner-hint"),
+            ),
+            Banner::Crashed(path) => (
+                self.state.strings.get("banner-crashed"),
+                self.state
+                    .strings
+                    .format("banner-crash-report", &[("path", path.as_str().into())]),
+            ),
+        };
+        let lines = [
+            (banner_text.as_str(), self.state.theme.text_color, 32.0),
+            (banner_hint.as_str(), self.state.theme.secondary_text_color, 24.0),
+        ];
+        let panel = self.state.panel(text_brush, &lines, screen_width, screen_height);
+        let hidden_offset = 1.0 - BANNER_BOTTOM + panel.measure().1;
+        let arrangement = Widget::column(vec![panel])
+            .with_align(Align::End, Align::Center)
+            .arrange(Bounds::new(
+                0.0,
+                0.0,
+                1.0,
+                BANNER_BOTTOM + (1.0 - shown) * hidden_offset,
+            ));
+        self.draw_panel(target, text_brush, &lines, &arrangement, screen_width, screen_height);
+        text_brush.draw_queued(display, target);
+        debug!("MLB banner drawn");
     }
-}
 
-/// A container for backing information for a single day.
-struct DayRowInfo {
-    games: Vec<MlbGameGlInfo>,
-    begin_index: usize,
-}
+    /// Draws the date picker with the given chosen date over the center of the screen.
+    ///
+    /// # Errors
+    /// Panics if the given target cannot be used to render the picker.
+    fn draw_date_picker(
+        &self,
+        display: &dyn Facade,
+        target: &mut Frame,
+        text_brush: &mut GlyphBrush,
+        date: NaiveDate,
+        screen_width: f32,
+        screen_height: f32,
+    ) {
+        let title = self.state.strings.get("date-picker-title");
+        let chosen = self.state.strings.format_date(date, "date-picker-format");
+        let hint = self.state.strings.get("date-picker-hint");
+        let lines = [
+            (title.as_str(), self.state.theme.secondary_text_color, 32.0),
+            (chosen.as_str(), self.state.theme.text_color, 48.0),
+            (hint.as_str(), self.state.theme.secondary_text_color, 24.0),
+        ];
+        let panel = self.state.panel(text_brush, &lines, screen_width, screen_height);
+        let arrangement = Widget::column(vec![panel])
+            .with_align(Align::Center, Align::Center)
+            .arrange(Bounds::new(0.0, 0.0, 1.0, 1.0));
+        self.draw_panel(target, text_brush, &lines, &arrangement, screen_width, screen_height);
+        text_brush.draw_queued(display, target);
+        debug!("MLB date picker drawn");
+    }
 
-impl DayRowInfo {
-    pub fn new(games: Vec<MlbGameGlInfo>) -> Self {
-        DayRowInfo { games, begin_index: 0 }
+    /// Draws the diagnostics overlay with the given measurements in the top left corner of the screen.
+    ///
+    /// # Errors
+    /// Panics if the given target cannot be used to render the overlay.
+    fn draw_diagnostics(
+        &self,
+        display: &dyn Facade,
+        target: &mut Frame,
+        text_brush: &mut GlyphBrush,
+        diagnostics: &Diagnostics,
+        screen_width: f32,
+        screen_height: f32,
+    ) {
+        let textures = self.texture_stats();
+        let frame = self.state.strings.format(
+            "diagnostics-frame",
+            &[
+                (
+                    "ms",
+                    format!("{:.1}", diagnostics.frame_time.as_secs_f32() * 1000.0).into(),
+                ),
+                ("fps", format!("{:.0}", diagnostics.fps).into()),
+            ],
+        );
+        let texture_text = self.state.strings.format(
+            "diagnostics-textures",
+            &[
+                ("count", textures.count.into()),
+                ("mb", format!("{:.1}", textures.bytes as f32 / (1024.0 * 1024.0)).into()),
+            ],
+        );
+        let cache = &diagnostics.loader.cache;
+        let cache_text = match cache.hit_rate() {
+            Some(rate) => self.state.strings.format(
+                "diagnostics-cache",
+                &[
+                    ("rate", format!("{:.0}", rate * 100.0).into()),
+                    ("hits", cache.hits.into()),
+                    ("total", (cache.hits + cache.misses).into()),
+                ],
+            ),
+            None => self.state.strings.get("diagnostics-cache-unused"),
+        };
+        let fetch_text = match diagnostics.loader.last_fetch {
+            Some(latency) => self.state.strings.format(
+                "diagnostics-fetch",
+                &[("ms", format!("{:.0}", latency.as_secs_f32() * 1000.0).into())],
+            ),
+            None => self.state.strings.get("diagnostics-fetch-none"),
+        };
+        let text = [frame, texture_text, cache_text, fetch_text].join("\n");
+        let padding = text_scale(BADGE_PADDING * 2.0, self.state.text_ratio);
+        let section = Section {
+            text: vec![Text::new(&text)
+                .with_color(self.state.theme.text_color)
+                .with_scale(text_scale(BADGE_TEXT_SIZE, self.state.text_ratio))],
+            screen_position: (2.0 * padding, 2.0 * padding),
+            bounds: (screen_width, screen_height),
+            layout: Layout::default(),
+        };
+        if let Some(bounds) = text_brush.glyph_bounds(&section) {
+            self.draw_text_background(target, self.state.theme.badge_color, bounds, padding, padding);
+        }
+        text_brush.queue(section);
+        text_brush.draw_queued(display, target);
+        debug!("MLB diagnostics drawn");
     }
-}
+
+    /// Draws the vertical paging indicator over the given (possibly fractional) number of rows, with the thumb
+    /// positioned at the given (possibly fractional) first displayed row.
+    ///
+    /// # Errors
+    /// Panics if the given target cannot be used to render the indicator.
+    fn draw_indicator(&self, target: &mut Frame, row_count: f32, row_scroll: f32) {
+        let track_height = 1.0 - TOP_INDENT - BORDER_OFFSET;
+        let thumb_height = track_height * Y_PAGE_SIZE as f32 / row_count;
+        let thumb_top = TOP_INDENT + track_height * row_scroll / row_count;
+        for (vertices, top, height) in [
+            (&self.indicator_track_vertices, TOP_INDENT, track_height),
+            (&self.indicator_thumb_vertices, thumb_top, thumb_height),
+        ]
+        .iter()
+        {
+            let indicator_uniforms = uniform! {
+                matrix: Transform::onto(Bounds::new(INDICATOR_LEFT, *top, INDICATOR_WIDTH, *height)).matrix(),
+            };
+            target
+                .draw(
+                    *vertices,
+                    &NoIndices(PrimitiveType::TriangleStrip),
+                    &self.rect_program,
+                    &indicator_uniforms,
+                    &gl_utils::viewport_parameters(self.state.viewport),
+                )
+                .unwrap_or_else(|ex| {
+                    let msg = "Target could not draw paging indicator";
+                    error!("{}:\n{}", msg, ex);
+                    panic!("{}.", msg);
+                });
+        }
+        debug!("MLB paging indicator drawn");
+    }
+
+    /// Draws a dot for each page of a row of the given length at the given (possibly fractional) row, right-aligned
+    /// below its last column, with the dot of the page starting nearest the given first displayed game highlighted.
+    ///
+    /// # Errors
+    /// Panics if the given target cannot be used to render the dots.
+    fn draw_page_dots(
+        &self,
+        target: &mut Frame,
+        len: usize,
+        begin_index: usize,
+        y: f32,
+        screen_width: f32,
+        screen_height: f32,
+    ) {
+        let pages = len.div_ceil(X_PAGE_SIZE);
+        // the last page is only reached once its last game is displayed, as moving the focus scrolls by single games
+        let current = if begin_index + X_PAGE_SIZE >= len {
+            pages - 1
+        } else {
+            ((begin_index as f32 / X_PAGE_SIZE as f32).round() as usize).min(pages - 2)
+        };
+        let size = text_scale(PAGE_DOT_SIZE, self.state.text_ratio);
+        let step = size + text_scale(PAGE_DOT_GAP, self.state.text_ratio);
+        let (last_left, top) = calc_tile_location_percentage(true, (X_PAGE_SIZE - 1) as f32, y);
+        let right = (last_left + FOCUSED_TILE_SCALE) * screen_width;
+        let center_y = (top + FOCUSED_TILE_SCALE + PAGE_DOT_OFFSET) * screen_height;
+        let (x_scale, y_scale) = (size / screen_width, size / screen_height);
+        for page in 0..pages {
+            let center_x = right - size / 2.0 - (pages - 1 - page) as f32 * step;
+            let dot = Bounds::new(
+                center_x / screen_width - x_scale / 2.0,
+                center_y / screen_height - y_scale / 2.0,
+                x_scale,
+                y_scale,
+            );
+            let color = if page == current {
+                self.state.theme.thumb_color
+            } else {
+                self.state.theme.track_color
+            };
+            self.rounded_rect
+                .draw_rounded_rect(target, self.state.viewport, dot, size / 2.0, color);
+        }
+    }
+
+    /// Draws the detail view of the focused game with the given parameters.
+    ///
+    /// # Errors
+    /// Panics if the given target cannot be used to render the detail view.
+    fn draw_detail(
+        &mut self,
+        display: &dyn Facade,
+        target: &mut Frame,
+        text_brush_option: Option<&mut GlyphBrush>,
+        screen_width: f32,
+        screen_height: f32,
+    ) {
+        let detail_scroll = self.state.detail_scroll;
+        let game = match self.state.ui_info.focused_game_mut() {
+            Some(game) => game,
+            None => {
+                self.state.screen = self.state.history.pop().unwrap_or(MlbScreen::Grid);
+                return;
+            }
+        };
+
+        let (image_x_scale, image_y_scale) = fit_aspect(DETAIL_IMAGE_SCALE, screen_width, screen_height);
+        let game_uniforms = uniform! {
+            matrix: Transform::new()
+                .scale(image_x_scale, image_y_scale)
+                .center_at(LEFT_INDENT + DETAIL_IMAGE_SCALE / 2.0, TOP_INDENT + DETAIL_IMAGE_SCALE / 2.0)
+                .matrix(),
+            tex: gl_utils::tile_sampler(game.get_texture(display)),
+            alpha: 1.0f32,
+            size: [0.0, 0.0f32],
+            radius: 0.0f32,
+        };
+        target
+            .draw(
+                &self.image_square_vertices,
+                &NoIndices(PrimitiveType::TriangleStrip),
+                &self.image_program,
+                &game_uniforms,
+                &gl_utils::viewport_parameters(self.state.viewport),
+            )
+            .unwrap_or_else(|ex| {
+                let msg = "Target could not draw detail image";
+                error!("{}:\n{}", msg, ex);
+                panic!("{}.", msg);
+            });
+        debug!("MLB detail image drawn");
+
+        if let Some(text_brush) = text_brush_option {
+            let info = &game.info;
+            let scores = info.away_score.zip(info.home_score);
+            let body = match &game.detail {
+                DetailState::NotLoaded | DetailState::Loading => self.state.strings.get("detail-loading"),
+                DetailState::Loaded(detail) => detail
+                    .recap_body
+                    .as_ref()
+                    .map(|body| {
+                        let paragraphs = body.split("\n\n").collect::<Vec<_>>();
+                        paragraphs[std::cmp::min(detail_scroll, paragraphs.len() - 1)..].join("\n\n")
+                    })
+                    .unwrap_or_default(),
+                DetailState::Unavailable => self.state.strings.get("detail-unavailable"),
+            };
+            let title_color = if game.favorite {
+                self.state.theme.favorite_color
+            } else {
+                self.state.theme.text_color
+            };
+            // the state of a game in progress is colored as its badge is, and that of other games is dimmed
+            let state_color = if info.state == MlbGameState::Live {
+                self.state.theme.live_color
+            } else {
+                self.state.theme.secondary_text_color
+            };
+            let lines = [
+                (info.title.as_str(), 48.0, 0.08),
+                (info.summary.as_str(), 36.0, 0.12),
+                ("", 32.0, 0.05),
+                (info.detailed_state.as_str(), 32.0, 0.05),
+                (body.as_str(), 24.0, 0.9 - TOP_INDENT - 0.3),
+            ];
+            let x_offset = DETAIL_TEXT_INDENT * screen_width;
+            let mut y_offset = TOP_INDENT;
+            for (line, (text, scale, height)) in lines.iter().enumerate() {
+                let bounds = (
+                    (1.0 - DETAIL_TEXT_INDENT - LEFT_INDENT) * screen_width,
+                    height * screen_height,
+                );
+                let color = match line {
+                    0 => title_color,
+                    3 => state_color,
+                    _ => self.state.theme.text_color,
+                };
+                let scale = text_scale(*scale, self.state.text_ratio);
+                let screen_position = (x_offset, y_offset * screen_height);
+                y_offset += height;
+                if line == 2 {
+                    // the score line names each team in bold beside its score
+                    if let Some((away_score, home_score)) = scores {
+                        let style = TextStyle::new(color, scale);
+                        let (away_score, home_score) = (format!(" {}, ", away_score), format!(" {}", home_score));
+                        let score = RichText::new()
+                            .push(&info.away_team, style.bold())
+                            .push(&away_score, style)
+                            .push(&info.home_team, style.bold())
+                            .push(&home_score, style);
+                        text_brush.queue(score.section(screen_position, bounds));
+                    }
+                    continue;
+                }
+                let text = text_brush.fit_text(text, scale, bounds);
+                text_brush.queue(Section {
+                    text: vec![Text::new(&text).with_color(color).with_scale(scale)],
+                    screen_position,
+                    bounds,
+                    ..Section::default()
+                });
+            }
+            queue_box_score(
+                text_brush,
+                &self.state.theme,
+                &self.state.strings,
+                game,
+                screen_width,
+                screen_height,
+                self.state.text_ratio,
+            );
+            self.state.queue_clock(text_brush, screen_width, screen_height);
+            text_brush.draw_queued(display, target);
+            debug!("MLB detail text drawn");
+        }
+    }
+
+    /// Draws the current frame of the playing video as large as the screen allows, preserving its aspect ratio.
+    ///
+    /// # Errors
+    /// Panics if the given target cannot be used to render the video.
+    fn draw_video(&mut self, display: &dyn Facade, target: &mut Frame, screen_width: f32, screen_height: f32) {
+        let (x_scale, y_scale) = fit_aspect(1.0, screen_width, screen_height);
+        let texture = match self.state.video.as_mut().and_then(|video| video.get_texture(display)) {
+            Some(texture) => texture,
+            None => return,
+        };
+        let video_uniforms = uniform! {
+            matrix: Transform::new().scale(x_scale, y_scale).matrix(),
+            tex: texture,
+            alpha: 1.0f32,
+            size: [0.0, 0.0f32],
+            radius: 0.0f32,
+        };
+        target
+            .draw(
+                &self.image_square_vertices,
+                &NoIndices(PrimitiveType::TriangleStrip),
+                &self.image_program,
+                &video_uniforms,
+                &gl_utils::viewport_parameters(self.state.viewport),
+            )
+            .unwrap_or_else(|ex| {
+                let msg = "Target could not draw video";
+                error!("{}:\n{}", msg, ex);
+                panic!("{}.", msg);
+            });
+        debug!("MLB video frame drawn");
+    }
+
+    /// Recompiles the shaders from their source files with the given display, keeping the current shaders if they fail.
+    /// The visual representation will be updated on the next call to draw.
+    pub fn reload_shaders(&mut self, display: &dyn Facade) {
+        shaders::IMAGE.reload_into(display, &mut self.image_program);
+        shaders::RECT.reload_into(display, &mut self.rect_program);
+        self.rounded_rect.reload_shaders(display);
+        self.sprites.reload_shaders(display);
+    }
+
+    /// Stops any playing video, in preparation for the application exiting.
+    pub fn shutdown(&mut self) {
+        self.state.video = None;
+        self.background_video = None;
+    }
+
+    /// Returns to the detail view if the video with the given identifier is still playing, or displays the background
+    /// image if it is the background video, which only ends if it cannot be decoded.
+    pub fn finish_video(&mut self, id: u64) {
+        if self.state.video.as_ref().map(|video| video.id()) == Some(id) {
+            self.state.back();
+        } else if self.background_video.as_ref().map(|video| video.id()) == Some(id) {
+            error!("Background video ended, using image");
+            self.background_video = None;
+        }
+    }
+}
+
+impl MlbUiState {
+    /// Initializes the state of the MLB UI, which displays a loading splash until the days being retrieved are provided
+    /// with `set_pending_days`. Videos notify the event loop of the given proxy as frames are decoded, and are not
+    /// played without one. Elements are colored by the given theme, and text is taken from the given catalog.
+    pub fn new(proxy: Option<EventLoopProxy<UiEvent>>, theme: Theme, strings: Strings) -> Self {
+        let ui_info = MlbUiInfo::default();
+        let border = calc_tile_location_percentage(
+            true,
+            ui_info.grid.focused_index as f32,
+            (ui_info.grid.focused_row - ui_info.grid.begin_row) as f32,
+        );
+        let leagues = league::providers()
+            .iter()
+            .map(|provider| LeagueUiInfo::new(provider.name()))
+            .collect();
+        MlbUiState {
+            ui_info,
+            leagues,
+            tab: 0,
+            theme,
+            strings,
+            scale_factor: 1.0,
+            text_ratio: 1.0,
+            screen: MlbScreen::Loading,
+            history: Vec::new(),
+            menu_focus: FocusManager::new(FocusEdge::Wrap)
+                .with_focusables(menu::focusables())
+                .with_listener(|section| debug!("menu focus {:?}", section)),
+            scores_focus: FocusManager::new(FocusEdge::Clamp),
+            settings: Vec::new(),
+            load_progress: None,
+            detail_scroll: 0,
+            searching: false,
+            date_picker: None,
+            banner: Banner::Unreachable,
+            banner_slide: Tween::new(0.0, BANNER_SLIDE_DURATION),
+            video: None,
+            image_requests: Vec::new(),
+            day_requests: Vec::new(),
+            image_size: None,
+            proxy,
+            border: Tween::new(border, tween::FOCUS_DURATION),
+            clock: FrameClock::new(),
+            animating: false,
+            frame_interval: Duration::from_secs(0),
+            content_aspect: Some(REFERENCE_ASPECT),
+            safe_area: 0.0,
+            viewport: glium::Rect {
+                left: 0,
+                bottom: 0,
+                width: 1,
+                height: 1,
+            },
+            window_height: 1,
+            started: Instant::now(),
+            fixed_time: None,
+            restoring: None,
+            diagnostics: None,
+        }
+    }
+
+    /// Displays the diagnostics overlay with the given measurements, or hides it if None. The visual representation
+    /// will be updated on the next call to draw.
+    pub fn set_diagnostics(&mut self, diagnostics: Option<Diagnostics>) {
+        self.diagnostics = diagnostics;
+    }
+
+    /// Determines when draw should next be called if nothing else changes: soon if an animation was in progress during
+    /// the last call to draw, otherwise when the displayed time changes.
+    pub fn next_redraw(&self) -> Instant {
+        let now = Instant::now();
+        if self.animating {
+            return now + self.frame_interval;
+        }
+        now + Duration::from_secs(u64::from(60 - Local::now().second()))
+    }
+
+    /// Queues the labels of the tab of each league below the search text, with the label of the displayed tab
+    /// highlighted.
+    fn queue_tabs(&self, text_brush: &mut GlyphBrush, screen_width: f32, screen_height: f32) {
+        let labels = std::iter::once(self.strings.get("mlb-tab"))
+            .chain(self.leagues.iter().map(|league| league.name.clone()))
+            .enumerate()
+            .map(|(tab, label)| {
+                let color = if tab == self.tab {
+                    self.theme.text_color
+                } else {
+                    self.theme.secondary_text_color
+                };
+                (label, color)
+            });
+        let position = (LEFT_INDENT * screen_width, TAB_BAR_TOP * screen_height);
+        menu::queue_strip(text_brush, labels, position, 32.0, self.text_ratio);
+    }
+
+    /// Queues the label of each section in the menu at the top of the screen: the focused section, if the menu is
+    /// focused, highlighted, the section of the displayed screen in the text color, and the rest in the secondary text
+    /// color.
+    fn queue_menu(&self, text_brush: &mut GlyphBrush, screen_width: f32, screen_height: f32) {
+        let active = self.active_section();
+        let labels = MenuSection::ALL.iter().map(|section| {
+            let color = if self.menu_focus.focused() == Some(*section) {
+                self.theme.highlight_color
+            } else if active == *section {
+                self.theme.text_color
+            } else {
+                self.theme.secondary_text_color
+            };
+            (self.strings.get(section.label_id()), color)
+        });
+        let position = (LEFT_INDENT * screen_width, MENU_TOP * screen_height);
+        menu::queue_strip(text_brush, labels, position, 36.0, self.text_ratio);
+    }
+
+    /// Queues the current time, and the time at which games were retrieved, in the top-right corner of the screen.
+    fn queue_clock(&self, text_brush: &mut GlyphBrush, screen_width: f32, screen_height: f32) {
+        let clock = self.strings.format_time(self.now(), "clock-format");
+        let time = self.strings.format_time(self.ui_info.loaded_at, "clock-format");
+        let freshness = self.strings.format("clock-freshness", &[("time", time.into())]);
+        let style = TextStyle::new(self.theme.text_color, text_scale(48.0, self.text_ratio));
+        let text = RichText::new().push(&clock, style).push("\n", style).push(
+            &freshness,
+            style
+                .with_color(self.theme.secondary_text_color)
+                .with_scale(text_scale(24.0, self.text_ratio)),
+        );
+        text_brush.queue(Section {
+            layout: Layout::default().h_align(HorizontalAlign::Right),
+            ..text.section(
+                ((1.0 - LEFT_INDENT) * screen_width, CLOCK_TOP * screen_height),
+                (0.3 * screen_width, 0.15 * screen_height),
+            )
+        });
+    }
+
+    /// Draws the section of the displayed screen other than the grid, below the menu.
+    fn draw_section(
+        &self,
+        display: &dyn Facade,
+        target: &mut Frame,
+        text_brush_option: Option<&mut GlyphBrush>,
+        screen_width: f32,
+        screen_height: f32,
+    ) {
+        let text_brush = match text_brush_option {
+            Some(text_brush) => text_brush,
+            None => return,
+        };
+        self.queue_menu(text_brush, screen_width, screen_height);
+        match self.screen {
+            MlbScreen::Scores => self.queue_scores(text_brush, screen_width, screen_height),
+            MlbScreen::Standings => self.ui_info.standings.queue_table(
+                text_brush,
+                &self.theme,
+                &self.strings,
+                screen_width,
+                screen_height,
+                self.text_ratio,
+            ),
+            MlbScreen::Settings => menu::queue_settings(
+                text_brush,
+                &self.theme,
+                &self.strings,
+                &self.settings,
+                screen_width,
+                screen_height,
+                self.text_ratio,
+            ),
+            MlbScreen::Loading | MlbScreen::Grid | MlbScreen::Detail | MlbScreen::Video => (),
+        }
+        self.queue_clock(text_brush, screen_width, screen_height);
+        text_brush.draw_queued(display, target);
+        debug!("MLB section drawn");
+    }
+
+    /// Queues the scoreboard of today's games, a page at a time around the focused game, which is highlighted unless
+    /// the menu is focused.
+    fn queue_scores(&self, text_brush: &mut GlyphBrush, screen_width: f32, screen_height: f32) {
+        let scale = text_scale(32.0, self.text_ratio);
+        let games = self.scores();
+        if games.is_empty() {
+            text_brush.queue(Section {
+                text: vec![Text::new(&self.strings.get("no-scores"))
+                    .with_color(self.theme.secondary_text_color)
+                    .with_scale(scale)],
+                screen_position: (LEFT_INDENT * screen_width, SECTION_TOP * screen_height),
+                ..Section::default()
+            });
+            return;
+        }
+        let begin = self.scores_index().saturating_sub(SCORES_PAGE_SIZE - 1);
+        let page = &games[begin..games.len().min(begin + SCORES_PAGE_SIZE)];
+        // the state column takes up the rest of each row
+        let widths = [
+            Length::Fixed(SCORES_TEAM_WIDTH),
+            Length::Fixed(SCORES_SCORE_WIDTH),
+            Length::Fixed(SCORES_TEAM_WIDTH),
+            Length::Fixed(SCORES_SCORE_WIDTH),
+            Length::Fill(1.0),
+        ];
+        let table = Widget::column(
+            (0..page.len())
+                .map(|row| {
+                    let cells = widths
+                        .iter()
+                        .enumerate()
+                        .map(|(col, width)| Widget::leaf(*width, Length::Fill(1.0)).with_key((row, col)))
+                        .collect();
+                    Widget::row(cells).with_size(Length::Fill(1.0), Length::Fixed(SCORES_ROW_HEIGHT))
+                })
+                .collect(),
+        );
+        let arrangement = table.arrange(Bounds::new(
+            LEFT_INDENT,
+            SECTION_TOP,
+            1.0 - LEFT_INDENT,
+            1.0 - SECTION_TOP,
+        ));
+        for (row, game) in page.iter().enumerate() {
+            let index = begin + row;
+            let color = if self.menu_focus.focused().is_none() && index == self.scores_index() {
+                self.theme.highlight_color
+            } else if game.favorite {
+                self.theme.favorite_color
+            } else {
+                self.theme.text_color
+            };
+            let info = &game.info;
+            let score = |score: Option<u32>| score.map(|score| score.to_string()).unwrap_or_default();
+            let columns = [
+                info.away_team.clone(),
+                score(info.away_score),
+                info.home_team.clone(),
+                score(info.home_score),
+                info.detailed_state.clone(),
+            ];
+            for (col, text) in columns.iter().enumerate() {
+                let cell = match arrangement.bounds((row, col)) {
+                    Some(cell) => cell,
+                    None => continue,
+                };
+                let bounds = (cell.width() * screen_width, cell.height() * screen_height);
+                let text = text_brush.fit_text(text, scale, bounds);
+                // the team names are bold
+                let style = match col {
+                    0 | 2 => TextStyle::new(color, scale).bold(),
+                    _ => TextStyle::new(color, scale),
+                };
+                text_brush.queue(
+                    RichText::new()
+                        .push(&text, style)
+                        .section((cell.left * screen_width, cell.top * screen_height), bounds),
+                );
+            }
+        }
+    }
+
+    /// Lists the games of today, or of the most recent displayed day, in the order displayed in the grid.
+    fn scores(&self) -> Vec<&MlbGameGlInfo> {
+        match self.ui_info.days.first() {
+            Some(day) => day.games.iter().collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Determines the section of the menu that the displayed screen belongs to.
+    fn active_section(&self) -> MenuSection {
+        match self.screen {
+            MlbScreen::Scores => MenuSection::Scores,
+            MlbScreen::Standings => MenuSection::Standings,
+            MlbScreen::Settings => MenuSection::Settings,
+            MlbScreen::Loading | MlbScreen::Grid | MlbScreen::Detail | MlbScreen::Video => MenuSection::Home,
+        }
+    }
+
+    /// Retrieves the index of the focused game of the scoreboard.
+    fn scores_index(&self) -> usize {
+        self.scores_focus.focused().unwrap_or(0)
+    }
+
+    /// Opens the given section from the menu, above the grid so that returning from it displays the grid.
+    fn open_section(&mut self, section: MenuSection) {
+        self.history.clear();
+        self.screen = MlbScreen::Grid;
+        self.scores_focus.focus(0);
+        let screen = match section {
+            MenuSection::Home => return,
+            MenuSection::Scores => MlbScreen::Scores,
+            MenuSection::Standings => MlbScreen::Standings,
+            MenuSection::Settings => MlbScreen::Settings,
+        };
+        self.open(screen);
+    }
+
+    /// Displays the given screen, returning to the displayed screen on `back`.
+    fn open(&mut self, screen: MlbScreen) {
+        self.history.push(self.screen);
+        self.screen = screen;
+    }
+
+    /// Records the settings displayed in the settings section, each a name and its value. The visual representation
+    /// will be updated on the next call to draw.
+    pub fn set_settings(&mut self, settings: Vec<(String, String)>) {
+        self.settings = settings;
+    }
+
+    /// Describes the retrieval state of the league of the displayed tab, displayed in place of its rails while it has
+    /// none. Returns None for the MLB grid.
+    fn league_status(&self) -> Option<String> {
+        let league = self.leagues.get(self.tab.checked_sub(1)?)?;
+        let id = match league.state {
+            LeagueState::NotLoaded | LeagueState::Loading => "league-loading",
+            LeagueState::Loaded => "league-empty",
+            LeagueState::Unavailable => "league-unavailable",
+        };
+        Some(self.strings.format(id, &[("league", league.name.clone().into())]))
+    }
+
+    /// Determines the color of the badge overlaid on games in progress, faded according to the time elapsed at the given
+    /// time.
+    fn live_badge_color(&self, now: Instant) -> Color {
+        let phase = now.saturating_duration_since(self.started).as_secs_f32() / LIVE_PULSE_PERIOD.as_secs_f32();
+        let pulse = 0.5 + 0.5 * (phase * 2.0 * std::f32::consts::PI).cos();
+        let mut color = self.theme.live_color;
+        color[3] *= LIVE_PULSE_MIN_OPACITY + (1.0 - LIVE_PULSE_MIN_OPACITY) * pulse;
+        color
+    }
+
+    /// Declares a panel of the given lines of text, each with its color and nominal size, centered above one another
+    /// within the padding of the panel. Each line is measured with the given text brush.
+    fn panel(
+        &self,
+        text_brush: &mut GlyphBrush,
+        lines: &[(&str, Color, f32)],
+        screen_width: f32,
+        screen_height: f32,
+    ) -> Widget<PanelPart> {
+        let padding = text_scale(BADGE_PADDING * 2.0, self.text_ratio);
+        let children = lines
+            .iter()
+            .enumerate()
+            .map(|(index, (text, _, size))| {
+                let section = Section {
+                    text: vec![Text::new(text).with_scale(text_scale(*size, self.text_ratio))],
+                    bounds: (screen_width, screen_height),
+                    ..Section::default()
+                };
+                let (width, height) = text_brush
+                    .glyph_bounds(&section)
+                    .map_or((0.0, 0.0), |bounds| (bounds.max.x, bounds.max.y));
+                Widget::leaf(
+                    Length::Fixed(width / screen_width),
+                    Length::Fixed(height / screen_height),
+                )
+                .with_key(PanelPart::Line(index))
+            })
+            .collect();
+        Widget::column(children)
+            .with_key(PanelPart::Background)
+            .with_padding(padding / screen_width, padding / screen_height)
+            .with_spacing(padding / screen_height)
+            .with_align(Align::Start, Align::Center)
+    }
+
+    /// Queues the retrieval of the images of games that are displayed or within `IMAGE_PREFETCH_PAGES` pages of being
+    /// displayed, and have not yet been requested.
+    fn request_nearby_images(&mut self) {
+        let info = &mut self.ui_info;
+        let first_day = info.grid.begin_row.saturating_sub(Y_PAGE_SIZE * IMAGE_PREFETCH_PAGES);
+        let day_count = Y_PAGE_SIZE * (1 + 2 * IMAGE_PREFETCH_PAGES);
+        for day in info.days.iter_mut().skip(first_day).take(day_count) {
+            let first_index = day.begin_index.saturating_sub(X_PAGE_SIZE * IMAGE_PREFETCH_PAGES);
+            let game_count = X_PAGE_SIZE * (1 + 2 * IMAGE_PREFETCH_PAGES);
+            for &index in day.visible.iter().skip(first_index).take(game_count) {
+                let game = &mut day.games[index];
+                if game.image_requested {
+                    continue;
+                }
+                if let Some(url) = &game.info.image_url {
+                    game.image_requested = true;
+                    self.image_requests.push((game.info.game_pk, url.to_owned()));
+                }
+            }
+        }
+    }
+
+    /// Takes the images queued for retrieval since the last call, as pairs of game identifier and image URL. Each
+    /// result should be provided with `set_image`.
+    pub fn take_image_requests(&mut self) -> Vec<(u64, String)> {
+        std::mem::take(&mut self.image_requests)
+    }
+
+    /// Takes the days added to the grid since the last call, whose games should be retrieved and provided with `set_day`
+    /// or `set_day_failed`.
+    pub fn take_day_requests(&mut self) -> Vec<NaiveDate> {
+        std::mem::take(&mut self.day_requests)
+    }
+
+    /// Adds a row for the day before the oldest day once the focus reaches the oldest day, queueing its games for
+    /// retrieval, so that history can be scrolled through indefinitely. Only one older day is retrieved at a time, and
+    /// none while the oldest day could not be retrieved.
+    fn extend_older_days(&mut self) {
+        let info = &mut self.ui_info;
+        let oldest = match info.days.last() {
+            Some(day) if day.loaded && !day.failed && info.grid.focused_row + 1 >= info.days.len() => day.date,
+            _ => return,
+        };
+        let date = oldest.pred();
+        info.insert_day(DayRowInfo::pending(date));
+        self.day_requests.push(date);
+    }
+
+    /// Adds a row for the day after the most recent day above it and focuses it, queueing its games for retrieval, so
+    /// that scheduled games can be browsed up to `UPCOMING_DAYS` days after today. Only one upcoming day is retrieved at
+    /// a time, and none while the most recent day could not be retrieved. Returns whether a day was added.
+    fn extend_upcoming_days(&mut self) -> bool {
+        let last_upcoming = self.now().date().naive_local() + chrono::Duration::days(UPCOMING_DAYS);
+        let info = &mut self.ui_info;
+        let latest = match info.days.first() {
+            Some(day) if day.loaded && !day.failed && day.date < last_upcoming => day.date,
+            _ => return false,
+        };
+        let date = latest.succ();
+        info.insert_day(DayRowInfo::pending(date));
+        info.focus(0, 0);
+        self.day_requests.push(date);
+        true
+    }
+
+    /// Determines the largest size in pixels at which the image of a game was displayed by the last call to draw, or None
+    /// if it has not been called. Larger images can be downscaled before they are provided with `set_image` without
+    /// visible loss.
+    pub fn image_size(&self) -> Option<(u32, u32)> {
+        self.image_size
+    }
+
+    /// Stores the decoded image of the game with the given identifier, replacing the default artwork once it is uploaded
+    /// on the next call to draw. Games whose image could not be retrieved or decoded keep the default artwork.
+    pub fn set_image(&mut self, game_pk: u64, image: Option<DecodedImage>) {
+        // a suspended game is listed on the day it resumes as well, and each listing is given the image
+        let mut games: Vec<&mut MlbGameGlInfo> = self
+            .ui_info
+            .days
+            .iter_mut()
+            .flat_map(|day| day.games.iter_mut())
+            .filter(|game| game.info.game_pk == game_pk)
+            .collect();
+        if let Some(last) = games.pop() {
+            for game in games {
+                game.set_image(image.clone());
+            }
+            last.set_image(image);
+        }
+    }
+
+    /// Retries the retrieval of the days and images that could not be retrieved. Days being retried display a spinner
+    /// until they are provided with `set_day` or `set_day_failed`, and images are queued for retrieval as they are
+    /// displayed. Returns the days to be retrieved.
+    pub fn retry(&mut self) -> Vec<NaiveDate> {
+        let mut dates = Vec::new();
+        for day in &mut self.ui_info.days {
+            if day.failed && !day.retrying {
+                day.retrying = true;
+                dates.push(day.date);
+            }
+            for game in day.games.iter_mut().filter(|game| game.image_failed) {
+                game.image_failed = false;
+                game.image_requested = false;
+            }
+        }
+        dates
+    }
+
+    /// Records the scale factor of the window, which sets the smallest size of text. The visual representation will be
+    /// updated on the next call to draw.
+    pub fn set_scale_factor(&mut self, scale_factor: f64) {
+        self.scale_factor = scale_factor as f32;
+    }
+
+    /// Records the aspect ratio of the area in which content is laid out, or None to lay out content across the window,
+    /// and the fraction of each dimension of the window by which its edges are inset first. The rest of the window is
+    /// covered only by the background. The visual representation will be updated on the next call to draw.
+    pub fn set_content_area(&mut self, aspect: Option<f32>, safe_area: f32) {
+        self.content_aspect = aspect;
+        self.safe_area = safe_area;
+    }
+
+    /// Converts the given point, in pixels from the top-left corner of the window, to percentages of the area in which
+    /// content was laid out by the last call to draw, from its top-left corner, as expected by `hover` and the like.
+    pub fn content_point(&self, x: f32, y: f32) -> (f32, f32) {
+        let viewport = self.viewport;
+        let top = self.window_height.saturating_sub(viewport.bottom + viewport.height);
+        (
+            (x - viewport.left as f32) / viewport.width as f32,
+            (y - top as f32) / viewport.height as f32,
+        )
+    }
+
+    /// Displays the given time as the current time and as the time games are retrieved, or the time as it passes if
+    /// None, so that frames drawn of the same games do not change over time. The visual representation will be
+    /// updated on the next call to draw.
+    pub fn set_fixed_time(&mut self, time: Option<DateTime<Local>>) {
+        self.fixed_time = time;
+    }
+
+    /// Determines the time displayed as the current time.
+    fn now(&self) -> DateTime<Local> {
+        self.fixed_time.unwrap_or_else(Local::now)
+    }
+
+    /// Records the shortest time between frames, or None to draw frames as often as the display allows while an
+    /// animation is in progress.
+    pub fn set_frame_interval(&mut self, frame_interval: Option<Duration>) {
+        self.frame_interval = frame_interval.unwrap_or_default();
+    }
+
+    /// Records the progress of the retrieval of games. The visual representation will be updated on the next call to
+    /// draw.
+    pub fn set_progress(&mut self, progress: MlbLoadProgress) {
+        self.load_progress = Some(progress);
+    }
+
+    /// Replaces the displayed days with placeholders for the given days, most recent first, leaving the loading splash
+    /// if it is displayed. The visual representation will be updated on the next call to draw.
+    pub fn set_pending_days(&mut self, dates: Vec<NaiveDate>) {
+        let standings = std::mem::replace(&mut self.ui_info.standings, StandingsRail::new());
+        self.ui_info = MlbUiInfo::pending(dates);
+        self.ui_info.standings = standings;
+        self.load_progress = None;
+        if self.screen == MlbScreen::Loading {
+            self.screen = MlbScreen::Grid;
+        }
+        self.restore_pending();
+    }
+
+    /// Replaces the placeholders or previously retrieved games of the given day with the given games. The focus and
+    /// paging positions are preserved, as are the textures and details of games that are unchanged. The visual
+    /// representation will be updated on the next call to draw.
+    pub fn set_day(&mut self, date: NaiveDate, games: Vec<MlbGameClientInfo>, options: &Options) {
+        let loaded_at = self.now();
+        let info = &mut self.ui_info;
+        let mut previous: Vec<MlbGameGlInfo> = match info.days.iter_mut().find(|day| day.date == date) {
+            Some(day) => day.games.drain(..).collect(),
+            None => Vec::new(),
+        };
+        let games = games
+            .into_iter()
+            .map(|game| {
+                let mut game: MlbGameGlInfo = game.into();
+                game.favorite = options.is_favorite(&game.info.away_team) || options.is_favorite(&game.info.home_team);
+                if let Some(old) = previous.iter_mut().find(|old| old.info.game_pk == game.info.game_pk) {
+                    game.focus = std::mem::replace(&mut old.focus, Tween::new(0.0, tween::FOCUS_DURATION));
+                    if old.info.image_url == game.info.image_url {
+                        game.info.image = old.info.image.take();
+                        game.decoded = old.decoded.take();
+                        game.texture = old.texture.take();
+                        game.replaced_texture = old.replaced_texture.take();
+                        game.fade = std::mem::replace(&mut old.fade, Tween::new(1.0, tween::FADE_DURATION));
+                        game.image_requested = old.image_requested;
+                    }
+                    if old.info.state == game.info.state {
+                        game.detail = std::mem::replace(&mut old.detail, DetailState::NotLoaded);
+                    }
+                }
+                game
+            })
+            .collect();
+        info.set_games(date, games, loaded_at);
+        self.restore_pending();
+    }
+
+    /// Records that the games of the given day could not be retrieved, displaying a banner. Games previously retrieved
+    /// for the day remain displayed. The visual representation will be updated on the next call to draw.
+    pub fn set_day_failed(&mut self, date: NaiveDate) {
+        if let Some(day) = self.ui_info.days.iter_mut().find(|day| day.date == date) {
+            day.failed = true;
+            day.retrying = false;
+            day.loaded = true;
+        }
+        self.banner = Banner::Unreachable;
+        self.banner_slide = banner_slide(BANNER_DURATION);
+        self.restore_pending();
+    }
+
+    /// Displays a banner reporting that the previous run crashed, with a report saved to the given path. The visual
+    /// representation will be updated on the next call to draw.
+    pub fn show_crash_notice(&mut self, report: &Path) {
+        self.banner = Banner::Crashed(report.display().to_string());
+        self.banner_slide = banner_slide(CRASH_BANNER_DURATION);
+    }
+
+    /// Restores the focus and paging of the MLB grid from the given session saved by a previous run, as the days it
+    /// refers to are provided with `set_day`. The tab of the session is not restored; it should be selected with
+    /// `select_tab` once the grid is displayed. The visual representation will be updated on the next call to draw.
+    pub fn restore_session(&mut self, session: Session) {
+        self.restoring = Some(session);
+        self.restore_pending();
+    }
+
+    /// Describes the state of the UI to be saved for the next run. While a saved session is being restored, the parts
+    /// of it not yet restored are described as saved.
+    pub fn session(&self) -> Session {
+        if let Some(restoring) = &self.restoring {
+            return restoring.clone();
+        }
+        let info = &self.ui_info;
+        let grid = &info.grid;
+        let date_of = |row: usize| info.days.get(row).map(|day| day.date.to_string());
+        Session {
+            tab: self.tab,
+            focused_day: date_of(grid.focused_row),
+            focused_index: info
+                .days
+                .get(grid.focused_row)
+                .map_or(0, |day| day.begin_index + grid.focused_index),
+            begin_day: date_of(grid.begin_row),
+            begin_indices: info
+                .days
+                .iter()
+                .filter(|day| day.begin_index > 0)
+                .map(|day| (day.date.to_string(), day.begin_index))
+                .collect(),
+            collapsed_days: info
+                .days
+                .iter()
+                .filter(|day| day.collapsed)
+                .map(|day| day.date.to_string())
+                .collect(),
+        }
+    }
+
+    /// Restores as much of the session being restored as the retrieved days allow, leaving the rest to be restored as
+    /// further days are retrieved. Parts referring to days that are not displayed or could not be retrieved are
+    /// discarded.
+    fn restore_pending(&mut self) {
+        let mut session = match self.restoring.take() {
+            Some(session) => session,
+            None => return,
+        };
+        let info = &mut self.ui_info;
+        let find_row = |days: &[DayRowInfo], date: &str| days.iter().position(|day| day.date.to_string() == date);
+        let days = &mut info.days;
+        for date in std::mem::take(&mut session.collapsed_days) {
+            if let Some(day) = days.iter_mut().find(|day| day.date.to_string() == date) {
+                day.collapsed = true;
+            }
+        }
+        session.begin_indices.retain(|date, begin_index| {
+            match days.iter_mut().find(|day| day.date.to_string() == *date) {
+                Some(day) if day.loaded && !day.failed => {
+                    day.begin_index = (*begin_index).min(day.visible_len().saturating_sub(X_PAGE_SIZE));
+                    false
+                }
+                Some(day) => !day.loaded,
+                None => false,
+            }
+        });
+        let focused_row = session
+            .focused_day
+            .as_deref()
+            .and_then(|date| find_row(&info.days, date));
+        match focused_row {
+            Some(row) if !info.days[row].loaded => (),
+            Some(row) if !info.days[row].failed => {
+                let begin_row = session.begin_day.as_deref().and_then(|date| find_row(&info.days, date));
+                if let Some(begin_row) = begin_row.filter(|begin_row| *begin_row <= row) {
+                    info.grid.begin_row = begin_row;
+                }
+                let index = session
+                    .focused_index
+                    .min(info.days[row].visible_len().saturating_sub(1));
+                info.focus(row, index);
+                session.focused_day = None;
+                session.begin_day = None;
+            }
+            _ => {
+                session.focused_day = None;
+                session.begin_day = None;
+            }
+        }
+        if session.is_pending() {
+            self.restoring = Some(session);
+        }
+    }
+
+    /// Replaces the standings displayed below the days with the given standings. The visual representation will be
+    /// updated on the next call to draw.
+    pub fn set_standings(&mut self, standings: Vec<MlbDivisionStandings>) {
+        self.ui_info.standings.set_standings(standings);
+        self.ui_info.clamp_focus();
+    }
+
+    /// Determines the number of tabs, which is the MLB grid and one for each other league.
+    pub fn tab_count(&self) -> usize {
+        1 + self.leagues.len()
+    }
+
+    /// Displays the tab at the given index, where 0 is the MLB grid and the leagues follow in the order of
+    /// `league::providers`. Tabs can only be switched from the grid. Returns true if the rails of the leagues need to be
+    /// retrieved, which should be provided with `set_leagues`.
+    pub fn select_tab(&mut self, tab: usize) -> bool {
+        if self.screen != MlbScreen::Grid || tab >= self.tab_count() || self.date_picker.is_some() {
+            return false;
+        }
+        self.tab = tab;
+        let league = match tab.checked_sub(1) {
+            Some(index) => &mut self.leagues[index],
+            None => return false,
+        };
+        match league.state {
+            LeagueState::NotLoaded | LeagueState::Unavailable => {
+                league.state = LeagueState::Loading;
+                true
+            }
+            LeagueState::Loading | LeagueState::Loaded => false,
+        }
+    }
+
+    /// Displays the tab after the displayed one, wrapping around to the MLB grid. Returns the same as `select_tab`.
+    pub fn next_tab(&mut self) -> bool {
+        self.select_tab((self.tab + 1) % self.tab_count())
+    }
+
+    /// Replaces the rails of each league with the rails retrieved from its provider, and records that the leagues of
+    /// the failed providers could not be retrieved, so that one league's outage leaves the others displayed. The visual
+    /// representation will be updated on the next call to draw.
+    pub fn set_leagues(&mut self, result: AggregateResult) {
+        for provided in result.provided {
+            if let Some(league) = self.leagues.get_mut(provided.index) {
+                league.set_rails(Some(provided.rails));
+            }
+        }
+        for failure in result.failures {
+            if let Some(league) = self.leagues.get_mut(failure.index) {
+                league.set_rails(None);
+            }
+        }
+    }
+
+    /// Retrieves the grid of the displayed tab.
+    fn grid(&self) -> &dyn RailGrid {
+        match self.tab.checked_sub(1).and_then(|index| self.leagues.get(index)) {
+            Some(league) => league,
+            None => &self.ui_info,
+        }
+    }
+
+    /// Retrieves the grid of the displayed tab.
+    fn grid_mut(&mut self) -> &mut dyn RailGrid {
+        active_grid_mut(self.tab, &mut self.ui_info, &mut self.leagues)
+    }
+
+    /// Opens the detail view of the focused game, or of the game the focused item of another rail leads to, or plays
+    /// its recap video if the detail view is already open. The visual representation will be updated on the next call
+    /// to draw. Returns the identifier of the game if its detail needs to be retrieved.
+    pub fn select(&mut self) -> Option<u64> {
+        if let Some(section) = self.menu_focus.unfocus() {
+            self.open_section(section);
+            return None;
+        }
+        match self.screen {
+            MlbScreen::Loading | MlbScreen::Video | MlbScreen::Standings | MlbScreen::Settings => return None,
+            MlbScreen::Grid => {
+                if self.tab != 0 {
+                    return None;
+                }
+                let info = &mut self.ui_info;
+                if let Some(day) = info.days.get_mut(info.grid.focused_row).filter(|day| day.collapsed) {
+                    day.collapsed = false;
+                    return None;
+                }
+                if info.grid.focused_row >= info.days.len() {
+                    let game_pk = info
+                        .rails()
+                        .get(info.grid.focused_row)
+                        .and_then(|rail| rail.select(rail.begin_index() + info.grid.focused_index))?;
+                    if !info.focus_game(game_pk) {
+                        return None;
+                    }
+                }
+            }
+            MlbScreen::Scores => {
+                let game_pk = self.scores().get(self.scores_index())?.info.game_pk;
+                if !self.ui_info.focus_game(game_pk) {
+                    self.clear_search();
+                    if !self.ui_info.focus_game(game_pk) {
+                        return None;
+                    }
+                }
+            }
+            MlbScreen::Detail => {
+                self.play_video();
+                return None;
+            }
+        }
+        let game = self.ui_info.focused_game_mut()?;
+        self.history.push(self.screen);
+        self.screen = MlbScreen::Detail;
+        self.detail_scroll = 0;
+        match game.detail {
+            DetailState::NotLoaded | DetailState::Unavailable => {
+                game.detail = DetailState::Loading;
+                Some(game.info.game_pk)
+            }
+            DetailState::Loading | DetailState::Loaded(_) => None,
+        }
+    }
+
+    /// Stores the retrieved detail of the game with the given identifier. The visual representation will be updated
+    /// on the next call to draw.
+    pub fn set_detail(&mut self, game_pk: u64, detail: Option<MlbGameDetail>) {
+        let games = self.ui_info.days.iter_mut().flat_map(|day| day.games.iter_mut());
+        for game in games.filter(|game| game.info.game_pk == game_pk) {
+            game.detail = match &detail {
+                Some(detail) => DetailState::Loaded(detail.clone()),
+                None => DetailState::Unavailable,
+            };
+        }
+    }
+
+    /// Starts the recap video of the focused game, if its detail has been retrieved and has a video.
+    fn play_video(&mut self) {
+        let url = match self.ui_info.focused_game_mut().map(|game| &game.detail) {
+            Some(DetailState::Loaded(MlbGameDetail {
+                recap_video_url: Some(url),
+                ..
+            })) => url.to_owned(),
+            _ => return,
+        };
+        let proxy = match &self.proxy {
+            Some(proxy) => proxy.clone(),
+            None => return,
+        };
+        match VideoPlayer::start(&url, false, proxy) {
+            Ok(video) => {
+                self.video = Some(video);
+                self.open(MlbScreen::Video);
+            }
+            Err(ex) => error!("Could not play video {}:\n{}", url, ex),
+        }
+    }
+
+    /// Returns to the previous screen, stopping any playing video. The visual representation will be updated on the
+    /// next call to draw.
+    pub fn back(&mut self) {
+        if self.menu_focus.unfocus().is_some() || self.date_picker.take().is_some() {
+            return;
+        }
+        if self.screen == MlbScreen::Grid && self.tab == 0 && !self.ui_info.filter.is_empty() {
+            self.clear_search();
+            return;
+        }
+        if let Some(screen) = self.history.pop() {
+            self.screen = screen;
+        }
+        self.video = None;
+    }
+
+    /// Determines whether the displayed screen is the unfiltered grid or the loading splash with the menu unfocused,
+    /// from which there is no previous screen to return to.
+    pub fn is_top_level(&self) -> bool {
+        self.menu_focus.focused().is_none()
+            && self.date_picker.is_none()
+            && self.history.is_empty()
+            && ((self.screen == MlbScreen::Grid && (self.tab != 0 || self.ui_info.filter.is_empty()))
+                || self.screen == MlbScreen::Loading)
+    }
+
+    /// Determines whether search text is being typed, in which case typed characters should be provided with
+    /// `type_search` rather than treated as commands.
+    pub fn is_searching(&self) -> bool {
+        self.searching
+    }
+
+    /// Starts typing search text in the MLB grid, continuing from any current search. The visual representation will
+    /// be updated on the next call to draw.
+    pub fn start_search(&mut self) {
+        if self.screen == MlbScreen::Grid && self.tab == 0 {
+            self.searching = true;
+        }
+    }
+
+    /// Opens the date picker over the MLB grid, starting from the focused day, or today if no day is focused. While it
+    /// is open, moving the focus changes the chosen date by a day or a week, and paging by a month or a year. The
+    /// visual representation will be updated on the next call to draw.
+    pub fn open_date_picker(&mut self) {
+        if self.screen != MlbScreen::Grid || self.tab != 0 || self.searching {
+            return;
+        }
+        let info = &self.ui_info;
+        let date = info
+            .days
+            .get(info.grid.focused_row)
+            .map_or_else(|| self.now().date().naive_local(), |day| day.date);
+        self.menu_focus.unfocus();
+        self.date_picker = Some(date);
+    }
+
+    /// Determines whether the date picker is open, in which case the chosen date should be confirmed with `pick_date`
+    /// rather than selecting the focused item.
+    pub fn is_picking_date(&self) -> bool {
+        self.date_picker.is_some()
+    }
+
+    /// Closes the date picker and focuses the chosen day, adding a row for it if it is not displayed. Returns the day if
+    /// its games need to be retrieved, which should be provided with `set_day` or `set_day_failed`. The visual
+    /// representation will be updated on the next call to draw.
+    pub fn pick_date(&mut self) -> Option<NaiveDate> {
+        let date = self.date_picker.take()?;
+        let info = &mut self.ui_info;
+        let (row, added) = match info.days.iter().position(|day| day.date == date) {
+            Some(row) => (row, false),
+            None => (info.insert_day(DayRowInfo::pending(date)), true),
+        };
+        let day = &mut info.days[row];
+        day.collapsed = false;
+        let begin_index = day.begin_index;
+        info.focus(row, begin_index);
+        if added {
+            Some(date)
+        } else {
+            None
+        }
+    }
+
+    /// Appends the given character to the search text, displaying only games of teams whose names contain the text.
+    /// Control characters are ignored. The visual representation will be updated on the next call to draw.
+    pub fn type_search(&mut self, c: char) {
+        if self.searching && !c.is_control() {
+            let mut filter = self.ui_info.filter.clone();
+            filter.push(c);
+            self.ui_info.set_filter(filter);
+        }
+    }
+
+    /// Removes the last character of the search text. The visual representation will be updated on the next call to
+    /// draw.
+    pub fn erase_search(&mut self) {
+        if self.searching {
+            let mut filter = self.ui_info.filter.clone();
+            filter.pop();
+            self.ui_info.set_filter(filter);
+        }
+    }
+
+    /// Stops typing search text, keeping the displayed games filtered. The visual representation will be updated on
+    /// the next call to draw.
+    pub fn finish_search(&mut self) {
+        self.searching = false;
+    }
+
+    /// Stops typing search text and displays every game. The visual representation will be updated on the next call
+    /// to draw.
+    pub fn clear_search(&mut self) {
+        self.searching = false;
+        self.ui_info.set_filter(String::new());
+    }
+
+    /// Moves the focus to the game under the given point, as percentages of the screen from the top-left corner.
+    /// Returns true if the point is over a game. The visual representation will be updated on the next call to draw.
+    pub fn hover(&mut self, x: f32, y: f32) -> bool {
+        if self.screen != MlbScreen::Grid || self.date_picker.is_some() {
+            return false;
+        }
+        let info = self.grid_mut();
+        let grid = info.grid();
+        let offsets = info.row_offsets();
+        let rails = info.rails();
+        let hovered = rails.iter().enumerate().skip(grid.begin_row).find_map(|(row, rail)| {
+            let row_y = offsets[row] - offsets[grid.begin_row];
+            if rail.is_collapsed() || row_y + rail::row_height(*rail) > Y_PAGE_SIZE as f32 {
+                return None;
+            }
+            let focused_col = Some(grid.focused_index).filter(|_| row == grid.focused_row);
+            calc_tile_column(x, y, row_y, focused_col)
+                .filter(|col| rail.begin_index() + col < rail.len())
+                .map(|col| (row, col))
+        });
+        match hovered {
+            Some((row, col)) => {
+                let grid = info.grid_mut();
+                grid.focused_row = row;
+                grid.focused_index = col;
+                self.menu_focus.unfocus();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Activates the given point, as percentages of the screen from the top-left corner: in the grid the game under
+    /// the point is focused and selected, elsewhere it is equivalent to `select`. Returns the identifier of the game
+    /// if its detail needs to be retrieved.
+    pub fn click(&mut self, x: f32, y: f32) -> Option<u64> {
+        if self.screen == MlbScreen::Grid && !self.hover(x, y) {
+            return None;
+        }
+        self.select()
+    }
+
+    /// Taps the given point, as percentages of the screen from the top-left corner: in the grid the game under the
+    /// point is focused, or selected if it was already focused, and elsewhere it is equivalent to `select`. Returns the
+    /// identifier of the game if its detail needs to be retrieved.
+    pub fn tap(&mut self, x: f32, y: f32) -> Option<u64> {
+        if self.screen == MlbScreen::Grid {
+            let grid = self.grid().grid();
+            let focused = (grid.focused_row, grid.focused_index);
+            if !self.hover(x, y) {
+                return None;
+            }
+            let grid = self.grid().grid();
+            if (grid.focused_row, grid.focused_index) != focused {
+                return None;
+            }
+        }
+        self.select()
+    }
+
+    /// Responds to a swipe in the given direction that started at the given point, as percentages of the screen from
+    /// the top-left corner: in the grid a horizontal swipe pages the row under the point and a vertical swipe changes
+    /// the focused rail, in the detail view a vertical swipe scrolls the recap text, and a rightward swipe elsewhere
+    /// returns to the previous screen. The visual representation will be updated on the next call to draw.
+    pub fn swipe(&mut self, y: f32, direction: FocusDirection) {
+        match (self.screen, direction) {
+            (MlbScreen::Grid, FocusDirection::Left) => self.scroll(y, -1),
+            (MlbScreen::Grid, FocusDirection::Right) => self.scroll(y, 1),
+            (MlbScreen::Grid, FocusDirection::Up) => self.move_focus(FocusDirection::Down),
+            (MlbScreen::Grid, FocusDirection::Down) => self.move_focus(FocusDirection::Up),
+            (MlbScreen::Detail, FocusDirection::Up) => self.move_focus(FocusDirection::Down),
+            (MlbScreen::Detail, FocusDirection::Down) => self.move_focus(FocusDirection::Up),
+            (MlbScreen::Detail, FocusDirection::Right) | (MlbScreen::Video, FocusDirection::Right) => self.back(),
+            _ => (),
+        }
+    }
+
+    /// Scrolls by the given number of notches (positive away from the user) at the given point, as percentages of the
+    /// screen from the top-left corner: in the grid the row under the point is paged horizontally, one page per
+    /// notch, and in the detail view the recap text is scrolled. The visual representation will be updated on the
+    /// next call to draw.
+    pub fn scroll(&mut self, y: f32, notches: i32) {
+        if self.screen == MlbScreen::Detail {
+            let direction = if notches > 0 {
+                FocusDirection::Up
+            } else {
+                FocusDirection::Down
+            };
+            for _ in 0..notches.abs() {
+                self.move_focus(direction);
+            }
+            return;
+        }
+        if self.screen != MlbScreen::Grid {
+            return;
+        }
+        let position = calc_row_position(y);
+        if position < 0.0 || position >= Y_PAGE_SIZE as f32 {
+            return;
+        }
+        let info = self.grid_mut();
+        let offsets = info.row_offsets();
+        let begin_offset = offsets[info.grid().begin_row];
+        let row = (info.grid().begin_row..offsets.len() - 1)
+            .find(|row| position >= offsets[*row] - begin_offset && position < offsets[row + 1] - begin_offset);
+        if let Some(row) = row {
+            info.page_row(row, -notches);
+        }
+    }
+
+    /// Collapses the focused day of the MLB grid to its label, or expands it if it is collapsed, so that days of no
+    /// interest take up less of the screen. The visual representation will be updated on the next call to draw.
+    pub fn toggle_collapsed(&mut self) {
+        if self.screen != MlbScreen::Grid || self.tab != 0 || self.menu_focus.focused().is_some() {
+            return;
+        }
+        let info = &mut self.ui_info;
+        if let Some(day) = info.days.get_mut(info.grid.focused_row) {
+            day.collapsed = !day.collapsed;
+            info.clamp_focus();
+        }
+    }
+
+    /// Pages in the given direction: left and right page the items of the focused rail, and up and down page the rails.
+    /// The visual representation will be updated on the next call to draw.
+    pub fn page(&mut self, direction: FocusDirection) {
+        if let Some(date) = self.date_picker {
+            let months = match direction {
+                FocusDirection::Left => -1,
+                FocusDirection::Right => 1,
+                FocusDirection::Up => -12,
+                FocusDirection::Down => 12,
+            };
+            self.date_picker = Some(shift_months(date, months));
+            return;
+        }
+        if self.screen != MlbScreen::Grid {
+            return;
+        }
+        let info = self.grid_mut();
+        let focused_row = info.grid().focused_row;
+        match direction {
+            FocusDirection::Left => info.page_row(focused_row, -1),
+            FocusDirection::Right => info.page_row(focused_row, 1),
+            FocusDirection::Up => info.page_rows(-1),
+            FocusDirection::Down => info.page_rows(1),
+        }
+        if direction == FocusDirection::Down && self.tab == 0 {
+            self.extend_older_days();
+        }
+    }
+
+    /// Moves the focus in the given direction. Moving up from the top of the grid or a section focuses the menu, which
+    /// is left by moving down. The visual representation will be updated on the next call to draw.
+    pub fn move_focus(&mut self, direction: FocusDirection) {
+        if let Some(date) = self.date_picker {
+            let days = match direction {
+                FocusDirection::Left => -1,
+                FocusDirection::Right => 1,
+                FocusDirection::Up => -7,
+                FocusDirection::Down => 7,
+            };
+            self.date_picker = Some(date + chrono::Duration::days(days));
+            return;
+        }
+        if self.menu_focus.focused().is_some() {
+            match direction {
+                FocusDirection::Left | FocusDirection::Right => {
+                    self.menu_focus.move_focus(direction);
+                }
+                FocusDirection::Up => (),
+                FocusDirection::Down => {
+                    self.menu_focus.unfocus();
+                }
+            }
+            return;
+        }
+        match self.screen {
+            MlbScreen::Loading | MlbScreen::Video => return,
+            MlbScreen::Detail => {
+                match direction {
+                    FocusDirection::Up => self.detail_scroll = self.detail_scroll.saturating_sub(1),
+                    FocusDirection::Down => self.detail_scroll += 1,
+                    FocusDirection::Left | FocusDirection::Right => (),
+                }
+                return;
+            }
+            MlbScreen::Scores => {
+                let focusables = (0..self.scores().len())
+                    .map(|index| Focusable {
+                        key: index,
+                        bounds: Bounds::new(0.0, index as f32 * SCORES_ROW_HEIGHT, 1.0, SCORES_ROW_HEIGHT),
+                    })
+                    .collect();
+                self.scores_focus.set_focusables(focusables);
+                if !self.scores_focus.move_focus(direction) && direction == FocusDirection::Up {
+                    self.menu_focus.focus(MenuSection::Scores);
+                }
+                return;
+            }
+            MlbScreen::Standings | MlbScreen::Settings => {
+                if direction == FocusDirection::Up {
+                    let section = self.active_section();
+                    self.menu_focus.focus(section);
+                }
+                return;
+            }
+            MlbScreen::Grid => (),
+        }
+        let info = self.grid_mut();
+        let grid = info.grid();
+        let from = calc_tile_bounds(grid.focused_index as f32, info.row_position(grid.focused_row));
+        let focusables = info.focusables(direction);
+        match gl_utils::find_nearest(&from, &focusables, direction) {
+            Some((row, index)) => info.focus(row, index),
+            None if direction == FocusDirection::Up && self.tab == 0 && self.extend_upcoming_days() => (),
+            None if direction == FocusDirection::Up => self.menu_focus.focus(MenuSection::Home),
+            None => (),
+        }
+        if direction == FocusDirection::Down && self.tab == 0 {
+            self.extend_older_days();
+        }
+    }
+
+    /// Retrieves the information of the focused game of the MLB grid, or of the game whose detail or video is displayed.
+    /// Returns None if no game is focused.
+    pub fn focused_game(&self) -> Option<&MlbGameClientInfo> {
+        match self.screen {
+            MlbScreen::Grid | MlbScreen::Detail | MlbScreen::Video
+                if self.tab == 0 && self.menu_focus.focused().is_none() =>
+            {
+                self.ui_info.focused_game().map(|game| &game.info)
+            }
+            _ => None,
+        }
+    }
+
+    /// Describes the position of the focus and the paging of the displayed grid.
+    pub fn focus_state(&self) -> FocusState {
+        let info = self.grid();
+        let grid = info.grid();
+        let begin_index = info.rails().get(grid.focused_row).map_or(0, |rail| rail.begin_index());
+        FocusState {
+            screen: self.screen.name(),
+            menu: self.menu_focus.focused(),
+            tab: self.tab,
+            focused_row: grid.focused_row,
+            focused_index: begin_index + grid.focused_index,
+            begin_row: grid.begin_row,
+            begin_index,
+        }
+    }
+
+    /// Describes the menu and the content of the displayed screen for screen readers, with the focused element: the
+    /// rails and items of the grid, the games of the scoreboard, the divisions of the standings, or the settings.
+    pub fn access_tree(&self) -> AccessTree {
+        let mut menu = AccessNode::new(ACCESS_MENU_ID, Role::TabList, String::new());
+        let mut nodes = Vec::new();
+        for (index, section) in MenuSection::ALL.iter().enumerate() {
+            let id = ACCESS_MENU_ID + 1 + index as u64;
+            menu.children.push(id);
+            nodes.push(AccessNode::new(id, Role::Tab, self.strings.get(section.label_id())));
+        }
+        let section_name = self.strings.get(self.active_section().label_id());
+        let mut content = AccessNode::new(ACCESS_CONTENT_ID, Role::Group, section_name);
+        let mut focus = ACCESS_CONTENT_ID;
+        match self.screen {
+            MlbScreen::Loading => content.name = self.strings.get("loading-games"),
+            MlbScreen::Grid => {
+                let grid = self.grid();
+                let focus_grid = grid.grid();
+                for (row, rail) in grid.rails().into_iter().enumerate() {
+                    let rail_id = ACCESS_RAIL_STRIDE * (row as u64 + 1);
+                    let mut rail_node = AccessNode::new(rail_id, Role::List, rail.label(&self.strings));
+                    for index in 0..rail.len() {
+                        if let Some(caption) = rail.caption(index, &self.strings) {
+                            let id = rail_id + 1 + index as u64;
+                            rail_node.children.push(id);
+                            nodes.push(AccessNode {
+                                description: Some(caption.summary),
+                                value: rail.status(index, &self.strings),
+                                ..AccessNode::new(id, Role::ListItem, caption.title)
+                            });
+                        }
+                    }
+                    if row == focus_grid.focused_row {
+                        let index = rail.begin_index() + focus_grid.focused_index;
+                        focus = if rail.caption(index, &self.strings).is_some() {
+                            rail_id + 1 + index as u64
+                        } else {
+                            rail_id
+                        };
+                    }
+                    content.children.push(rail_id);
+                    nodes.push(rail_node);
+                }
+            }
+            MlbScreen::Detail | MlbScreen::Video => {
+                if let Some(game) = self.ui_info.focused_game() {
+                    content.role = if self.screen == MlbScreen::Video {
+                        Role::Video
+                    } else {
+                        Role::Document
+                    };
+                    content.name = game.info.title.clone();
+                    content.description = Some(game.info.summary.clone());
+                    content.value = Some(game.access_status(&self.strings));
+                }
+            }
+            MlbScreen::Scores => {
+                for (index, game) in self.scores().into_iter().enumerate() {
+                    let id = ACCESS_CONTENT_ID + 1 + index as u64;
+                    content.children.push(id);
+                    nodes.push(AccessNode {
+                        value: Some(game.access_status(&self.strings)),
+                        ..AccessNode::new(id, Role::ListItem, game.info.title.clone())
+                    });
+                    if index == self.scores_index() {
+                        focus = id;
+                    }
+                }
+            }
+            MlbScreen::Standings => {
+                let standings = &self.ui_info.standings;
+                for index in 0..standings.len() {
+                    if let Some(caption) = standings.caption(index, &self.strings) {
+                        let id = ACCESS_CONTENT_ID + 1 + index as u64;
+                        content.children.push(id);
+                        nodes.push(AccessNode {
+                            description: Some(caption.summary),
+                            ..AccessNode::new(id, Role::ListItem, caption.title)
+                        });
+                    }
+                }
+            }
+            MlbScreen::Settings => {
+                for (index, (name, value)) in self.settings.iter().enumerate() {
+                    let id = ACCESS_CONTENT_ID + 1 + index as u64;
+                    content.children.push(id);
+                    nodes.push(AccessNode {
+                        value: Some(value.clone()),
+                        ..AccessNode::new(id, Role::ListItem, name.clone())
+                    });
+                }
+            }
+        }
+        if let Some(section) = self.menu_focus.focused() {
+            let index = MenuSection::ALL.iter().position(|other| *other == section).unwrap_or(0);
+            focus = ACCESS_MENU_ID + 1 + index as u64;
+        }
+        let mut tree = vec![
+            AccessNode::window(vec![ACCESS_MENU_ID, ACCESS_CONTENT_ID]),
+            menu,
+            content,
+        ];
+        tree.append(&mut nodes);
+        AccessTree { nodes: tree, focus }
+    }
+}
+
+/// Retrieves the grid of the tab at the given index, where 0 is the MLB grid with the given backing information and
+/// the given leagues follow. This borrows only the grids, so that other resources can be used while drawing them.
+fn active_grid_mut<'a>(
+    tab: usize,
+    ui_info: &'a mut MlbUiInfo,
+    leagues: &'a mut [LeagueUiInfo],
+) -> &'a mut dyn RailGrid {
+    match tab.checked_sub(1).and_then(move |index| leagues.get_mut(index)) {
+        Some(league) => league,
+        None => ui_info,
+    }
+}
+
+/// Splits the given text into consecutive parts, each paired with whether it matches the given search text, ignoring
+/// case.
+fn highlight_matches<'a>(text: &'a str, filter: &str) -> Vec<(&'a str, bool)> {
+    if filter.is_empty() {
+        return vec![(text, false)];
+    }
+    let lowercase = text.to_ascii_lowercase();
+    let filter = filter.to_ascii_lowercase();
+    let mut parts = Vec::new();
+    let mut start = 0;
+    for (index, matched) in lowercase.match_indices(&filter) {
+        if index > start {
+            parts.push((&text[start..index], false));
+        }
+        parts.push((&text[index..index + matched.len()], true));
+        start = index + matched.len();
+    }
+    if start < text.len() {
+        parts.push((&text[start..], false));
+    }
+    parts
+}
+
+/// Determines the horizontal and vertical scales at which an image of the given aspect ratio covers a screen of the
+/// given aspect ratio without distortion, cropping whichever dimension overflows.
+fn cover_scale(image_aspect: f32, screen_aspect: f32) -> (f32, f32) {
+    if image_aspect > screen_aspect {
+        (image_aspect / screen_aspect, 1.0)
+    } else {
+        (1.0, screen_aspect / image_aspect)
+    }
+}
+
+/// Determines the date the given number of months after the given date, or before it if negative, on the same day of
+/// the month, or on the last day of the month if it is shorter.
+fn shift_months(date: NaiveDate, months: i32) -> NaiveDate {
+    let month_index = date.year() * 12 + date.month0() as i32 + months;
+    let (year, month) = (month_index.div_euclid(12), month_index.rem_euclid(12) as u32 + 1);
+    (1..=date.day())
+        .rev()
+        .find_map(|day| NaiveDate::from_ymd_opt(year, month, day))
+        .unwrap_or(date)
+}
+
+/// Determines the ratio of displayed to nominal text size on a screen of the given height with the given scale factor.
+/// Text is proportional to the screen, but no smaller than the scale factor allows to remain legible.
+fn calc_text_ratio(screen_height: f32, scale_factor: f32) -> f32 {
+    f32::max(screen_height / REFERENCE_HEIGHT, MIN_TEXT_RATIO * scale_factor)
+}
+
+/// Determines the on-screen column of the slot containing the given point, as percentages of the screen from the
+/// top-left corner, within the row at the given (possibly fractional) position on screen, given the column that is
+/// focused and therefore enlarged, if any. This is the inverse of `calc_tile_location_percentage` over the visible
+/// slots of the row.
+fn calc_tile_column(x: f32, y: f32, row: f32, focused_col: Option<usize>) -> Option<usize> {
+    (0..X_PAGE_SIZE).find(|col| {
+        let focused = focused_col == Some(*col);
+        let (left, top) = calc_tile_location_percentage(focused, *col as f32, row);
+        let scale = if focused { FOCUSED_TILE_SCALE } else { TILE_SCALE };
+        x >= left && x < left + scale && y >= top && y < top + scale
+    })
+}
+
+/// Determines the (possibly fractional) position on screen, in rows, of the given vertical position, as a percentage of
+/// the screen from the top, where each row includes the space above it.
+fn calc_row_position(y: f32) -> f32 {
+    let (_, first_top) = calc_tile_location_percentage(true, 0.0, 0.0);
+    let (_, second_top) = calc_tile_location_percentage(true, 0.0, 1.0);
+    let row_height = second_top - first_top;
+    (y - first_top + TILE_Y_PADDING) / row_height
+}
+
+/// Queues the box score of the given game, if its details have been retrieved, as a table below the game image of the
+/// detail view, in the text color of the given theme and the text of the given catalog.
+fn queue_box_score(
+    text_brush: &mut GlyphBrush,
+    theme: &Theme,
+    strings: &Strings,
+    game: &MlbGameGlInfo,
+    screen_width: f32,
+    screen_height: f32,
+    text_ratio: f32,
+) {
+    let linescore = match &game.detail {
+        DetailState::Loaded(MlbGameDetail {
+            linescore: Some(linescore),
+            ..
+        }) => linescore,
+        _ => return,
+    };
+    let info = &game.info;
+    let mut header = vec![String::new()];
+    let mut away = vec![info.away_team.clone()];
+    let mut home = vec![info.home_team.clone()];
+    for inning in &linescore.innings {
+        header.push(inning.inning.to_string());
+        away.push(inning.away_runs.map(|runs| runs.to_string()).unwrap_or_default());
+        home.push(inning.home_runs.map(|runs| runs.to_string()).unwrap_or_default());
+    }
+    header.extend(vec![
+        strings.get("box-score-runs"),
+        strings.get("box-score-hits"),
+        strings.get("box-score-errors"),
+    ]);
+    for (row, totals) in [(&mut away, &linescore.away), (&mut home, &linescore.home)].iter_mut() {
+        row.extend(vec![
+            totals.runs.to_string(),
+            totals.hits.to_string(),
+            totals.errors.to_string(),
+        ]);
+    }
+
+    for (row, cells) in [header, away, home].iter().enumerate() {
+        let y_offset = (BOX_SCORE_TOP + BOX_SCORE_ROW_HEIGHT * row as f32) * screen_height;
+        for (col, cell) in cells.iter().enumerate() {
+            let (x_offset, width) = if col == 0 {
+                (LEFT_INDENT, BOX_SCORE_TEAM_WIDTH)
+            } else {
+                (
+                    LEFT_INDENT + BOX_SCORE_TEAM_WIDTH + BOX_SCORE_COLUMN_WIDTH * (col - 1) as f32,
+                    BOX_SCORE_COLUMN_WIDTH,
+                )
+            };
+            text_brush.queue(Section {
+                text: vec![Text::new(cell)
+                    .with_color(theme.text_color)
+                    .with_scale(text_scale(24.0, text_ratio))],
+                screen_position: (x_offset * screen_width, y_offset),
+                bounds: (width * screen_width, BOX_SCORE_ROW_HEIGHT * screen_height),
+                ..Section::default()
+            });
+        }
+    }
+}
+
+/// Creates the animation of the banner sliding into view, remaining displayed for the given time, and sliding out of
+/// view.
+fn banner_slide(duration: Duration) -> Tween {
+    Tween::new(0.0, BANNER_SLIDE_DURATION)
+        .then(1.0, BANNER_SLIDE_DURATION, Easing::Out)
+        .then(1.0, duration, Easing::Out)
+        .then(0.0, BANNER_SLIDE_DURATION, Easing::In)
+        .with_listener(|_| debug!("MLB banner dismissed"))
+}
+
+/// Creates the vertices of a square of the given color covering the screen, to be scaled and translated into a
+/// rectangle.
+///
+/// # Errors
+/// Panics if the given display cannot be used to create the vertices.
+fn rect_vertices(display: &dyn Facade, color: [f32; 4]) -> VertexBuffer<Vertex> {
+    VertexBuffer::new(display, &rect_shape(color)).unwrap_or_else(|ex| {
+        let msg = "Could not create rect vertices";
+        error!("{}:\n{}", msg, ex);
+        panic!("{}.", msg);
+    })
+}
+
+/// Creates the vertices of a square of the given color, spanning the screen before transformation.
+fn rect_shape(color: [f32; 4]) -> [Vertex; 4] {
+    [
+        Vertex {
+            position: [-1.0, -1.0],
+            color,
+        },
+        Vertex {
+            position: [-1.0, 1.0],
+            color,
+        },
+        Vertex {
+            position: [1.0, -1.0],
+            color,
+        },
+        Vertex {
+            position: [1.0, 1.0],
+            color,
+        },
+    ]
+}
+
+/// A container for backing information for a single game.
+struct MlbGameGlInfo {
+    info: MlbGameClientInfo,
+    texture: Option<Rc<SrgbTexture2d>>,
+    /// The decoded image awaiting upload, which replaces the texture on the next call to draw.
+    decoded: Option<DecodedImage>,
+    /// The texture displayed before the image was retrieved, over which the texture of the image fades in.
+    replaced_texture: Option<Rc<SrgbTexture2d>>,
+    fade: Tween,
+    /// Whether the image has been queued for retrieval or was provided with the game, so that it is not requested again
+    /// once its bytes are released.
+    image_requested: bool,
+    image_failed: bool,
+    detail: DetailState,
+    focus: Tween,
+    favorite: bool,
+}
+
+impl MlbGameGlInfo {
+    /// Stores the given decoded image, which fades in over the default image if it is displayed, or records that the
+    /// image could not be retrieved or decoded if None.
+    fn set_image(&mut self, image: Option<DecodedImage>) {
+        match image {
+            Some(image) => {
+                self.decoded = Some(image);
+                // the default image, if displayed, stays beneath the image as it fades in
+                self.replaced_texture = self.texture.take();
+                if self.replaced_texture.is_some() {
+                    self.fade = Tween::new(0.0, tween::FADE_DURATION);
+                }
+            }
+            None => self.image_failed = true,
+        }
+    }
+
+    /// Determines whether the name of either team contains the given search text, ignoring case. Every game matches
+    /// empty text.
+    fn matches(&self, filter: &str) -> bool {
+        let filter = filter.to_lowercase();
+        self.info.away_team.to_lowercase().contains(&filter) || self.info.home_team.to_lowercase().contains(&filter)
+    }
+
+    /// Describes the score of the game as away runs to home runs in the text of the given catalog if it is in progress
+    /// or final, marking final scores.
+    fn score_badge(&self, strings: &Strings) -> Option<String> {
+        let (away_score, home_score) = (self.info.away_score?, self.info.home_score?);
+        let id = match self.info.state {
+            MlbGameState::Live => "badge-score",
+            MlbGameState::Final => "badge-final",
+            MlbGameState::Scheduled => return None,
+        };
+        Some(strings.format(id, &[("away", away_score.into()), ("home", home_score.into())]))
+    }
+
+    /// Describes the state of the game and, if it has started, its score, for screen readers.
+    fn access_status(&self, strings: &Strings) -> String {
+        match (self.info.away_score, self.info.home_score) {
+            (Some(away_score), Some(home_score)) => strings.format(
+                "access-game-status",
+                &[
+                    ("state", self.info.detailed_state.clone().into()),
+                    ("away-team", self.info.away_team.clone().into()),
+                    ("away", away_score.into()),
+                    ("home-team", self.info.home_team.clone().into()),
+                    ("home", home_score.into()),
+                ],
+            ),
+            _ => self.info.detailed_state.clone(),
+        }
+    }
+
+    /// Lazily initializes the texture for the game represented by this container, uploading the decoded image if one
+    /// is awaiting upload. Images provided with the game are decoded here, on the render thread. The pixels and bytes
+    /// of the image are released once its texture is created.
+    fn get_texture(&mut self, display: &dyn Facade) -> &SrgbTexture2d {
+        if let Some(decoded) = self.decoded.take() {
+            self.texture = Some(Rc::new(decoded.into_texture(display)));
+        }
+        if self.texture.is_none() {
+            let decoded = self.info.image.take().and_then(|image| {
+                DecodedImage::decode_retrieved(&image, None)
+                    .map_err(|ex| error!("Could not decode image for {}:\n{}", self.info.title, ex))
+                    .ok()
+            });
+            let decoded = decoded.unwrap_or_else(|| {
+                DecodedImage::decode(DEFAULT_RAW, Some(image::ImageFormat::Jpeg), None).unwrap_or_else(|ex| {
+                    let msg = "Could not create game image from bytes";
+                    error!("{}:\n{}", msg, ex);
+                    panic!("{}.", msg);
+                })
+            });
+            self.texture = Some(Rc::new(decoded.into_texture(display)));
+        }
+        self.texture.as_deref().unwrap()
+    }
+
+    /// Lists the textures of the tile of the game with their opacities, in the order drawn: the texture of the image
+    /// at the given opacity over the texture it replaces while it fades in, or only the texture of the image once it
+    /// has faded in.
+    fn tile_images(&mut self, display: &dyn Facade, fade: f32) -> Vec<(Rc<SrgbTexture2d>, f32)> {
+        self.get_texture(display);
+        if fade >= 1.0 {
+            self.replaced_texture = None;
+        }
+        let texture = Rc::clone(self.texture.as_ref().unwrap());
+        match &self.replaced_texture {
+            Some(replaced) => vec![(Rc::clone(replaced), 1.0), (texture, fade)],
+            None => vec![(texture, 1.0)],
+        }
+    }
+}
+
+impl From<MlbGameClientInfo> for MlbGameGlInfo {
+    fn from(orig: MlbGameClientInfo) -> Self {
+        // an image provided with the game need not be requested
+        let image_requested = orig.image.is_some();
+        MlbGameGlInfo {
+            info: orig,
+            texture: None,
+            decoded: None,
+            replaced_texture: None,
+            fade: Tween::new(1.0, tween::FADE_DURATION),
+            image_requested,
+            image_failed: false,
+            detail: DetailState::NotLoaded,
+            focus: Tween::new(0.0, tween::FOCUS_DURATION),
+            favorite: false,
+        }
+    }
+}
+
+/// A container for backing information for a single day.
+struct DayRowInfo {
+    date: NaiveDate,
+    games: Vec<MlbGameGlInfo>,
+    /// The indices of the games matching the search, in display order.
+    visible: Vec<usize>,
+    /// Whether the games are filtered by search text.
+    filtered: bool,
+    loaded: bool,
+    failed: bool,
+    retrying: bool,
+    /// Whether the day is collapsed to its label, hiding its games.
+    collapsed: bool,
+    begin_index: usize,
+    scroll: Tween,
+}
+
+impl DayRowInfo {
+    /// Initializes the backing information of a day with the given retrieved games.
+    pub fn new(date: NaiveDate, games: Vec<MlbGameGlInfo>) -> Self {
+        DayRowInfo {
+            date,
+            visible: (0..games.len()).collect(),
+            filtered: false,
+            games,
+            loaded: true,
+            failed: false,
+            retrying: false,
+            collapsed: false,
+            begin_index: 0,
+            scroll: Tween::new(0.0, tween::PAGE_DURATION).with_easing(Easing::InOut),
+        }
+    }
+
+    /// Initializes the backing information of a day whose games have not been retrieved.
+    pub fn pending(date: NaiveDate) -> Self {
+        DayRowInfo {
+            loaded: false,
+            ..DayRowInfo::new(date, Vec::new())
+        }
+    }
+
+    /// Determines the number of games matching the search.
+    fn visible_len(&self) -> usize {
+        self.visible.len()
+    }
+
+    /// Retrieves the game at the given index among the games matching the search.
+    fn visible_game(&self, index: usize) -> Option<&MlbGameGlInfo> {
+        self.games.get(*self.visible.get(index)?)
+    }
+
+    /// Retrieves the game at the given index among the games matching the search.
+    fn visible_game_mut(&mut self, index: usize) -> Option<&mut MlbGameGlInfo> {
+        self.games.get_mut(*self.visible.get(index)?)
+    }
+
+    /// Determines the games matching the given search text, keeping the first displayed game within range.
+    fn apply_filter(&mut self, filter: &str) {
+        self.visible = (0..self.games.len())
+            .filter(|index| self.games[*index].matches(filter))
+            .collect();
+        self.filtered = !filter.is_empty();
+        self.begin_index = self.begin_index.min(self.visible.len().saturating_sub(X_PAGE_SIZE));
+    }
+}
+
+impl Rail for DayRowInfo {
+    /// Describes the day by its date, marking days after today as upcoming.
+    fn label(&self, strings: &Strings) -> String {
+        let label = strings.format_date(self.date, "day-label-format");
+        if self.date > Local::today().naive_local() {
+            strings.format("day-label-upcoming", &[("day", label.into())])
+        } else {
+            label
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.visible_len()
+    }
+
+    fn begin_index(&self) -> usize {
+        self.begin_index
+    }
+
+    fn set_begin_index(&mut self, begin_index: usize) {
+        self.begin_index = begin_index;
+    }
+
+    fn is_busy(&self) -> bool {
+        self.retrying
+    }
+
+    fn is_collapsed(&self) -> bool {
+        self.collapsed
+    }
+
+    /// Draws a row of placeholders until the games have been retrieved, and a single placeholder card if there are no
+    /// games matching the search.
+    fn draw(&mut self, painter: &mut RailPainter, y: f32, focused: Option<usize>) {
+        if !self.loaded {
+            for x in 0..X_PAGE_SIZE {
+                let focus = if focused == Some(x) { 1.0 } else { 0.0 };
+                painter.queue_placeholder((x as f32, y), focus);
+            }
+            debug!("MLB placeholders at {} drawn", y);
+            return;
+        }
+        if self.visible_len() == 0 {
+            let focus = if focused.is_some() { 1.0 } else { 0.0 };
+            painter.queue_placeholder((0.0, y), focus);
+            return;
+        }
+        let scroll = painter.animate(&mut self.scroll, self.begin_index as f32);
+        for (index, x) in rail::visible_columns(scroll) {
+            if let Some(game) = self.visible_game_mut(index) {
+                let focus = painter.animate(&mut game.focus, if focused == Some(index) { 1.0 } else { 0.0 });
+                let mut badges = Vec::new();
+                if game.info.state == MlbGameState::Live {
+                    badges.push(BadgeKind::Live);
+                }
+                if let Some(score) = game.score_badge(painter.strings) {
+                    badges.push(BadgeKind::Score(score));
+                }
+                let favorite = game.favorite;
+                let fade = painter.animate(&mut game.fade, 1.0);
+                let images = game.tile_images(painter.display, fade);
+                painter.queue_tile(&images, (x, y), focus, favorite, badges);
+                debug!("MLB game at {}, {} drawn", x, y);
+            }
+        }
+    }
+
+    /// Queues the text of the placeholder card displayed if there are no games matching the search, explaining whether
+    /// the games could not be retrieved.
+    fn queue_text(&self, painter: &RailPainter, text_brush: &mut GlyphBrush, y: f32, focused: Option<usize>) {
+        if !self.loaded || self.visible_len() > 0 {
+            return;
+        }
+        let text = painter.strings.get(if self.failed {
+            "games-failed"
+        } else if self.filtered {
+            "no-matches"
+        } else {
+            "no-games"
+        });
+        let focus = if focused.is_some() { 1.0 } else { 0.0 };
+        painter.queue_tile_text(text_brush, &text, painter.theme.secondary_text_color, (0.0, y), focus);
+    }
+
+    fn status(&self, index: usize, strings: &Strings) -> Option<String> {
+        self.visible_game(index).map(|game| game.access_status(strings))
+    }
+
+    /// Describes the game by its title and summary, preceded by the probable starting pitchers of scheduled games once
+    /// either has been announced.
+    fn caption(&self, index: usize, strings: &Strings) -> Option<Caption> {
+        let game = self.visible_game(index)?;
+        let info = &game.info;
+        let summary = match (&info.away_pitcher, &info.home_pitcher) {
+            (None, None) => info.summary.clone(),
+            (away, home) => {
+                let tbd = strings.get("probable-pitcher-tbd");
+                let pitchers = strings.format(
+                    "probable-pitchers",
+                    &[
+                        ("away", away.as_deref().unwrap_or(&tbd).to_owned().into()),
+                        ("home", home.as_deref().unwrap_or(&tbd).to_owned().into()),
+                    ],
+                );
+                format!("{}\n{}", pitchers, info.summary)
+            }
+        };
+        Some(Caption {
+            title: info.title.clone(),
+            summary,
+            accent: game.favorite,
+        })
+    }
+
+    fn select(&self, index: usize) -> Option<u64> {
+        self.visible_game(index).map(|game| game.info.game_pk)
+    }
+}
 
 /// A container for MLB UI backing information.
-pub struct MlbUiInfo {
+struct MlbUiInfo {
     days: Vec<DayRowInfo>,
-    focused_day: usize,
-    focused_index: usize,
+    stories: StoriesRail,
+    standings: StandingsRail,
+    grid: GridFocus,
+    loaded_at: DateTime<Local>,
+    filter: String,
 }
 
 impl MlbUiInfo {
-    /// Asynchronously initializes the backing information container.
-    pub async fn init() -> Self {
-        let result = dss_mlb::get_games().await;
-        let mut days = Vec::with_capacity(result.len());
-        for day in result.values().rev() {
-            let mut games: Vec<MlbGameGlInfo> = Vec::with_capacity(day.len());
-            for game in day {
-                games.push(game.to_owned().into());
+    /// Initializes the backing information container with placeholders for the given days, in the given order.
+    pub fn pending(dates: Vec<NaiveDate>) -> Self {
+        MlbUiInfo {
+            days: dates.into_iter().map(DayRowInfo::pending).collect(),
+            ..MlbUiInfo::default()
+        }
+    }
+
+    /// Replaces the placeholders or previously retrieved games of the given day with the given games, inserting the day
+    /// in date order if it is not displayed. Games involving a favorite team are moved to the front of the day, in their
+    /// original order. The stories are rebuilt from the recaps of every displayed day, which were retrieved at the given
+    /// time.
+    fn set_games(&mut self, date: NaiveDate, mut games: Vec<MlbGameGlInfo>, loaded_at: DateTime<Local>) {
+        games.sort_by_key(|game| !game.favorite);
+        match self.days.iter_mut().find(|day| day.date == date) {
+            Some(day) => {
+                day.games = games;
+                day.loaded = true;
+                day.failed = false;
+                day.retrying = false;
+                day.apply_filter(&self.filter);
             }
-            days.push(DayRowInfo::new(games));
+            None => {
+                let mut day = DayRowInfo::new(date, games);
+                day.apply_filter(&self.filter);
+                self.insert_day(day);
+            }
+        }
+        let recaps = self
+            .days
+            .iter()
+            .flat_map(|day| day.games.iter().map(move |game| (day.date, &game.info)));
+        self.stories.set_games(recaps);
+        self.clamp_focus();
+        self.loaded_at = loaded_at;
+    }
+
+    /// Inserts the given day among the days, which are ordered from the most recent, keeping the focus on the same
+    /// rail. Returns the row of the inserted day.
+    fn insert_day(&mut self, day: DayRowInfo) -> usize {
+        let row = self.days.iter().take_while(|other| other.date > day.date).count();
+        self.days.insert(row, day);
+        if row <= self.grid.focused_row && self.grid.focused_row + 1 < self.rails().len() {
+            self.grid.focused_row += 1;
+        }
+        row
+    }
+
+    /// Retrieves the backing information of the focused game, if there is a game at the focused location.
+    fn focused_game(&self) -> Option<&MlbGameGlInfo> {
+        let day = self.days.get(self.grid.focused_row).filter(|day| !day.collapsed)?;
+        day.visible_game(day.begin_index + self.grid.focused_index)
+    }
+
+    /// Retrieves the backing information of the focused game, if there is a game at the focused location.
+    fn focused_game_mut(&mut self) -> Option<&mut MlbGameGlInfo> {
+        let day = self.days.get_mut(self.grid.focused_row).filter(|day| !day.collapsed)?;
+        let index = day.begin_index + self.grid.focused_index;
+        day.visible_game_mut(index)
+    }
+
+    /// Focuses the game with the given identifier among the games matching the search. Returns whether the game was
+    /// found.
+    fn focus_game(&mut self, game_pk: u64) -> bool {
+        let found = self.days.iter().enumerate().find_map(|(row, day)| {
+            (0..day.visible_len())
+                .find(|index| day.visible_game(*index).map(|game| game.info.game_pk) == Some(game_pk))
+                .map(|index| (row, index))
+        });
+        match found {
+            Some((row, index)) => {
+                self.days[row].collapsed = false;
+                self.focus(row, index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Displays only games of teams whose names contain the given text, ignoring case, or every game if it is empty.
+    /// Each day is displayed from its first matching game.
+    fn set_filter(&mut self, filter: String) {
+        self.filter = filter;
+        for day in &mut self.days {
+            day.begin_index = 0;
+            day.apply_filter(&self.filter);
         }
+        self.grid.focused_index = 0;
+    }
+}
+
+impl RailGrid for MlbUiInfo {
+    /// Lists a rail for each day, then the stories and standings if they have items.
+    fn rails(&self) -> Vec<&dyn Rail> {
+        let mut rails: Vec<&dyn Rail> = self.days.iter().map(|day| day as &dyn Rail).collect();
+        if !self.stories.is_empty() {
+            rails.push(&self.stories);
+        }
+        if !self.standings.is_empty() {
+            rails.push(&self.standings);
+        }
+        rails
+    }
+
+    fn rails_mut(&mut self) -> Vec<&mut dyn Rail> {
+        let mut rails: Vec<&mut dyn Rail> = self.days.iter_mut().map(|day| day as &mut dyn Rail).collect();
+        if !self.stories.is_empty() {
+            rails.push(&mut self.stories);
+        }
+        if !self.standings.is_empty() {
+            rails.push(&mut self.standings);
+        }
+        rails
+    }
+
+    fn grid(&self) -> &GridFocus {
+        &self.grid
+    }
+
+    fn grid_mut(&mut self) -> &mut GridFocus {
+        &mut self.grid
+    }
+}
+
+impl Default for MlbUiInfo {
+    fn default() -> Self {
         MlbUiInfo {
-            days,
-            focused_day: 0,
-            focused_index: 0,
+            days: Vec::new(),
+            stories: StoriesRail::new(),
+            standings: StandingsRail::new(),
+            grid: GridFocus::new(),
+            loaded_at: Local::now(),
+            filter: String::new(),
         }
     }
 }
@@ -1,49 +1,22 @@
 //! General purpose OpenGL utilities.
 
 use std::borrow::Cow;
-use std::ops::Deref;
+use std::collections::HashMap;
+use std::rc::Rc;
 
-use glium::backend::{Context, Facade};
+use crate::shaders;
+use glium::backend::Facade;
 use glium::index::{NoIndices, PrimitiveType};
 use glium::texture::texture2d::Texture2d;
-use glium::texture::{ClientFormat, RawImage2d};
-use glium::{Blend, DrawParameters, Frame, Program, Surface, VertexBuffer};
-use glyph_brush::ab_glyph::FontArc;
-use glyph_brush::{BrushAction, BrushError, Extra, Section};
+use glium::texture::{ClientFormat, MipmapsOption, RawImage2d, SrgbTexture2d, TextureCreationError};
+use glium::uniforms::{MagnifySamplerFilter, MinifySamplerFilter, Sampler, SamplerWrapFunction};
+use glium::{Blend, DrawParameters, Frame, IndexBuffer, Program, Surface, VertexBuffer};
+use glyph_brush::ab_glyph::{Font, FontArc, GlyphId, GlyphImageFormat, PxScale, ScaleFont};
+use glyph_brush::{BrushAction, BrushError, Extra, FontId, GlyphCruncher, OwnedSection, OwnedText, Section, Text};
 use log::error;
 use rusttype::{point, Rect};
-
-/// The vertex shader program used to render an image.
-pub const IMAGE_VERTEX_SHADER_SRC: &str = r#"
-    #version 140
-
-    uniform mat4 matrix;
-
-    in vec2 position;
-    in vec2 tex_coords;
-
-    out vec2 v_tex_coords;
-            
-    void main() {
-        v_tex_coords = tex_coords;
-        gl_Position = matrix * vec4(position, 0.0, 1.0);
-    }
-"#;
-
-/// The fragment shader program used to render an image.
-pub const IMAGE_FRAGMENT_SHADER_SRC: &str = r#"
-    #version 140
-
-    uniform sampler2D tex;
-
-    in vec2 v_tex_coords;
-
-    out vec4 color;
-    
-    void main() {
-        color = texture(tex, v_tex_coords);
-    }
-"#;
+use ttf_parser::colr::{ClipBox, CompositeMode, Paint, Painter};
+use ttf_parser::RgbaColor;
 
 /// A container for the position of a vertex and the associated texture.
 #[derive(Copy, Clone)]
@@ -53,35 +26,159 @@ pub struct ImageVertex {
 }
 implement_vertex!(ImageVertex, position, tex_coords);
 
-/// The vertex shader program used to render a point with a color.
-pub const RECT_VERTEX_SHADER_SRC: &str = r#"
-    #version 140
+/// A container for a vertex of a sprite: its position, the coordinates of the texture sampled there, and its position
+/// within the sprite from 0 to 1, with the tint, size in pixels, and corner radius in pixels of the sprite.
+#[derive(Copy, Clone)]
+struct SpriteVertex {
+    position: [f32; 2],
+    tex_coords: [f32; 2],
+    local: [f32; 2],
+    tint: [f32; 4],
+    size: [f32; 2],
+    radius: f32,
+}
+implement_vertex!(SpriteVertex, position, tex_coords, local, tint, size, radius);
+
+/// The region of a texture covering all of it, from the top-left corner of the image.
+pub const FULL_TEXTURE: Bounds = Bounds {
+    left: 0.0,
+    top: 0.0,
+    right: 1.0,
+    bottom: 1.0,
+};
 
-    uniform mat4 matrix;
+/// A textured quad queued in a sprite batch, as the two triangles drawn for it.
+struct Sprite {
+    /// The texture sampled by the quad, or None to fill it with its tint.
+    texture: Option<Rc<SrgbTexture2d>>,
+    vertices: [SpriteVertex; 6],
+}
 
-    in vec2 position;
-    in vec4 color;
+/// A list of textured quads accumulated over a frame, drawn in the order they were queued by `SpriteBatcher::flush`.
+pub struct SpriteBatch {
+    sprites: Vec<Sprite>,
+    /// The dimensions in pixels of the screen, as which the corners of quads are rounded.
+    screen: (f32, f32),
+}
 
-    out vec4 f_color;
+impl SpriteBatch {
+    /// Initializes an empty batch for a screen of the given dimensions in pixels.
+    pub fn new(screen_width: f32, screen_height: f32) -> Self {
+        SpriteBatch {
+            sprites: Vec::new(),
+            screen: (screen_width, screen_height),
+        }
+    }
 
-    void main() {
-        f_color = color;
-        gl_Position = matrix * vec4(position, 0.0, 1.0);
+    /// Queues a quad over the given rect, as percentages of the screen, sampling the given region of the given texture,
+    /// or filled if None, multiplied by the given sRGB tint, with corners of the given radius in pixels.
+    pub fn queue(&mut self, texture: Option<Rc<SrgbTexture2d>>, rect: Bounds, uv: Bounds, tint: [f32; 4], radius: f32) {
+        let size = [rect.width() * self.screen.0, rect.height() * self.screen.1];
+        // textures are uploaded from the bottom row up, so the top of the image is at 1
+        let corner = |x: f32, y: f32| SpriteVertex {
+            position: [
+                -1.0 + (rect.left + x * rect.width()) * 2.0,
+                1.0 - (rect.top + y * rect.height()) * 2.0,
+            ],
+            tex_coords: [uv.left + x * uv.width(), 1.0 - (uv.top + y * uv.height())],
+            local: [x, y],
+            tint,
+            size,
+            radius,
+        };
+        let (top_left, top_right) = (corner(0.0, 0.0), corner(1.0, 0.0));
+        let (bottom_left, bottom_right) = (corner(0.0, 1.0), corner(1.0, 1.0));
+        self.sprites.push(Sprite {
+            texture,
+            vertices: [top_left, top_right, bottom_left, top_right, bottom_right, bottom_left],
+        });
     }
-"#;
+}
+
+/// A container for the shader with which sprite batches are drawn, and the blank texture sampled by quads without one.
+pub struct SpriteBatcher {
+    program: Program,
+    blank: Rc<SrgbTexture2d>,
+}
 
-/// The fragment shader program used to apply the color of a vertex.
-pub const RECT_FRAGMENT_SHADER_SRC: &str = r#"
-    #version 140
-    
-    in vec4 f_color;
+impl SpriteBatcher {
+    /// Initializes the shader and blank texture with the given display.
+    ///
+    /// # Errors
+    /// Panics if the given display cannot be used to create the shader or the texture.
+    pub fn init(display: &dyn Facade) -> Self {
+        let program = shaders::SPRITE.compile(display);
+        let blank =
+            mipmapped_texture(display, RawImage2d::from_raw_rgba(vec![255u8; 4], (1, 1))).unwrap_or_else(|ex| {
+                let msg = "Could not create blank sprite texture";
+                error!("{}:\n{}", msg, ex);
+                panic!("{}.", msg);
+            });
+        SpriteBatcher {
+            program,
+            blank: Rc::new(blank),
+        }
+    }
 
-    out vec4 color;
+    /// Recompiles the shader from its source file with the given display, keeping the current shader if it fails.
+    pub fn reload_shaders(&mut self, display: &dyn Facade) {
+        shaders::SPRITE.reload_into(display, &mut self.program);
+    }
 
-    void main() {
-        color = f_color;
+    /// Draws the quads of the given batch within the given viewport, in as few draw calls as their textures allow: one
+    /// for each run of consecutive quads sampling the same texture, with quads without a texture sharing one.
+    ///
+    /// # Errors
+    /// Panics if the given display cannot be used to upload the quads, or the given target cannot be used to render
+    /// them.
+    pub fn flush(&self, display: &dyn Facade, target: &mut Frame, viewport: glium::Rect, batch: SpriteBatch) {
+        if batch.sprites.is_empty() {
+            return;
+        }
+        let vertices: Vec<SpriteVertex> = batch
+            .sprites
+            .iter()
+            .flat_map(|sprite| sprite.vertices.iter().copied())
+            .collect();
+        let buffer = VertexBuffer::new(display, &vertices).unwrap_or_else(|ex| {
+            let msg = "Could not create sprite vertices";
+            error!("{}:\n{}", msg, ex);
+            panic!("{}.", msg);
+        });
+        let parameters = DrawParameters {
+            blend: Blend::alpha_blending(),
+            ..viewport_parameters(viewport)
+        };
+        let mut start = 0;
+        while start < batch.sprites.len() {
+            let texture = batch.sprites[start].texture.as_ref().unwrap_or(&self.blank);
+            let run = batch.sprites[start..]
+                .iter()
+                .take_while(|sprite| Rc::ptr_eq(sprite.texture.as_ref().unwrap_or(&self.blank), texture))
+                .count();
+            let uniforms = uniform! {
+                tex: tile_sampler(texture),
+            };
+            let slice = buffer
+                .slice(start * 6..(start + run) * 6)
+                .expect("Unable to slice sprite vertices.");
+            target
+                .draw(
+                    slice,
+                    &NoIndices(PrimitiveType::TrianglesList),
+                    &self.program,
+                    &uniforms,
+                    &parameters,
+                )
+                .unwrap_or_else(|ex| {
+                    let msg = "Target could not draw sprites";
+                    error!("{}:\n{}", msg, ex);
+                    panic!("{}.", msg);
+                });
+            start += run;
+        }
     }
-"#;
+}
 
 /// A container for the position and color of a vertex.
 #[derive(Copy, Clone)]
@@ -91,79 +188,269 @@ pub struct Vertex {
 }
 implement_vertex!(Vertex, position, color);
 
-/// The vertex shader program used to render a glyph.
-pub const GLYPH_VERTEX_SHADER_SRC: &str = r#"
-    #version 150
+/// A container for the position of a vertex of a shape whose color is given when it is drawn.
+#[derive(Copy, Clone)]
+struct ShapeVertex {
+    position: [f32; 2],
+}
+implement_vertex!(ShapeVertex, position);
+
+/// A container for the shader and the geometry, a square spanning the screen before transformation, with which
+/// rectangles with rounded corners are drawn, so that borders, backgrounds, badges, and dots share one implementation.
+pub struct RoundedRectPainter {
+    program: Program,
+    vertices: VertexBuffer<ShapeVertex>,
+}
+
+impl RoundedRectPainter {
+    /// Initializes the shader and geometry with the given display.
+    ///
+    /// # Errors
+    /// Panics if the given display cannot be used to create the shader or the geometry.
+    pub fn init(display: &dyn Facade) -> Self {
+        let program = shaders::ROUNDED_RECT.compile(display);
+        let shape = [[-1.0, -1.0], [-1.0, 1.0], [1.0, -1.0], [1.0, 1.0]].map(|position| ShapeVertex { position });
+        let vertices = VertexBuffer::new(display, &shape).unwrap_or_else(|ex| {
+            let msg = "Could not create rounded rect vertices";
+            error!("{}:\n{}", msg, ex);
+            panic!("{}.", msg);
+        });
+        RoundedRectPainter { program, vertices }
+    }
 
-    const mat4 INVERT_Y_AXIS = mat4(
-        vec4(1.0, 0.0, 0.0, 0.0),
-        vec4(0.0, -1.0, 0.0, 0.0),
-        vec4(0.0, 0.0, 1.0, 0.0),
-        vec4(0.0, 0.0, 0.0, 1.0)
-    );
+    /// Recompiles the shader from its source file with the given display, keeping the current shader if it fails.
+    pub fn reload_shaders(&mut self, display: &dyn Facade) {
+        shaders::ROUNDED_RECT.reload_into(display, &mut self.program);
+    }
 
-    uniform mat4 transform;
+    /// Fills the given rect, as percentages of the given viewport, with corners of the given radius in pixels in the
+    /// given sRGB color.
+    ///
+    /// # Errors
+    /// Panics if the given target cannot be used to render the rect.
+    pub fn draw_rounded_rect(
+        &self,
+        target: &mut Frame,
+        viewport: glium::Rect,
+        rect: Bounds,
+        radius: f32,
+        color: [f32; 4],
+    ) {
+        self.draw(target, viewport, rect, radius, 0.0, color);
+    }
 
-    in vec3 left_top;
-    in vec2 right_bottom;
-    in vec2 tex_left_top;
-    in vec2 tex_right_bottom;
-    in vec4 color;
+    /// Draws the outline of the given rect, as percentages of the given viewport, with corners of the given radius in
+    /// pixels, in lines of the given width in pixels in the given sRGB color.
+    ///
+    /// # Errors
+    /// Panics if the given target cannot be used to render the outline.
+    pub fn draw_rounded_outline(
+        &self,
+        target: &mut Frame,
+        viewport: glium::Rect,
+        rect: Bounds,
+        radius: f32,
+        line_width: f32,
+        color: [f32; 4],
+    ) {
+        self.draw(target, viewport, rect, radius, line_width, color);
+    }
 
-    out vec2 f_tex_pos;
-    out vec4 f_color;
+    /// Draws the given rect, filled if the given line width is 0, and otherwise outlined.
+    ///
+    /// # Errors
+    /// Panics if the given target cannot be used to render the rect.
+    fn draw(
+        &self,
+        target: &mut Frame,
+        viewport: glium::Rect,
+        rect: Bounds,
+        radius: f32,
+        line_width: f32,
+        color: [f32; 4],
+    ) {
+        let uniforms = uniform! {
+            matrix: Transform::onto(rect).matrix(),
+            fill: color,
+            size: [rect.width() * viewport.width as f32, rect.height() * viewport.height as f32],
+            radius: radius,
+            line_width: line_width,
+        };
+        target
+            .draw(
+                &self.vertices,
+                &NoIndices(PrimitiveType::TriangleStrip),
+                &self.program,
+                &uniforms,
+                &DrawParameters {
+                    blend: Blend::alpha_blending(),
+                    ..viewport_parameters(viewport)
+                },
+            )
+            .unwrap_or_else(|ex| {
+                let msg = "Target could not draw rounded rect";
+                error!("{}:\n{}", msg, ex);
+                panic!("{}.", msg);
+            });
+    }
+}
 
-    // generate positional data based on vertex ID
-    void main() {
-        vec2 pos = vec2(0.0);
-        float left = left_top.x;
-        float right = right_bottom.x;
-        float top = left_top.y;
-        float bottom = right_bottom.y;
+/// A container for a texture drawn as a nine-patch: its corners keep their size, its edges stretch along their length,
+/// and its center stretches in both directions, so that chrome such as frames and backgrounds can be drawn from a small
+/// image at any size without distortion.
+pub struct NinePatch {
+    texture: SrgbTexture2d,
+    /// The width in texels of the border of the texture, which is not stretched along its length.
+    inset: f32,
+    /// The 16 vertices at the corners of the patches, in rows from the top, positioned by each draw.
+    vertices: VertexBuffer<ImageVertex>,
+    indices: IndexBuffer<u16>,
+}
 
-        switch (gl_VertexID) {
-            case 0:
-                pos = vec2(left, top);
-                f_tex_pos = tex_left_top;
-                break;
-            case 1:
-                pos = vec2(right, top);
-                f_tex_pos = vec2(tex_right_bottom.x, tex_left_top.y);
-                break;
-            case 2:
-                pos = vec2(left, bottom);
-                f_tex_pos = vec2(tex_left_top.x, tex_right_bottom.y);
-                break;
-            case 3:
-                pos = vec2(right, bottom);
-                f_tex_pos = tex_right_bottom;
-                break;
+impl NinePatch {
+    /// Initializes a nine-patch of the given texture, whose border of the given width in texels is not stretched along
+    /// its length.
+    ///
+    /// # Errors
+    /// Panics if the given display cannot be used to create the geometry.
+    pub fn init(display: &dyn Facade, texture: SrgbTexture2d, inset: f32) -> Self {
+        let vertices = VertexBuffer::empty_dynamic(display, 16).unwrap_or_else(|ex| {
+            let msg = "Could not create nine-patch vertices";
+            error!("{}:\n{}", msg, ex);
+            panic!("{}.", msg);
+        });
+        // two triangles for each patch, between the vertex at its top-left corner and those right of and below it
+        let indices: Vec<u16> = (0..3)
+            .flat_map(|row| (0..3).map(move |col| row * 4 + col))
+            .flat_map(|corner| vec![corner, corner + 1, corner + 4, corner + 1, corner + 5, corner + 4])
+            .collect();
+        let indices = IndexBuffer::new(display, PrimitiveType::TrianglesList, &indices).unwrap_or_else(|ex| {
+            let msg = "Could not create nine-patch indices";
+            error!("{}:\n{}", msg, ex);
+            panic!("{}.", msg);
+        });
+        NinePatch {
+            texture,
+            inset,
+            vertices,
+            indices,
         }
+    }
+
+    /// Retrieves the texture of the nine-patch.
+    pub fn texture(&self) -> &SrgbTexture2d {
+        &self.texture
+    }
 
-        f_color = color;
-        gl_Position = INVERT_Y_AXIS * transform * vec4(pos, left_top.z, 1.0);
+    /// Draws the nine-patch over the given rect, as percentages of the given viewport, with its border drawn the given
+    /// width in pixels, or less if the rect is too small, using the given program created from the image shaders.
+    ///
+    /// # Errors
+    /// Panics if the given target cannot be used to render the nine-patch.
+    pub fn draw(&self, target: &mut Frame, program: &Program, viewport: glium::Rect, rect: Bounds, border: f32) {
+        let border_x = (border / viewport.width as f32).min(rect.width() / 2.0);
+        let border_y = (border / viewport.height as f32).min(rect.height() / 2.0);
+        let xs = [rect.left, rect.left + border_x, rect.right - border_x, rect.right];
+        let ys = [rect.top, rect.top + border_y, rect.bottom - border_y, rect.bottom];
+        let (texture_width, texture_height) = self.texture.dimensions();
+        let u = (self.inset / texture_width as f32).min(0.5);
+        let v = (self.inset / texture_height as f32).min(0.5);
+        let us = [0.0, u, 1.0 - u, 1.0];
+        // textures are uploaded from the bottom row up, so the top of the image is at 1
+        let vs = [1.0, 1.0 - v, v, 0.0];
+        let shape: Vec<ImageVertex> = ys
+            .iter()
+            .zip(&vs)
+            .flat_map(|(y, v)| {
+                xs.iter().zip(&us).map(move |(x, u)| ImageVertex {
+                    position: [-1.0 + x * 2.0, 1.0 - y * 2.0],
+                    tex_coords: [*u, *v],
+                })
+            })
+            .collect();
+        self.vertices.write(&shape);
+        let uniforms = uniform! {
+            matrix: Transform::new().matrix(),
+            tex: self
+                .texture
+                .sampled()
+                .wrap_function(SamplerWrapFunction::Clamp)
+                .minify_filter(MinifySamplerFilter::Linear)
+                .magnify_filter(MagnifySamplerFilter::Linear),
+            alpha: 1.0f32,
+            size: [0.0, 0.0f32],
+            radius: 0.0f32,
+        };
+        target
+            .draw(
+                &self.vertices,
+                &self.indices,
+                program,
+                &uniforms,
+                &DrawParameters {
+                    blend: Blend::alpha_blending(),
+                    ..viewport_parameters(viewport)
+                },
+            )
+            .unwrap_or_else(|ex| {
+                let msg = "Target could not draw nine-patch";
+                error!("{}:\n{}", msg, ex);
+                panic!("{}.", msg);
+            });
     }
-"#;
+}
 
-/// The fragment shader program used to render a glyph.
-pub const GLYPH_FRAGMENT_SHADER_SRC: &str = r#"
-    #version 150
+/// Determines the area of a framebuffer of the given dimensions in which content is laid out: the largest area of the
+/// given aspect ratio, or of any aspect ratio if None, centered within the framebuffer once each edge is inset by the
+/// given fraction of the dimension it spans, as a safe area for TVs that overscan.
+pub fn content_area(dimensions: (u32, u32), aspect: Option<f32>, safe_area: f32) -> glium::Rect {
+    let (width, height) = (dimensions.0 as f32, dimensions.1 as f32);
+    let (safe_width, safe_height) = (width * (1.0 - 2.0 * safe_area), height * (1.0 - 2.0 * safe_area));
+    let (content_width, content_height) = match aspect {
+        Some(aspect) if safe_width > safe_height * aspect => (safe_height * aspect, safe_height),
+        Some(aspect) => (safe_width, safe_width / aspect),
+        None => (safe_width, safe_height),
+    };
+    glium::Rect {
+        left: ((width - content_width) / 2.0).round() as u32,
+        bottom: ((height - content_height) / 2.0).round() as u32,
+        width: (content_width.round() as u32).max(1),
+        height: (content_height.round() as u32).max(1),
+    }
+}
 
-    uniform sampler2D font_tex;
+/// Creates the parameters with which content is drawn within the given area of the framebuffer, so that the
+/// coordinates of a draw span only that area.
+pub fn viewport_parameters(viewport: glium::Rect) -> DrawParameters<'static> {
+    DrawParameters {
+        viewport: Some(viewport),
+        ..DrawParameters::default()
+    }
+}
 
-    in vec2 f_tex_pos;
-    in vec4 f_color;
+/// The most samples taken along the axis of anisotropy when sampling a tile texture.
+const TILE_ANISOTROPY: u16 = 4;
 
-    out vec4 Target0;
+/// Creates a texture of the given sRGB image with a full chain of mipmaps, so that it can be drawn at any scale with
+/// `tile_sampler`.
+///
+/// # Errors
+/// * If the texture cannot be created with the given display.
+pub fn mipmapped_texture(display: &dyn Facade, image: RawImage2d<u8>) -> Result<SrgbTexture2d, TextureCreationError> {
+    SrgbTexture2d::with_mipmaps(display, image, MipmapsOption::AutoGeneratedMipmaps)
+}
 
-    void main() {
-        float alpha = texture(font_tex, f_tex_pos).r;
-        if (alpha <= 0.0) {
-            discard;
-        }
-        Target0 = f_color * vec4(1.0, 1.0, 1.0, alpha);
-    }
-"#;
+/// Configures the sampling of the given texture of a tile, which is drawn at several scales as it gains and loses focus.
+/// Filtering is trilinear across the mipmaps of the texture so that it does not shimmer as it scales, and coordinates
+/// are clamped so that the opposite edges do not bleed into the rounded corners.
+pub fn tile_sampler(texture: &SrgbTexture2d) -> Sampler<'_, SrgbTexture2d> {
+    texture
+        .sampled()
+        .wrap_function(SamplerWrapFunction::Clamp)
+        .minify_filter(MinifySamplerFilter::LinearMipmapLinear)
+        .magnify_filter(MagnifySamplerFilter::Linear)
+        .anisotropy(TILE_ANISOTROPY)
+}
 
 #[derive(Copy, Clone, Debug)]
 pub struct GlyphVertex {
@@ -198,6 +485,37 @@ pub type DefaultSectionHasher = twox_hash::RandomXxHashBuilder;
 #[cfg(target_arch = "wasm32")]
 pub type DefaultSectionHasher = std::hash::BuildHasherDefault<twox_hash::XxHash>;
 
+/// The offset in pixels of the shadow drawn behind text.
+const SHADOW_OFFSET: [f32; 2] = [2.0, 2.0];
+/// The offsets in pixels of the copies of text drawn behind it as an outline.
+const OUTLINE_OFFSETS: [[f32; 2]; 4] = [[-1.0, 0.0], [1.0, 0.0], [0.0, -1.0], [0.0, 1.0]];
+
+/// The font of bold text among the fonts of a `GlyphBrush`.
+const BOLD_FONT: FontId = FontId(1);
+/// The first of the fonts of a `GlyphBrush` displaying characters missing from the regular and bold fonts.
+const FIRST_FALLBACK_FONT: usize = 2;
+
+/// The distance in pixels from the outline of a glyph at which its distance field saturates.
+const GLYPH_DISTANCE_SPREAD: f32 = 4.0;
+/// The largest difference in pixels between the scale of text and the scale at which a cached glyph was rasterized for
+/// which the cached glyph is scaled rather than rasterized again, so that text scaled by animations reuses its glyphs.
+const GLYPH_SCALE_TOLERANCE: f32 = 4.0;
+/// The size in pixels of the larger side of the textures into which the layers of COLR glyphs are composited.
+const COLOR_GLYPH_SIZE: f32 = 128.0;
+
+/// The character appended to text that has been shortened to fit.
+const ELLIPSIS: char = '\u{2026}';
+
+/// Shortens the given line until it, followed by an ellipsis, is no wider than the given width according to the given
+/// measurement. Trailing whitespace before the ellipsis is removed.
+fn ellipsize<M: Fn(&str) -> f32>(line: &str, width: f32, measure: M) -> String {
+    let mut shortened = line.trim_end_matches(ELLIPSIS).to_owned();
+    while !shortened.is_empty() && measure(&format!("{}{}", shortened, ELLIPSIS)) > width {
+        shortened.pop();
+    }
+    format!("{}{}", shortened.trim_end(), ELLIPSIS)
+}
+
 fn rect_to_rect(rect: Rect<u32>) -> glium::Rect {
     glium::Rect {
         left: rect.min.x,
@@ -220,9 +538,246 @@ fn rect_from_rect(rect: glyph_brush::Rectangle<u32>) -> Rect<u32> {
     }
 }
 
+/// Decodes the color image of the given glyph of the given font, from its largest strike, into a texture. Returns None if
+/// the glyph has no image in a color format or it cannot be decoded, which is logged.
+fn color_glyph_texture(display: &dyn Facade, font: &FontArc, id: GlyphId) -> Option<SrgbTexture2d> {
+    let image = font.glyph_raster_image2(id, u16::MAX)?;
+    let (rgba, dimensions) = match image.format {
+        GlyphImageFormat::Png => {
+            let decoded = image::load_from_memory_with_format(image.data, image::ImageFormat::Png)
+                .map_err(|ex| error!("Could not decode color glyph {:?}:\n{}", id, ex))
+                .ok()?
+                .into_rgba();
+            let dimensions = decoded.dimensions();
+            (decoded.into_raw(), dimensions)
+        }
+        GlyphImageFormat::BitmapPremulBgra32 => {
+            // the premultiplied blue, green, red, and alpha of each pixel become straight red, green, blue, and alpha
+            let rgba = image
+                .data
+                .chunks_exact(4)
+                .flat_map(|pixel| {
+                    let unpremultiply = |value: u8| match pixel[3] {
+                        0 => 0,
+                        alpha => (u16::from(value) * 255 / u16::from(alpha)).min(255) as u8,
+                    };
+                    vec![
+                        unpremultiply(pixel[2]),
+                        unpremultiply(pixel[1]),
+                        unpremultiply(pixel[0]),
+                        pixel[3],
+                    ]
+                })
+                .collect();
+            (rgba, (u32::from(image.width), u32::from(image.height)))
+        }
+        _ => return None,
+    };
+    mipmapped_texture(display, RawImage2d::from_raw_rgba_reversed(&rgba, dimensions))
+        .map_err(|ex| error!("Could not create color glyph texture:\n{}", ex))
+        .ok()
+}
+
+/// A layer of a glyph of a COLR font: the glyph whose outline it fills, and the color it is filled with, or None if it
+/// is filled with the color of the text.
+struct ColorLayer {
+    id: GlyphId,
+    color: Option<[u8; 4]>,
+}
+
+/// Collects the layers of a COLR glyph as they are painted. Only the solid layers of version 0 of the table are
+/// supported, so any gradient, clip, transform, or composite of version 1 marks the glyph as unsupported.
+#[derive(Default)]
+struct LayerPainter {
+    outline: Option<GlyphId>,
+    layers: Vec<ColorLayer>,
+    unsupported: bool,
+}
+
+impl<'a> Painter<'a> for LayerPainter {
+    fn outline_glyph(&mut self, glyph_id: ttf_parser::GlyphId) {
+        self.outline = Some(GlyphId(glyph_id.0));
+    }
+
+    fn paint(&mut self, paint: Paint<'a>) {
+        match (self.outline, paint) {
+            (Some(id), Paint::Solid(color)) => self.layers.push(ColorLayer {
+                id,
+                color: Some([color.red, color.green, color.blue, color.alpha]),
+            }),
+            _ => self.unsupported = true,
+        }
+    }
+
+    fn push_clip(&mut self) {
+        self.unsupported = true;
+    }
+
+    fn push_clip_box(&mut self, _clipbox: ClipBox) {
+        self.unsupported = true;
+    }
+
+    fn pop_clip(&mut self) {}
+
+    fn push_layer(&mut self, _mode: CompositeMode) {
+        self.unsupported = true;
+    }
+
+    fn pop_layer(&mut self) {}
+
+    fn push_transform(&mut self, _transform: ttf_parser::Transform) {
+        self.unsupported = true;
+    }
+
+    fn pop_transform(&mut self) {}
+}
+
+/// Reads the layers of the given glyph of the given font from its COLR and CPAL tables, in the order they are painted
+/// from the first palette. Returns None if the font has no COLR table, the glyph has no layers, or its layers are not
+/// supported.
+fn color_layers(font: &FontArc, id: GlyphId) -> Option<Vec<ColorLayer>> {
+    let face = ttf_parser::Face::parse(font.font_data(), 0).ok()?;
+    // the data of a font from a collection holds every font of the collection, only the first of which can be read
+    let glyph_id = ttf_parser::GlyphId(id.0);
+    if usize::from(face.number_of_glyphs()) != font.glyph_count() || !face.is_color_glyph(glyph_id) {
+        return None;
+    }
+    let paint = |foreground: RgbaColor| {
+        let mut painter = LayerPainter::default();
+        face.paint_color_glyph(glyph_id, 0, foreground, &mut painter)?;
+        if painter.unsupported || painter.layers.is_empty() {
+            return None;
+        }
+        Some(painter.layers)
+    };
+    // the layers painted in the color of the text are those whose color differs between opposite foreground colors
+    let on_black = paint(RgbaColor::new(0, 0, 0, 0))?;
+    let on_white = paint(RgbaColor::new(255, 255, 255, 255))?;
+    Some(
+        on_black
+            .into_iter()
+            .zip(on_white)
+            .map(|(layer, other)| ColorLayer {
+                id: layer.id,
+                color: layer.color.filter(|_| layer.color == other.color),
+            })
+            .collect(),
+    )
+}
+
+/// Calculates the area covered by the given layers of a glyph of the given font, in units of the font with y
+/// increasing upwards, or None if none of the layers has an outline.
+fn layer_bounds(font: &FontArc, layers: &[ColorLayer]) -> Option<glyph_brush::ab_glyph::Rect> {
+    layers
+        .iter()
+        .filter_map(|layer| font.outline(layer.id).map(|outline| outline.bounds))
+        .fold(None, |union: Option<glyph_brush::ab_glyph::Rect>, bounds| {
+            Some(match union {
+                Some(union) => glyph_brush::ab_glyph::Rect {
+                    min: (union.min.x.min(bounds.min.x), union.min.y.min(bounds.min.y)).into(),
+                    max: (union.max.x.max(bounds.max.x), union.max.y.max(bounds.max.y)).into(),
+                },
+                None => bounds,
+            })
+        })
+}
+
+/// Composites the given layers of a glyph of the given font into a texture, filling the outline of each layer with its
+/// color, or with the given sRGB foreground color for layers painted in the color of the text, over the layers before
+/// it. Returns None if the layers have no outline or the texture cannot be created, which is logged.
+fn layered_glyph_texture(
+    display: &dyn Facade,
+    font: &FontArc,
+    layers: &[ColorLayer],
+    foreground: [u8; 3],
+) -> Option<SrgbTexture2d> {
+    let bounds = layer_bounds(font, layers)?;
+    let factor = COLOR_GLYPH_SIZE / bounds.width().max(bounds.height());
+    let width = (bounds.width() * factor).ceil().max(1.0) as u32;
+    let height = (bounds.height() * factor).ceil().max(1.0) as u32;
+    let scale = PxScale::from(factor * font.height_unscaled());
+    let position = (-bounds.min.x * factor, bounds.max.y * factor);
+
+    // straight red, green, blue, and alpha from 0 to 1 of each pixel, from the top-left corner
+    let mut pixels = vec![[0.0f32; 4]; (width * height) as usize];
+    for layer in layers {
+        let outlined = match font.outline_glyph(layer.id.with_scale_and_position(scale, position)) {
+            Some(outlined) => outlined,
+            None => continue,
+        };
+        let [red, green, blue, alpha] = layer
+            .color
+            .unwrap_or([foreground[0], foreground[1], foreground[2], 255]);
+        let color = [red, green, blue].map(|value| f32::from(value) / 255.0);
+        let alpha = f32::from(alpha) / 255.0;
+        let origin = outlined.px_bounds().min;
+        outlined.draw(|x, y, coverage| {
+            let (x, y) = (x as i32 + origin.x as i32, y as i32 + origin.y as i32);
+            if x < 0 || y < 0 || x >= width as i32 || y >= height as i32 {
+                return;
+            }
+            let pixel = &mut pixels[(y as u32 * width + x as u32) as usize];
+            let source = coverage.min(1.0) * alpha;
+            let blended = source + pixel[3] * (1.0 - source);
+            if blended > 0.0 {
+                for channel in 0..3 {
+                    pixel[channel] = (color[channel] * source + pixel[channel] * pixel[3] * (1.0 - source)) / blended;
+                }
+            }
+            pixel[3] = blended;
+        });
+    }
+    let rgba: Vec<u8> = pixels
+        .iter()
+        .flat_map(|pixel| pixel.iter().map(|value| (value * 255.0).round() as u8))
+        .collect();
+    mipmapped_texture(display, RawImage2d::from_raw_rgba_reversed(&rgba, (width, height)))
+        .map_err(|ex| error!("Could not create color glyph texture:\n{}", ex))
+        .ok()
+}
+
+/// Converts the given coverage of a glyph rasterized into a bitmap of the given dimensions into a signed distance field,
+/// in which 0.5 lies on the outline of the glyph and values increase inwards, so that the glyph stays crisp when it is
+/// drawn larger than it was rasterized.
+fn distance_field(width: u32, height: u32, coverage: &[u8]) -> Vec<u8> {
+    let (width, height) = (width as i32, height as i32);
+    let spread = GLYPH_DISTANCE_SPREAD as i32;
+    let inside =
+        |x: i32, y: i32| x >= 0 && y >= 0 && x < width && y < height && coverage[(y * width + x) as usize] >= 128;
+    let mut field = Vec::with_capacity(coverage.len());
+    for y in 0..height {
+        for x in 0..width {
+            let value = coverage[(y * width + x) as usize];
+            let distance = if value > 0 && value < 255 {
+                // partially covered pixels lie on the outline, at a distance estimated from their coverage
+                f32::from(value) / 255.0 - 0.5
+            } else {
+                let is_inside = value == 255;
+                let mut nearest = GLYPH_DISTANCE_SPREAD;
+                for dy in -spread..=spread {
+                    for dx in -spread..=spread {
+                        if inside(x + dx, y + dy) != is_inside {
+                            nearest = nearest.min(((dx * dx + dy * dy) as f32).sqrt() - 0.5);
+                        }
+                    }
+                }
+                if is_inside {
+                    nearest
+                } else {
+                    -nearest
+                }
+            };
+            let normalized = 0.5 + distance / (2.0 * GLYPH_DISTANCE_SPREAD);
+            field.push((normalized.clamp(0.0, 1.0) * 255.0).round() as u8);
+        }
+    }
+    field
+}
+
+/// Writes the distance field of the given coverage of a glyph to the given area of the glyph texture.
 fn update_texture(tex: &Texture2d, rect: Rect<u32>, tex_data: &[u8]) {
     let image = RawImage2d {
-        data: Cow::Borrowed(tex_data),
+        data: Cow::Owned(distance_field(rect.width(), rect.height(), tex_data)),
         format: ClientFormat::U8,
         height: rect.height(),
         width: rect.width(),
@@ -277,6 +832,109 @@ fn to_vertex(
     }
 }
 
+/// A container for the style of a span of rich text.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TextStyle {
+    color: [f32; 4],
+    scale: f32,
+    bold: bool,
+}
+
+impl TextStyle {
+    /// Initializes the regular style of the given color and scale in pixels.
+    pub fn new(color: [f32; 4], scale: f32) -> Self {
+        TextStyle {
+            color,
+            scale,
+            bold: false,
+        }
+    }
+
+    /// Displays text of this style in the bold font.
+    pub fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    /// Displays text of this style in the given color.
+    pub fn with_color(mut self, color: [f32; 4]) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Displays text of this style at the given scale in pixels.
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Determines the font of text of this style among the fonts of a `GlyphBrush`.
+    pub fn font_id(self) -> FontId {
+        if self.bold {
+            BOLD_FONT
+        } else {
+            FontId::default()
+        }
+    }
+}
+
+/// A builder of text in several styles laid out as one section, such as bold team names beside their scores.
+#[derive(Clone, Debug, Default)]
+pub struct RichText<'a> {
+    spans: Vec<Text<'a>>,
+}
+
+impl<'a> RichText<'a> {
+    /// Initializes text without any spans.
+    pub fn new() -> Self {
+        RichText::default()
+    }
+
+    /// Appends the given text in the given style.
+    pub fn push(mut self, text: &'a str, style: TextStyle) -> Self {
+        self.spans.push(
+            Text::new(text)
+                .with_color(style.color)
+                .with_scale(style.scale)
+                .with_font_id(style.font_id()),
+        );
+        self
+    }
+
+    /// Creates a section of the text at the given position within the given bounds, in pixels.
+    pub fn section(self, screen_position: (f32, f32), bounds: (f32, f32)) -> Section<'a> {
+        Section {
+            text: self.spans,
+            screen_position,
+            bounds,
+            ..Section::default()
+        }
+    }
+}
+
+/// An enumeration of the effects drawn behind text to keep it readable over bright images.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TextEffect {
+    /// Draw text without an effect.
+    None,
+    /// Draw a copy of the text offset down and to the right as a drop shadow.
+    Shadow,
+    /// Draw copies of the text offset by a pixel in each direction as an outline.
+    Outline,
+}
+
+/// A glyph drawn from a color image or from colored layers, such as an emoji, queued to be drawn over the space left
+/// for it in its text.
+struct ColorGlyph {
+    font: usize,
+    id: GlyphId,
+    /// The sRGB color of the text, with which the layers of a COLR glyph painted in the color of the text are filled.
+    foreground: [u8; 3],
+    /// The area covered by the glyph, in pixels from the top-left corner of the screen.
+    rect: Bounds,
+    alpha: f32,
+}
+
 pub struct GlyphBrush<'a> {
     glyph_brush: glyph_brush::GlyphBrush<GlyphVertex, Extra, FontArc, DefaultSectionHasher>,
     params: DrawParameters<'a>,
@@ -285,22 +943,34 @@ pub struct GlyphBrush<'a> {
     index_buffer: NoIndices,
     vertex_buffer: glium::VertexBuffer<GlyphVertex>,
     instances: glium::VertexBuffer<InstanceVertex>,
+    effect: TextEffect,
+    effect_color: [f32; 4],
+    /// The color glyphs of the queued text, which are drawn as sprites rather than from their outlines.
+    color_glyphs: Vec<ColorGlyph>,
+    /// The layers of the glyphs of COLR fonts read so far by font and glyph, or None if a glyph has no supported layers.
+    color_layers: HashMap<(usize, GlyphId), Option<Rc<Vec<ColorLayer>>>>,
+    /// The textures of the color glyphs drawn so far by font, glyph, and foreground color, or None if a glyph's image
+    /// cannot be decoded.
+    color_textures: HashMap<(usize, GlyphId, [u8; 3]), Option<Rc<SrgbTexture2d>>>,
+    sprites: SpriteBatcher,
 }
 
 impl<'a> GlyphBrush<'a> {
-    pub fn build<F: Facade>(font: FontArc, display: &F) -> GlyphBrush<'a> {
+    /// Initializes a brush drawing text in the given fonts: the regular font, the bold font of rich text, and any fonts
+    /// displaying characters missing from both, in order of preference.
+    ///
+    /// # Errors
+    /// Panics if the glyph program cannot be loaded.
+    pub fn build(fonts: Vec<FontArc>, display: &dyn Facade) -> GlyphBrush<'a> {
         let params = DrawParameters {
             blend: Blend::alpha_blending(),
             ..Default::default()
         };
-        let glyph_brush = glyph_brush::GlyphBrushBuilder::using_fonts(vec![font]).build();
+        let glyph_brush = glyph_brush::GlyphBrushBuilder::using_fonts(fonts)
+            .draw_cache_scale_tolerance(GLYPH_SCALE_TOLERANCE)
+            .build();
         let (cache_width, cache_height) = glyph_brush.texture_dimensions();
-        let program = Program::from_source(display, GLYPH_VERTEX_SHADER_SRC, GLYPH_FRAGMENT_SHADER_SRC, None)
-            .unwrap_or_else(|ex| {
-                let msg = "Could not load glyph program";
-                error!("{}:\n{}", msg, ex);
-                panic!("{}.", msg);
-            });
+        let program = shaders::GLYPH.compile(display);
         let texture = Texture2d::empty(display, cache_width, cache_height).unwrap();
         let index_buffer = NoIndices(PrimitiveType::TriangleStrip);
         // We only need this so that we have groups of four
@@ -317,9 +987,28 @@ impl<'a> GlyphBrush<'a> {
             index_buffer,
             vertex_buffer,
             instances,
+            effect: TextEffect::None,
+            effect_color: [0.0; 4],
+            color_glyphs: Vec::new(),
+            color_layers: HashMap::new(),
+            color_textures: HashMap::new(),
+            sprites: SpriteBatcher::init(display),
         }
     }
 
+    /// Recompiles the shaders from their source files with the given display, keeping the current shaders if they fail.
+    pub fn reload_shaders(&mut self, display: &dyn Facade) {
+        shaders::GLYPH.reload_into(display, &mut self.program);
+        self.sprites.reload_shaders(display);
+    }
+
+    /// Draws the given effect in the given color behind all text.
+    pub fn with_effect(mut self, effect: TextEffect, color: [f32; 4]) -> Self {
+        self.effect = effect;
+        self.effect_color = color;
+        self
+    }
+
     /// Queues a section/layout to be drawn by the next call of
     /// [`draw_queued`](struct.GlyphBrush.html#method.draw_queued). Can be called multiple times
     /// to queue multiple sections for drawing.
@@ -327,18 +1016,214 @@ impl<'a> GlyphBrush<'a> {
     /// Benefits from caching, see [caching behaviour](#caching-behaviour).
     #[inline]
     pub fn queue(&mut self, section: Section) {
+        let mut section = self.with_fallback(section);
+        if section.text.iter().any(|text| !text.text.is_ascii()) {
+            self.queue_color_glyphs(&section);
+            section = self.without_layered_glyphs(section);
+        }
         self.glyph_brush.queue(section)
     }
 
+    /// Records the glyphs of the given section drawn from color images or colored layers rather than outlines, such as
+    /// the emoji of color bitmap (CBDT or sbix) and COLR fonts, to be drawn over the space left for them by the glyph
+    /// cache.
+    fn queue_color_glyphs(&mut self, section: &Section) {
+        let fonts = self.glyph_brush.fonts().to_vec();
+        let glyphs: Vec<_> = self.glyph_brush.glyphs(section).cloned().collect();
+        for glyph in glyphs {
+            let font = &fonts[glyph.font_id.0];
+            let id = glyph.glyph.id;
+            let color = section.text[glyph.section_index].extra.color;
+            if let Some(layers) = self.layers(glyph.font_id.0, id) {
+                // layers are measured in units of the font, upwards from the baseline of their glyph
+                let bounds = match layer_bounds(font, &layers) {
+                    Some(bounds) => bounds,
+                    None => continue,
+                };
+                let scaled = font.as_scaled(glyph.glyph.scale);
+                let (h_factor, v_factor) = (scaled.h_scale_factor(), scaled.v_scale_factor());
+                let foreground = if layers.iter().any(|layer| layer.color.is_none()) {
+                    [color[0], color[1], color[2]].map(|value| (value.clamp(0.0, 1.0) * 255.0).round() as u8)
+                } else {
+                    [0; 3]
+                };
+                self.color_glyphs.push(ColorGlyph {
+                    font: glyph.font_id.0,
+                    id,
+                    foreground,
+                    rect: Bounds::new(
+                        glyph.glyph.position.x + bounds.min.x * h_factor,
+                        glyph.glyph.position.y - bounds.max.y * v_factor,
+                        bounds.width() * h_factor,
+                        bounds.height() * v_factor,
+                    ),
+                    alpha: color[3],
+                });
+                continue;
+            }
+            if font.outline(id).is_some() {
+                continue;
+            }
+            let image = match font.glyph_raster_image2(id, u16::MAX) {
+                Some(image) if image.pixels_per_em > 0 => image,
+                _ => continue,
+            };
+            // images are measured in pixels of their strike, and placed from the top of the line of their glyph
+            let scale = glyph.glyph.scale;
+            let pixels_per_em = scale.y * font.units_per_em().unwrap_or(1.0) / font.height_unscaled();
+            let factor = pixels_per_em / f32::from(image.pixels_per_em);
+            let left = glyph.glyph.position.x + image.origin.x * factor;
+            let top = glyph.glyph.position.y - font.as_scaled(scale).ascent() + image.origin.y * factor;
+            self.color_glyphs.push(ColorGlyph {
+                font: glyph.font_id.0,
+                id,
+                foreground: [0; 3],
+                rect: Bounds::new(
+                    left,
+                    top,
+                    f32::from(image.width) * factor,
+                    f32::from(image.height) * factor,
+                ),
+                alpha: color[3],
+            });
+        }
+    }
+
+    /// Calculates the pixel bounds of the glyphs in the given section, or None if the section has no visible glyphs.
     #[inline]
-    pub fn draw_queued<F: Facade + Deref<Target = Context>>(&mut self, facade: &F, frame: &mut Frame) {
-        let dims = facade.get_framebuffer_dimensions();
-        let transform = [
-            [2.0 / (dims.0 as f32), 0.0, 0.0, 0.0],
-            [0.0, 2.0 / (dims.1 as f32), 0.0, 0.0],
-            [0.0, 0.0, 1.0, 0.0],
-            [-1.0, -1.0, 0.0, 1.0],
-        ];
+    pub fn glyph_bounds(&mut self, section: &Section) -> Option<glyph_brush::ab_glyph::Rect> {
+        let section = self.with_fallback(section.clone());
+        self.glyph_brush.glyph_bounds(&section)
+    }
+
+    /// Reads the layers of the given glyph of the given font if it is a COLR glyph, reusing the layers read before.
+    fn layers(&mut self, font: usize, id: GlyphId) -> Option<Rc<Vec<ColorLayer>>> {
+        let fonts = self.glyph_brush.fonts();
+        self.color_layers
+            .entry((font, id))
+            .or_insert_with(|| color_layers(&fonts[font], id).map(Rc::new))
+            .clone()
+    }
+
+    /// Splits the text of the given section into spans displayed in the fallback fonts wherever its characters are
+    /// missing from the fonts of their spans.
+    fn with_fallback<'s>(&self, section: Section<'s>) -> Section<'s> {
+        if self.glyph_brush.fonts().len() <= FIRST_FALLBACK_FONT {
+            return section;
+        }
+        split_spans(section, |span, c| (self.font_for(span.font_id, c), span.extra.color))
+    }
+
+    /// Splits the text of the given section into transparent spans wherever its characters are COLR glyphs, so that
+    /// their base outlines leave space for their layers without being drawn.
+    fn without_layered_glyphs<'s>(&mut self, section: Section<'s>) -> Section<'s> {
+        let fonts = self.glyph_brush.fonts().to_vec();
+        split_spans(section, |span, c| {
+            let mut color = span.extra.color;
+            if self.layers(span.font_id.0, fonts[span.font_id.0].glyph_id(c)).is_some() {
+                color[3] = 0.0;
+            }
+            (span.font_id, color)
+        })
+    }
+
+    /// Determines the font displaying the given character: the given font, or the first fallback font containing the
+    /// character if the given font does not. Returns the given font if no font contains the character.
+    fn font_for(&self, font_id: FontId, c: char) -> FontId {
+        let fonts = self.glyph_brush.fonts();
+        let contains = |id: usize| fonts[id].glyph_id(c).0 != 0;
+        if c.is_whitespace() || contains(font_id.0) {
+            return font_id;
+        }
+        (FIRST_FALLBACK_FONT..fonts.len())
+            .find(|id| contains(*id))
+            .map_or(font_id, FontId)
+    }
+
+    /// Fits the given text, displayed at the given scale, within the given bounds in pixels. Each line is wrapped at word
+    /// boundaries, only as many lines as fit within the bounds are kept, and the last kept line ends with an ellipsis if
+    /// any text was removed.
+    pub fn fit_text(&self, text: &str, scale: f32, bounds: (f32, f32)) -> String {
+        let max_lines = ((bounds.1 / self.line_height(FontId::default(), scale)) as usize).max(1);
+        self.fit_lines(text, FontId::default(), scale, bounds.0, max_lines)
+    }
+
+    /// Creates a section of the given text in the given style, laid out within the given width in pixels and number of
+    /// lines. Each line is wrapped at word boundaries, and the last line ends with an ellipsis if any text was removed.
+    /// The section is bounded by the width and the height of the lines; its position and layout are left to the caller.
+    pub fn bounded_section(&self, text: &str, style: TextStyle, width: f32, max_lines: usize) -> OwnedSection {
+        let max_lines = max_lines.max(1);
+        let text = self.fit_lines(text, style.font_id(), style.scale, width, max_lines);
+        let height = self.line_height(style.font_id(), style.scale) * max_lines as f32;
+        OwnedSection::default().with_bounds((width, height)).add_text(
+            OwnedText::new(text)
+                .with_color(style.color)
+                .with_scale(style.scale)
+                .with_font_id(style.font_id()),
+        )
+    }
+
+    /// Calculates the height in pixels of a line of text in the given font at the given scale.
+    pub fn line_height(&self, font_id: FontId, scale: f32) -> f32 {
+        let font = self.glyph_brush.fonts()[font_id.0].as_scaled(PxScale::from(scale));
+        font.height() + font.line_gap()
+    }
+
+    /// Fits the given text, displayed in the given font at the given scale, within the given width in pixels and number
+    /// of lines, as described by `fit_text`.
+    fn fit_lines(&self, text: &str, font_id: FontId, scale: f32, width: f32, max_lines: usize) -> String {
+        let font = self.glyph_brush.fonts()[font_id.0].as_scaled(PxScale::from(scale));
+        let measure = |line: &str| line.chars().map(|c| font.h_advance(font.glyph_id(c))).sum::<f32>();
+
+        let mut lines = Vec::new();
+        for paragraph in text.split('\n') {
+            let mut current = String::new();
+            for word in paragraph.split_whitespace() {
+                if current.is_empty() {
+                    current.push_str(word);
+                } else if measure(&current) + measure(" ") + measure(word) <= width {
+                    current.push(' ');
+                    current.push_str(word);
+                } else {
+                    lines.push(std::mem::replace(&mut current, word.to_owned()));
+                }
+            }
+            lines.push(current);
+        }
+        if lines.len() > max_lines {
+            lines.truncate(max_lines);
+            let last = lines.pop().unwrap_or_default();
+            lines.push(ellipsize(&format!("{}{}", last, ELLIPSIS), width, measure));
+        }
+        lines
+            .iter()
+            .map(|line| {
+                if measure(line) > width {
+                    ellipsize(line, width, measure)
+                } else {
+                    line.clone()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Restricts the drawing of queued text to the given area of the framebuffer, whose top-left corner becomes the
+    /// origin of the positions of text, or lifts the restriction if None.
+    pub fn set_viewport(&mut self, viewport: Option<glium::Rect>) {
+        self.params.viewport = viewport;
+    }
+
+    #[inline]
+    pub fn draw_queued(&mut self, facade: &dyn Facade, frame: &mut Frame) {
+        let dims = match self.params.viewport {
+            Some(viewport) => (viewport.width, viewport.height),
+            None => facade.get_context().get_framebuffer_dimensions(),
+        };
+        let transform = Transform::new()
+            .scale(2.0 / dims.0 as f32, 2.0 / dims.1 as f32)
+            .translate(-1.0, -1.0)
+            .matrix();
         let mut brush_action;
         loop {
             // We need this scope because of lifetimes.
@@ -380,28 +1265,683 @@ impl<'a> GlyphBrush<'a> {
             BrushAction::ReDraw => {}
         };
 
-        let uniforms = uniform! {
-            font_tex: sampler,
-            transform: transform,
+        // the copies of the text drawn as its effect are followed by the text itself
+        let effect_offsets: &[[f32; 2]] = match self.effect {
+            TextEffect::None => &[],
+            TextEffect::Shadow => &[SHADOW_OFFSET],
+            TextEffect::Outline => &OUTLINE_OFFSETS,
         };
+        let passes = effect_offsets
+            .iter()
+            .map(|offset| (*offset, 1.0f32))
+            .chain(std::iter::once(([0.0, 0.0], 0.0)));
+        for (offset, effect_weight) in passes {
+            let uniforms = uniform! {
+                font_tex: sampler,
+                transform: transform,
+                offset: offset,
+                effect_color: self.effect_color,
+                effect_weight: effect_weight,
+            };
 
-        // drawing a frame
-        frame
-            .draw(
-                (&self.instances, self.vertex_buffer.per_instance().unwrap()),
-                &self.index_buffer,
-                &self.program,
-                &uniforms,
-                &self.params,
-            )
-            .unwrap();
+            // drawing a frame
+            frame
+                .draw(
+                    (&self.instances, self.vertex_buffer.per_instance().unwrap()),
+                    &self.index_buffer,
+                    &self.program,
+                    &uniforms,
+                    &self.params,
+                )
+                .unwrap();
+        }
+        self.draw_color_glyphs(facade, frame, dims);
+    }
+
+    /// Draws the queued color glyphs over their text on a screen of the given dimensions in pixels, decoding the images
+    /// or compositing the layers of glyphs drawn for the first time.
+    fn draw_color_glyphs(&mut self, facade: &dyn Facade, frame: &mut Frame, dims: (u32, u32)) {
+        if self.color_glyphs.is_empty() {
+            return;
+        }
+        let (width, height) = (dims.0 as f32, dims.1 as f32);
+        let mut batch = SpriteBatch::new(width, height);
+        let fonts = self.glyph_brush.fonts();
+        let color_layers = &self.color_layers;
+        for glyph in self.color_glyphs.drain(..) {
+            let texture = self
+                .color_textures
+                .entry((glyph.font, glyph.id, glyph.foreground))
+                .or_insert_with(|| {
+                    let font = &fonts[glyph.font];
+                    match color_layers.get(&(glyph.font, glyph.id)) {
+                        Some(Some(layers)) => layered_glyph_texture(facade, font, layers, glyph.foreground),
+                        _ => color_glyph_texture(facade, font, glyph.id),
+                    }
+                    .map(Rc::new)
+                });
+            if let Some(texture) = texture {
+                let rect = Bounds::new(
+                    glyph.rect.left / width,
+                    glyph.rect.top / height,
+                    glyph.rect.width() / width,
+                    glyph.rect.height() / height,
+                );
+                batch.queue(
+                    Some(Rc::clone(texture)),
+                    rect,
+                    FULL_TEXTURE,
+                    [1.0, 1.0, 1.0, glyph.alpha],
+                    0.0,
+                );
+            }
+        }
+        let viewport = self.params.viewport.unwrap_or(glium::Rect {
+            left: 0,
+            bottom: 0,
+            width: dims.0,
+            height: dims.1,
+        });
+        self.sprites.flush(facade, frame, viewport, batch);
+    }
+}
+
+/// Splits the spans of the given section wherever the font or color given by the given function for a character of a
+/// span differs from that of the character before it.
+fn split_spans<'s>(
+    mut section: Section<'s>,
+    mut style: impl FnMut(&Text<'s>, char) -> (FontId, [f32; 4]),
+) -> Section<'s> {
+    let spans = std::mem::take(&mut section.text);
+    for span in spans {
+        let mut start = 0;
+        let mut current = (span.font_id, span.extra.color);
+        let restyled = |text, (font_id, color): (FontId, [f32; 4])| Text {
+            text,
+            font_id,
+            extra: Extra { color, ..span.extra },
+            ..span
+        };
+        for (index, c) in span.text.char_indices() {
+            let next = style(&span, c);
+            if next != current {
+                if index > start {
+                    section.text.push(restyled(&span.text[start..index], current));
+                }
+                start = index;
+                current = next;
+            }
+        }
+        section.text.push(restyled(&span.text[start..], current));
     }
+    section
 }
 
 /// An enumeration of directions in which focus can move.
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum FocusDirection {
     Up,
     Down,
     Left,
     Right,
 }
+
+/// The weight of the distance perpendicular to the direction of movement, relative to the distance along it, when
+/// choosing the nearest focusable element.
+const PERPENDICULAR_WEIGHT: f32 = 2.0;
+
+/// The bounds of an element on screen, as percentages of the screen from the top-left corner.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bounds {
+    pub left: f32,
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+}
+
+impl Bounds {
+    /// Initializes the bounds of an element with the given top-left corner and dimensions.
+    pub fn new(left: f32, top: f32, width: f32, height: f32) -> Self {
+        Bounds {
+            left,
+            top,
+            right: left + width,
+            bottom: top + height,
+        }
+    }
+
+    /// Calculates the width of the bounds.
+    pub fn width(&self) -> f32 {
+        self.right - self.left
+    }
+
+    /// Calculates the height of the bounds.
+    pub fn height(&self) -> f32 {
+        self.bottom - self.top
+    }
+
+    /// Calculates the center of the bounds.
+    fn center(&self) -> (f32, f32) {
+        ((self.left + self.right) / 2.0, (self.top + self.bottom) / 2.0)
+    }
+
+    /// Determines whether the bounds overlap the given bounds perpendicular to the given direction, so that an element
+    /// with the given bounds can be reached by moving straight in that direction.
+    fn in_beam(&self, other: &Bounds, direction: FocusDirection) -> bool {
+        match direction {
+            FocusDirection::Left | FocusDirection::Right => self.top < other.bottom && other.top < self.bottom,
+            FocusDirection::Up | FocusDirection::Down => self.left < other.right && other.left < self.right,
+        }
+    }
+}
+
+/// A transformation of geometry into normalized device coordinates, in which the screen spans -1 to 1 from the
+/// bottom-left corner, built from scales, rotations, and translations applied in the order they are given.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform {
+    /// The columns of the matrix of the transformation.
+    columns: [[f32; 4]; 4],
+}
+
+impl Transform {
+    /// Initializes a transformation that leaves geometry unchanged.
+    pub fn new() -> Self {
+        Transform {
+            columns: [
+                [1.0, 0.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        }
+    }
+
+    /// Initializes a transformation of the square spanning the screen onto the given bounds.
+    pub fn onto(bounds: Bounds) -> Self {
+        let (center_x, center_y) = bounds.center();
+        Transform::new()
+            .scale(bounds.width(), bounds.height())
+            .center_at(center_x, center_y)
+    }
+
+    /// Scales geometry by the given factors along each axis.
+    pub fn scale(self, x: f32, y: f32) -> Self {
+        self.then([
+            [x, 0.0, 0.0, 0.0],
+            [0.0, y, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// Rotates geometry counterclockwise by the given angle in radians.
+    pub fn rotate(self, radians: f32) -> Self {
+        let (sin, cos) = radians.sin_cos();
+        self.then([
+            [cos, sin, 0.0, 0.0],
+            [-sin, cos, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// Moves geometry by the given distances in normalized device coordinates, which increase upwards.
+    pub fn translate(self, x: f32, y: f32) -> Self {
+        self.then([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [x, y, 0.0, 1.0],
+        ])
+    }
+
+    /// Moves geometry by the given distances in pixels of a screen of the given dimensions, which increase downwards.
+    pub fn translate_pixels(self, x: f32, y: f32, screen_width: f32, screen_height: f32) -> Self {
+        self.translate(x * 2.0 / screen_width, -y * 2.0 / screen_height)
+    }
+
+    /// Moves geometry centered on the screen to be centered on the given point, as percentages of the screen from the
+    /// top-left corner.
+    pub fn center_at(self, x: f32, y: f32) -> Self {
+        self.translate(-1.0 + x * 2.0, 1.0 - y * 2.0)
+    }
+
+    /// Returns the matrix of the transformation, as the columns given to shaders.
+    pub fn matrix(&self) -> [[f32; 4]; 4] {
+        self.columns
+    }
+
+    /// Composes the transformation with the transformation of the given columns, which is applied after it.
+    fn then(self, after: [[f32; 4]; 4]) -> Self {
+        let mut columns = [[0.0; 4]; 4];
+        for (column, before) in columns.iter_mut().zip(&self.columns) {
+            for (row, value) in column.iter_mut().enumerate() {
+                *value = (0..4).map(|k| after[k][row] * before[k]).sum();
+            }
+        }
+        Transform { columns }
+    }
+}
+
+/// An element that can receive focus, identified by a key of its owner's choosing.
+#[derive(Clone, Copy, Debug)]
+pub struct Focusable<K> {
+    pub key: K,
+    pub bounds: Bounds,
+}
+
+/// Chooses the focusable element nearest to the element with the given bounds in the given direction, so that focus
+/// can move across rails of different sizes, headers, and menus alike. Elements in line with the given bounds are
+/// preferred to those diagonal from them. Returns the key of the chosen element, or None if there is no element in the
+/// given direction.
+pub fn find_nearest<K: Copy>(from: &Bounds, focusables: &[Focusable<K>], direction: FocusDirection) -> Option<K> {
+    let (from_x, from_y) = from.center();
+    let scored = focusables.iter().filter_map(|focusable| {
+        let (x, y) = focusable.bounds.center();
+        let (along, across) = match direction {
+            FocusDirection::Left => (from_x - x, (y - from_y).abs()),
+            FocusDirection::Right => (x - from_x, (y - from_y).abs()),
+            FocusDirection::Up => (from_y - y, (x - from_x).abs()),
+            FocusDirection::Down => (y - from_y, (x - from_x).abs()),
+        };
+        if along <= f32::EPSILON {
+            return None;
+        }
+        let in_beam = from.in_beam(&focusable.bounds, direction);
+        Some((!in_beam, along + PERPENDICULAR_WEIGHT * across, focusable.key))
+    });
+    scored
+        .min_by(|a, b| {
+            a.0.cmp(&b.0)
+                .then(a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+        })
+        .map(|(_, _, key)| key)
+}
+
+/// An enumeration of the behaviors of focus moved past the last element in a direction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FocusEdge {
+    /// The focus stays on the last element, leaving its owner to move the focus elsewhere, such as to a menu.
+    Clamp,
+    /// The focus wraps around to the element farthest in the opposite direction.
+    Wrap,
+}
+
+/// A container for the focus among a set of focusable elements, which moves between them by direction, so that menus,
+/// lists, and rails share one implementation of directional navigation.
+pub struct FocusManager<K> {
+    focusables: Vec<Focusable<K>>,
+    focused: Option<K>,
+    edge: FocusEdge,
+    /// The callbacks notified of the newly focused element, or None, whenever the focus changes.
+    listeners: Vec<Box<dyn FnMut(Option<K>)>>,
+}
+
+impl<K: Copy + PartialEq> FocusManager<K> {
+    /// Initializes a manager without focusable elements or focus, with the given behavior at the last element in a
+    /// direction.
+    pub fn new(edge: FocusEdge) -> Self {
+        FocusManager {
+            focusables: Vec::new(),
+            focused: None,
+            edge,
+            listeners: Vec::new(),
+        }
+    }
+
+    /// Sets the focusable elements, without focusing any of them.
+    pub fn with_focusables(mut self, focusables: Vec<Focusable<K>>) -> Self {
+        self.focusables = focusables;
+        self
+    }
+
+    /// Adds the given callback, notified of the newly focused element, or None, whenever the focus changes.
+    pub fn with_listener(mut self, listener: impl FnMut(Option<K>) + 'static) -> Self {
+        self.listeners.push(Box::new(listener));
+        self
+    }
+
+    /// Replaces the focusable elements. The focus is kept if the focused element is still focusable, and otherwise
+    /// moves to the first element, if any.
+    pub fn set_focusables(&mut self, focusables: Vec<Focusable<K>>) {
+        self.focusables = focusables;
+        let kept = self
+            .focused
+            .filter(|key| self.focusables.iter().any(|focusable| focusable.key == *key));
+        self.set_focused(kept.or_else(|| self.focusables.first().map(|focusable| focusable.key)));
+    }
+
+    /// Retrieves the key of the focused element, or None if no element is focused.
+    pub fn focused(&self) -> Option<K> {
+        self.focused
+    }
+
+    /// Focuses the element with the given key.
+    pub fn focus(&mut self, key: K) {
+        self.set_focused(Some(key));
+    }
+
+    /// Removes the focus from every element. Returns the key of the element that was focused, if any.
+    pub fn unfocus(&mut self) -> Option<K> {
+        let focused = self.focused;
+        self.set_focused(None);
+        focused
+    }
+
+    /// Moves the focus to the nearest element in the given direction, as chosen by `find_nearest`, or wraps it around
+    /// if there is no such element and the manager wraps. If no element is focused, the first element is focused.
+    /// Returns whether the focus moved, so that the owner can handle moves past the last element itself.
+    pub fn move_focus(&mut self, direction: FocusDirection) -> bool {
+        let from = match self.focused_bounds() {
+            Some(from) => from,
+            None => {
+                let first = self.focusables.first().map(|focusable| focusable.key);
+                self.set_focused(first);
+                return first.is_some();
+            }
+        };
+        let nearest = find_nearest(&from, &self.focusables, direction).or_else(|| match self.edge {
+            FocusEdge::Clamp => None,
+            FocusEdge::Wrap => find_nearest(&self.wrap_bounds(&from, direction), &self.focusables, direction),
+        });
+        match nearest {
+            Some(key) if Some(key) != self.focused => {
+                self.set_focused(Some(key));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Retrieves the bounds of the focused element, or None if no focusable element is focused.
+    fn focused_bounds(&self) -> Option<Bounds> {
+        let focused = self.focused?;
+        self.focusables
+            .iter()
+            .find(|focusable| focusable.key == focused)
+            .map(|focusable| focusable.bounds)
+    }
+
+    /// Positions the given bounds just before every focusable element in the given direction, in line with where they
+    /// were, from which moving in that direction wraps around.
+    fn wrap_bounds(&self, from: &Bounds, direction: FocusDirection) -> Bounds {
+        let elements = self.focusables.iter().map(|focusable| focusable.bounds);
+        match direction {
+            FocusDirection::Left => {
+                let right = elements.map(|bounds| bounds.right).fold(from.right, f32::max);
+                Bounds::new(right, from.top, from.width(), from.height())
+            }
+            FocusDirection::Right => {
+                let left = elements.map(|bounds| bounds.left).fold(from.left, f32::min);
+                Bounds::new(left - from.width(), from.top, from.width(), from.height())
+            }
+            FocusDirection::Up => {
+                let bottom = elements.map(|bounds| bounds.bottom).fold(from.bottom, f32::max);
+                Bounds::new(from.left, bottom, from.width(), from.height())
+            }
+            FocusDirection::Down => {
+                let top = elements.map(|bounds| bounds.top).fold(from.top, f32::min);
+                Bounds::new(from.left, top - from.height(), from.width(), from.height())
+            }
+        }
+    }
+
+    /// Focuses the element with the given key, or none, notifying the listeners if the focus changes.
+    fn set_focused(&mut self, focused: Option<K>) {
+        if focused == self.focused {
+            return;
+        }
+        self.focused = focused;
+        for listener in &mut self.listeners {
+            listener(focused);
+        }
+    }
+}
+
+/// A length along one axis of a widget, as a percentage of the screen.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Length {
+    /// The given percentage of the screen.
+    Fixed(f32),
+    /// The length measured from the content of the widget, including its padding.
+    Auto,
+    /// A share of the space left by the other children of a row or column, in proportion to the given weight, or all
+    /// of the space across a row or column.
+    Fill(f32),
+}
+
+/// An enumeration of the positions of content within the space arranged for it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Align {
+    Start,
+    Center,
+    End,
+}
+
+impl Align {
+    /// Determines the offset of content of the given length within space of the given length.
+    fn offset(self, content: f32, space: f32) -> f32 {
+        match self {
+            Align::Start => 0.0,
+            Align::Center => (space - content) / 2.0,
+            Align::End => space - content,
+        }
+    }
+}
+
+/// An enumeration of the directions in which a widget lays out its children.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Axis {
+    Horizontal,
+    Vertical,
+}
+
+/// A node of a tree of widgets declaring the layout of a screen, whose bounds are determined by `arrange`. Widgets are
+/// identified by keys of their owner's choosing, and only keyed widgets are reported in the arrangement, so that the
+/// tree describes the structure of a screen while its owner draws the content of each keyed widget within its bounds.
+#[derive(Clone, Debug)]
+pub struct Widget<K> {
+    key: Option<K>,
+    width: Length,
+    height: Length,
+    /// The space inside each edge of the widget, as percentages of the screen: left, top, right, bottom.
+    padding: [f32; 4],
+    /// The space between the children of the widget, as a percentage of the screen.
+    spacing: f32,
+    /// The alignment of the children along the axis of the widget, and across it.
+    align: (Align, Align),
+    axis: Axis,
+    children: Vec<Widget<K>>,
+}
+
+impl<K: Copy> Widget<K> {
+    /// Initializes a widget without children, whose content is drawn by its owner, of the given size.
+    pub fn leaf(width: Length, height: Length) -> Self {
+        Widget {
+            key: None,
+            width,
+            height,
+            padding: [0.0; 4],
+            spacing: 0.0,
+            align: (Align::Start, Align::Start),
+            axis: Axis::Horizontal,
+            children: Vec::new(),
+        }
+    }
+
+    /// Initializes a widget that lays out the given children from left to right, sized to fit them.
+    pub fn row(children: Vec<Widget<K>>) -> Self {
+        Widget {
+            children,
+            ..Widget::leaf(Length::Auto, Length::Auto)
+        }
+    }
+
+    /// Initializes a widget that lays out the given children from top to bottom, sized to fit them.
+    pub fn column(children: Vec<Widget<K>>) -> Self {
+        Widget {
+            axis: Axis::Vertical,
+            children,
+            ..Widget::leaf(Length::Auto, Length::Auto)
+        }
+    }
+
+    /// Identifies the widget by the given key in the arrangement.
+    pub fn with_key(mut self, key: K) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    /// Sets the size of the widget.
+    pub fn with_size(mut self, width: Length, height: Length) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Sets the space inside each edge of the widget to the given percentages of the screen, horizontally and
+    /// vertically.
+    pub fn with_padding(mut self, horizontal: f32, vertical: f32) -> Self {
+        self.padding = [horizontal, vertical, horizontal, vertical];
+        self
+    }
+
+    /// Sets the space between the children of the widget to the given percentage of the screen.
+    pub fn with_spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Sets the alignment of the children of the widget along its axis, which applies when no child fills the
+    /// remaining space, and across it.
+    pub fn with_align(mut self, along: Align, across: Align) -> Self {
+        self.align = (along, across);
+        self
+    }
+
+    /// Measures the size the widget takes up if given only the space its content needs, as percentages of the screen.
+    /// Children that fill the remaining space are measured by their content.
+    pub fn measure(&self) -> (f32, f32) {
+        let [left, top, right, bottom] = self.padding;
+        let mut along = 0.0;
+        let mut across: f32 = 0.0;
+        for child in &self.children {
+            let (child_along, child_across) = self.axis_split(child.measure());
+            along += child_along;
+            across = across.max(child_across);
+        }
+        along += self.spacing * self.children.len().saturating_sub(1) as f32;
+        let (content_width, content_height) = self.axis_join(along, across);
+        let resolve = |length: Length, content: f32| match length {
+            Length::Fixed(length) => length,
+            Length::Auto | Length::Fill(_) => content,
+        };
+        (
+            resolve(self.width, content_width + left + right),
+            resolve(self.height, content_height + top + bottom),
+        )
+    }
+
+    /// Arranges the widget and its descendants within the given bounds, which the widget fills.
+    pub fn arrange(&self, bounds: Bounds) -> Arrangement<K> {
+        let mut arrangement = Arrangement { bounds: Vec::new() };
+        self.arrange_into(bounds, &mut arrangement);
+        arrangement
+    }
+
+    /// Arranges the widget within the given bounds, recording the bounds of it and its keyed descendants in the given
+    /// arrangement.
+    fn arrange_into(&self, bounds: Bounds, arrangement: &mut Arrangement<K>) {
+        if let Some(key) = self.key {
+            arrangement.bounds.push((key, bounds));
+        }
+        if self.children.is_empty() {
+            return;
+        }
+        let [left, top, right, bottom] = self.padding;
+        let inner = Bounds {
+            left: bounds.left + left,
+            top: bounds.top + top,
+            right: bounds.right - right,
+            bottom: bounds.bottom - bottom,
+        };
+        let (space_along, space_across) = self.axis_split((inner.width(), inner.height()));
+        let lengths: Vec<(Length, Length)> = self
+            .children
+            .iter()
+            .map(|child| self.axis_split((child.width, child.height)))
+            .collect();
+        let measured: Vec<(f32, f32)> = self
+            .children
+            .iter()
+            .map(|child| self.axis_split(child.measure()))
+            .collect();
+        let weights: f32 = lengths
+            .iter()
+            .map(|(along, _)| match along {
+                Length::Fill(weight) => *weight,
+                Length::Fixed(_) | Length::Auto => 0.0,
+            })
+            .sum();
+        let spacing = self.spacing * self.children.len().saturating_sub(1) as f32;
+        let used: f32 = lengths
+            .iter()
+            .zip(&measured)
+            .filter(|((along, _), _)| !matches!(along, Length::Fill(_)))
+            .map(|(_, (measured_along, _))| measured_along)
+            .sum::<f32>()
+            + spacing;
+        let remaining = (space_along - used).max(0.0);
+        let mut position = if weights > 0.0 {
+            0.0
+        } else {
+            self.align.0.offset(used, space_along)
+        };
+        for ((child, (along, across)), (measured_along, measured_across)) in
+            self.children.iter().zip(&lengths).zip(&measured)
+        {
+            let child_along = match along {
+                Length::Fill(weight) => remaining * weight / weights,
+                Length::Fixed(_) | Length::Auto => *measured_along,
+            };
+            let child_across = match across {
+                Length::Fill(_) => space_across,
+                Length::Fixed(_) | Length::Auto => *measured_across,
+            };
+            let offset_across = self.align.1.offset(child_across, space_across);
+            let (x, y) = self.axis_join(position, offset_across);
+            let (width, height) = self.axis_join(child_along, child_across);
+            child.arrange_into(Bounds::new(inner.left + x, inner.top + y, width, height), arrangement);
+            position += child_along + self.spacing;
+        }
+    }
+
+    /// Splits the given horizontal and vertical values into the values along the axis of the widget and across it.
+    fn axis_split<T>(&self, (horizontal, vertical): (T, T)) -> (T, T) {
+        match self.axis {
+            Axis::Horizontal => (horizontal, vertical),
+            Axis::Vertical => (vertical, horizontal),
+        }
+    }
+
+    /// Joins the given values along the axis of the widget and across it into horizontal and vertical values.
+    fn axis_join<T>(&self, along: T, across: T) -> (T, T) {
+        self.axis_split((along, across))
+    }
+}
+
+/// The bounds determined for each keyed widget of a tree by `Widget::arrange`.
+#[derive(Clone, Debug)]
+pub struct Arrangement<K> {
+    bounds: Vec<(K, Bounds)>,
+}
+
+impl<K: PartialEq> Arrangement<K> {
+    /// Retrieves the bounds of the widget with the given key, or None if there is no such widget.
+    pub fn bounds(&self, key: K) -> Option<Bounds> {
+        self.bounds
+            .iter()
+            .find(|(other, _)| *other == key)
+            .map(|(_, bounds)| *bounds)
+    }
+}
@@ -0,0 +1,375 @@
+//! Rendering of a synthetic scene to an image without a window, for generating screenshots and verifying the layout on
+//! machines without a display, and replaying input scripts against a known scene. Frames are drawn by an
+//! `OffscreenRenderer`; scripts are replayed against the state of the UI, which needs no context.
+
+use crate::gl_mlb::{MlbGlUi, MlbUiState};
+use crate::gl_utils::GlyphBrush;
+use crate::offscreen::OffscreenRenderer;
+use crate::options::Options;
+use crate::replay::Script;
+use chrono::{Duration as ChronoDuration, Local, NaiveDate, TimeZone};
+use dss_mlb::{MlbGameClientInfo, MlbGameState};
+use glium::texture::RawImage2d;
+use glium::Surface;
+use image::RgbaImage;
+use log::{error, info};
+use std::fs;
+use std::time::{Duration, Instant};
+
+/// The size in pixels of rendered images.
+pub const IMAGE_SIZE: (u32, u32) = (1920, 1080);
+/// The time after the first frame at which the rendered frame is drawn, by which every animation has settled.
+const SETTLE_TIME: Duration = Duration::from_secs(1);
+/// The most recent day of the synthetic scenes, fixed so that rendered images do not change from day to day.
+const SCENE_DATE: (i32, u32, u32) = (2020, 9, 1);
+/// The time of day on the most recent day of the synthetic scenes displayed as the current time and as the time their
+/// games were retrieved, fixed so that rendered images do not change from minute to minute.
+const SCENE_TIME: (u32, u32) = (19, 5);
+/// The number of days of games in the synthetic scenes.
+const SCENE_DAYS: i64 = 3;
+/// The number of games on each day of the synthetic scenes.
+const SCENE_GAMES: usize = 8;
+/// The text added to the summary of the focused game of the long headline scene.
+const LONG_SUMMARY: &str = "after a rain delay of two hours and fourteen minutes, a go-ahead double in the top of the \
+                            eleventh inning, and a game-ending double play turned with the bases loaded and one out";
+/// The teams of the games in the synthetic scenes, paired in order.
+const TEAMS: [&str; 10] = [
+    "New York Yankees",
+    "Boston Red Sox",
+    "Los Angeles Dodgers",
+    "San Francisco Giants",
+    "Chicago Cubs",
+    "St. Louis Cardinals",
+    "Houston Astros",
+    "Seattle Mariners",
+    "Atlanta Braves",
+    "New York Mets",
+];
+
+/// An enumeration of the synthetic scenes that can be rendered.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Scene {
+    /// The grid of several days of games, with the first game of the most recent day focused.
+    Grid,
+    /// The grid with a focused game whose title and summary are too long to fit below the grid.
+    LongHeadline,
+    /// The grid with no games on the most recent day, whose placeholder card is focused.
+    EmptyDay,
+}
+
+impl Scene {
+    /// Every scene, in the order listed in the usage message.
+    pub const ALL: [Scene; 3] = [Scene::Grid, Scene::LongHeadline, Scene::EmptyDay];
+
+    /// Determines the name by which the scene is selected on the command line.
+    pub fn name(self) -> &'static str {
+        match self {
+            Scene::Grid => "grid",
+            Scene::LongHeadline => "long-headline",
+            Scene::EmptyDay => "empty-day",
+        }
+    }
+
+    /// Finds the scene with the given name, if any.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Scene::ALL.iter().copied().find(|scene| scene.name() == name)
+    }
+
+    /// Provides the synthetic data of the scene to the given UI.
+    fn populate(self, ui: &mut MlbUiState, options: &Options) {
+        let last = NaiveDate::from_ymd(SCENE_DATE.0, SCENE_DATE.1, SCENE_DATE.2);
+        let dates: Vec<NaiveDate> = (0..SCENE_DAYS).map(|days| last - ChronoDuration::days(days)).collect();
+        ui.set_pending_days(dates.clone());
+        for (day, date) in dates.iter().enumerate() {
+            let mut games = synthetic_games(*date, day as u64, SCENE_GAMES);
+            match (self, day) {
+                (Scene::LongHeadline, 0) => {
+                    let game = &mut games[0];
+                    game.title = format!("{} (Game 1 of a Doubleheader, Suspended and Resumed)", game.title);
+                    game.summary = format!("{} {}", game.summary, LONG_SUMMARY);
+                }
+                (Scene::EmptyDay, 0) => games.clear(),
+                _ => (),
+            }
+            ui.set_day(*date, games, options);
+        }
+    }
+}
+
+/// Creates the given number of games on the given date, the given number of days before the most recent day. Games
+/// before the most recent day are final, and games of the most recent day are final or scheduled in turn.
+fn synthetic_games(date: NaiveDate, days_ago: u64, count: usize) -> Vec<MlbGameClientInfo> {
+    (0..count)
+        .map(|index| {
+            let away_team = TEAMS[(index * 2) % TEAMS.len()].to_owned();
+            let home_team = TEAMS[(index * 2 + 1) % TEAMS.len()].to_owned();
+            let final_score = days_ago > 0 || index % 2 == 0;
+            let (state, detailed_state, away_score, home_score, summary) = if final_score {
+                let (away_score, home_score) = ((index as u32 * 3) % 7, (index as u32 * 5 + 2) % 9);
+                let winner = if away_score > home_score {
+                    &away_team
+                } else {
+                    &home_team
+                };
+                (
+                    MlbGameState::Final,
+                    "Final",
+                    Some(away_score),
+                    Some(home_score),
+                    format!("{} win on {}", winner, date.format("%B %-d")),
+                )
+            } else {
+                let summary = format!("Live {}:10 PM", index % 4 + 6);
+                (MlbGameState::Scheduled, "Scheduled", None, None, summary)
+            };
+            MlbGameClientInfo {
+                game_pk: days_ago * 100 + index as u64 + 1,
+                state,
+                detailed_state: detailed_state.to_owned(),
+                title: format!("{} at {}", away_team, home_team),
+                away_team,
+                home_team,
+                away_score,
+                home_score,
+                image: None,
+                image_url: None,
+                summary,
+                series_game_number: Some(index as u32 % 3 + 1),
+                games_in_series: Some(3),
+                post_game: None,
+                away_pitcher: None,
+                home_pitcher: None,
+                warnings: Vec::new(),
+            }
+        })
+        .collect()
+}
+
+/// Creates a renderer that draws frames of the given size in pixels offscreen.
+///
+/// # Errors
+/// Panics if an offscreen OpenGL context cannot be created.
+pub fn create_renderer(size: (u32, u32)) -> OffscreenRenderer {
+    OffscreenRenderer::new(size).unwrap_or_else(|ex| {
+        let msg = "Could not create offscreen OpenGL context";
+        error!("{}:\n{}", msg, ex);
+        panic!("{}.", msg);
+    })
+}
+
+/// Initializes the state of the UI with the given options, displaying the given scene as of the fixed time of the
+/// scenes. The state can be navigated without rendering it.
+pub fn build_state(options: &Options, scene: Scene) -> MlbUiState {
+    let strings = options.load_strings();
+    let settings = options.settings(&strings);
+    let mut state = MlbUiState::new(None, options.theme.clone(), strings);
+    let now = Local
+        .ymd(SCENE_DATE.0, SCENE_DATE.1, SCENE_DATE.2)
+        .and_hms(SCENE_TIME.0, SCENE_TIME.1, 0);
+    state.set_fixed_time(Some(now));
+    state.set_content_area(options.content_aspect, options.safe_area);
+    state.set_settings(settings);
+    scene.populate(&mut state, options);
+    state
+}
+
+/// Draws a frame of the given UI with the given options on the given renderer, once its animations have settled, as an
+/// image of `IMAGE_SIZE`.
+///
+/// # Errors
+/// Panics if the frame cannot be drawn or read.
+pub fn capture(renderer: &OffscreenRenderer, ui: &mut MlbGlUi, options: &Options) -> RgbaImage {
+    let mut text_brush = GlyphBrush::build(options.theme.load_fonts(), renderer)
+        .with_effect(options.theme.text_effect, options.theme.text_effect_color);
+
+    // the first frame starts the animations of the scene, which have settled by the second
+    let [red, green, blue, alpha] = options.theme.clear_color;
+    let start = Instant::now();
+    for now in [start, start + SETTLE_TIME].iter() {
+        let mut target = renderer.draw();
+        target.clear_color(red, green, blue, alpha);
+        ui.draw_at(renderer, &mut target, Some(&mut text_brush), *now);
+        target.finish().unwrap_or_else(|ex| {
+            let msg = "Target could not finish headless frame";
+            error!("{}:\n{}", msg, ex);
+            panic!("{}.", msg);
+        });
+    }
+    let raw: RawImage2d<u8> = renderer.read_front_buffer().unwrap_or_else(|ex| {
+        let msg = "Could not read headless frame";
+        error!("{}:\n{:?}", msg, ex);
+        panic!("{}.", msg);
+    });
+    let (width, height) = (raw.width, raw.height);
+    let image = RgbaImage::from_raw(width, height, raw.data.into_owned()).unwrap_or_else(|| {
+        let msg = "Headless frame has unexpected size";
+        error!("{}: {}x{}", msg, width, height);
+        panic!("{}.", msg);
+    });
+    // OpenGL rows start at the bottom of the frame
+    image::imageops::flip_vertical(&image)
+}
+
+/// Displays the scene of the given options without a window, replays the input script of the options against it if
+/// any, and then writes a frame of it to the screenshot path of the options if any. Failed steps of the script are
+/// logged and printed. Returns true if every step of the script succeeded.
+///
+/// # Errors
+/// Panics if the frame cannot be rendered, or the script cannot be read or parsed, or the image cannot be written.
+pub async fn run(options: &Options) -> bool {
+    let mut state = build_state(options, options.scene);
+    let mut passed = true;
+    if let Some(path) = &options.replay {
+        let script = fs::read_to_string(path)
+            .map_err(|ex| ex.to_string())
+            .and_then(|text| Script::parse(&text))
+            .unwrap_or_else(|ex| {
+                let msg = "Could not read replay script";
+                error!("{} {}:\n{}", msg, path.display(), ex);
+                panic!("{}.", msg);
+            });
+        let failures = script.run(&mut state, &options.keymap);
+        for failure in failures.iter() {
+            error!("Replay of {} failed at {}", path.display(), failure);
+            eprintln!("{}: {}", path.display(), failure);
+        }
+        passed = failures.is_empty();
+    }
+    if let Some(path) = &options.screenshot {
+        let renderer = create_renderer(IMAGE_SIZE);
+        let background = options.theme.load_background().await;
+        let mut ui = MlbGlUi::init(&renderer, state, background);
+        let image = capture(&renderer, &mut ui, options);
+        image
+            .save_with_format(path, image::ImageFormat::Png)
+            .unwrap_or_else(|ex| {
+                let msg = "Could not write rendered image";
+                error!("{} {}:\n{}", msg, path.display(), ex);
+                panic!("{}.", msg);
+            });
+        info!("{} scene rendered to {}", options.scene.name(), path.display());
+    }
+    passed
+}
+
+#[cfg(test)]
+mod tests {
+    //! Golden-image tests, which render each synthetic scene and compare it to the reference image of the scene in
+    //! `tests/golden`. Rendering requires Mesa's EGL libraries, so these tests only run with the `golden` feature, as in
+    //! `cargo test -p dss_main --features golden`. Setting `UPDATE_GOLDEN=1` replaces the reference images with the
+    //! rendered images.
+
+    use super::*;
+    use crate::config::Config;
+    use image::Rgba;
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+
+    /// The largest difference of any channel at which two pixels are considered the same, absorbing differences in
+    /// rasterization and blending between OpenGL implementations.
+    const CHANNEL_TOLERANCE: u8 = 8;
+    /// The largest fraction of pixels that may differ from the reference image, absorbing only stray antialiased edges,
+    /// as the clock and retrieval time are fixed to `SCENE_TIME`.
+    const PIXEL_TOLERANCE: f64 = 0.0005;
+    /// The size in pixels of the rendered and reference images, small enough to keep the reference images small.
+    const GOLDEN_SIZE: (u32, u32) = (640, 360);
+
+    /// Determines the fraction of pixels of the given images that differ by more than `CHANNEL_TOLERANCE`, or None if
+    /// the images are not the same size.
+    fn differing_fraction(expected: &RgbaImage, actual: &RgbaImage) -> Option<f64> {
+        if expected.dimensions() != actual.dimensions() {
+            return None;
+        }
+        let differing = expected
+            .pixels()
+            .zip(actual.pixels())
+            .filter(|(Rgba(expected), Rgba(actual))| {
+                expected.iter().zip(actual.iter()).any(|(expected, actual)| {
+                    (i16::from(*expected) - i16::from(*actual)).abs() > i16::from(CHANNEL_TOLERANCE)
+                })
+            })
+            .count();
+        let (width, height) = expected.dimensions();
+        Some(differing as f64 / (f64::from(width) * f64::from(height)))
+    }
+
+    /// Renders the given scene with the default options and compares it to its reference image, writing the rendered
+    /// image to the temporary directory if it differs.
+    async fn assert_matches_golden(scene: Scene) {
+        let options = Options::from_config(Config::default(), None);
+        let renderer = create_renderer(GOLDEN_SIZE);
+        let background = options.theme.load_background().await;
+        let mut ui = MlbGlUi::init(&renderer, build_state(&options, scene), background);
+        let actual = capture(&renderer, &mut ui, &options);
+        let golden = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/golden")
+            .join(scene.name())
+            .with_extension("png");
+        if env::var_os("UPDATE_GOLDEN").is_some() {
+            fs::create_dir_all(golden.parent().unwrap()).expect("Unable to create reference image directory.");
+            actual.save(&golden).expect("Unable to write reference image.");
+            return;
+        }
+        let expected = image::open(&golden)
+            .unwrap_or_else(|ex| {
+                panic!(
+                    "Unable to read reference image {} ({}); render it with UPDATE_GOLDEN=1.",
+                    golden.display(),
+                    ex
+                )
+            })
+            .into_rgba();
+        let fraction = differing_fraction(&expected, &actual);
+        if let Some(fraction) = fraction {
+            if fraction <= PIXEL_TOLERANCE {
+                return;
+            }
+        }
+        let actual_path = env::temp_dir().join(scene.name()).with_extension("png");
+        actual.save(&actual_path).expect("Unable to write rendered image.");
+        match fraction {
+            Some(fraction) => panic!(
+                "{:.2}% of the pixels of {} differ from {}.",
+                fraction * 100.0,
+                actual_path.display(),
+                golden.display()
+            ),
+            None => panic!("{} is not the size of {}.", actual_path.display(), golden.display()),
+        }
+    }
+
+    #[tokio::test]
+    #[cfg_attr(not(feature = "golden"), ignore)]
+    async fn focused_tile_matches_golden() {
+        assert_matches_golden(Scene::Grid).await;
+    }
+
+    #[tokio::test]
+    #[cfg_attr(not(feature = "golden"), ignore)]
+    async fn long_headline_matches_golden() {
+        assert_matches_golden(Scene::LongHeadline).await;
+    }
+
+    #[tokio::test]
+    #[cfg_attr(not(feature = "golden"), ignore)]
+    async fn empty_day_matches_golden() {
+        assert_matches_golden(Scene::EmptyDay).await;
+    }
+
+    #[test]
+    fn differing_fraction_ignores_small_differences() {
+        let expected = RgbaImage::from_pixel(4, 4, Rgba([100, 100, 100, 255]));
+        let mut actual = RgbaImage::from_pixel(4, 4, Rgba([100 + CHANNEL_TOLERANCE, 100, 100, 255]));
+        assert_eq!(differing_fraction(&expected, &actual), Some(0.0));
+        actual.put_pixel(0, 0, Rgba([0, 100, 100, 255]));
+        assert_eq!(differing_fraction(&expected, &actual), Some(1.0 / 16.0));
+    }
+
+    #[test]
+    fn differing_fraction_rejects_different_sizes() {
+        let expected = RgbaImage::new(4, 4);
+        let actual = RgbaImage::new(4, 2);
+        assert_eq!(differing_fraction(&expected, &actual), None);
+    }
+}
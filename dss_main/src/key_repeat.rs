@@ -0,0 +1,63 @@
+//! Repetition of navigation while a key is held, at a steady rate independent of the keyboard settings of the system.
+
+use crate::gl_utils::FocusDirection;
+use std::time::{Duration, Instant};
+
+/// The time a key must be held before its action repeats.
+const REPEAT_DELAY: Duration = Duration::from_millis(400);
+/// The time between repetitions of the action of a held key.
+const REPEAT_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A tracker of the held navigation key, which determines when its action should repeat.
+#[derive(Debug, Default)]
+pub struct KeyRepeat {
+    held: Option<(FocusDirection, Instant)>,
+}
+
+impl KeyRepeat {
+    /// Initializes a tracker with no key held.
+    pub fn new() -> Self {
+        KeyRepeat::default()
+    }
+
+    /// Records that the key of the given direction was pressed at the given time. Returns true if the key was not
+    /// already held, in which case its action should be performed immediately; repeated presses reported by the
+    /// system while the key is held are ignored.
+    pub fn press(&mut self, direction: FocusDirection, now: Instant) -> bool {
+        match self.held {
+            Some((held, _)) if held == direction => false,
+            _ => {
+                self.held = Some((direction, now + REPEAT_DELAY));
+                true
+            }
+        }
+    }
+
+    /// Records that the key of the given direction was released, stopping its repetition.
+    pub fn release(&mut self, direction: FocusDirection) {
+        if self.held.map(|(held, _)| held) == Some(direction) {
+            self.held = None;
+        }
+    }
+
+    /// Stops the repetition of any held key, such as when the window loses focus and releases go unreported.
+    pub fn release_all(&mut self) {
+        self.held = None;
+    }
+
+    /// Determines whether the action of the held key should repeat as of the given time, scheduling the next
+    /// repetition if so. Returns the direction of the held key.
+    pub fn due(&mut self, now: Instant) -> Option<FocusDirection> {
+        let (direction, next) = self.held?;
+        if next > now {
+            return None;
+        }
+        self.held = Some((direction, next + REPEAT_INTERVAL));
+        Some(direction)
+    }
+
+    /// Determines when the action of the held key should next repeat, if a key is held.
+    pub fn next_repeat(&self) -> Option<Instant> {
+        self.held.map(|(_, next)| next)
+    }
+}
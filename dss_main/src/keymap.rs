@@ -0,0 +1,192 @@
+//! The mapping of keys to the actions of the UI, loaded from the configuration file so that keys can be remapped, such
+//! as to WASD, vim keys, or the keycodes of a TV remote.
+
+use crate::gl_utils::FocusDirection;
+use glium::glutin::event::KeyboardInput;
+use log::warn;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The prefix of a configured key given as a raw scancode rather than a key name.
+const SCANCODE_PREFIX: &str = "scancode:";
+
+/// An enumeration of the actions that can be bound to keys.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Action {
+    /// Move the focus in the given direction, repeating while the key is held.
+    Move(FocusDirection),
+    /// Select the focused game, or play its video from the detail view.
+    Select,
+    /// Return to the previous screen, or exit from the grid.
+    Back,
+    /// Retry the days and images that could not be retrieved, or refresh today's games if none failed.
+    Refresh,
+    /// Exit the application.
+    Quit,
+    /// Page towards earlier games, or towards more recent days with Ctrl.
+    PageBack,
+    /// Page towards later games, or towards earlier days with Ctrl.
+    PageForward,
+    /// Switch between fullscreen and a window.
+    Fullscreen,
+    /// Display the tab of the next league, wrapping around to the first.
+    NextTab,
+    /// Display the tab at the given index.
+    SelectTab(usize),
+    /// Open the date picker, to go to the games of any day.
+    JumpToDate,
+    /// Collapse the focused day to its label, or expand it if collapsed.
+    Collapse,
+    /// Show or hide the diagnostics overlay.
+    Diagnostics,
+    /// Pause the ambient audio, or resume it if paused.
+    ToggleAudio,
+    /// Raise the volume of the ambient audio.
+    VolumeUp,
+    /// Lower the volume of the ambient audio.
+    VolumeDown,
+}
+
+/// A container for the keys bound to each action in the configuration file. Keys are named as in winit's
+/// `VirtualKeyCode` (e.g. "Left", "W", "Return"), ignoring case, or given as "scancode:N" for keys without a name.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct KeymapConfig {
+    pub left: Vec<String>,
+    pub right: Vec<String>,
+    pub up: Vec<String>,
+    pub down: Vec<String>,
+    pub select: Vec<String>,
+    pub back: Vec<String>,
+    pub refresh: Vec<String>,
+    pub quit: Vec<String>,
+    pub page_back: Vec<String>,
+    pub page_forward: Vec<String>,
+    pub fullscreen: Vec<String>,
+    pub next_tab: Vec<String>,
+    pub jump_to_date: Vec<String>,
+    pub collapse: Vec<String>,
+    pub diagnostics: Vec<String>,
+    pub toggle_audio: Vec<String>,
+    pub volume_up: Vec<String>,
+    pub volume_down: Vec<String>,
+    /// The keys that display each tab, in tab order.
+    pub tabs: Vec<String>,
+}
+
+impl Default for KeymapConfig {
+    fn default() -> Self {
+        let keys = |names: &[&str]| names.iter().map(|name| (*name).to_owned()).collect();
+        KeymapConfig {
+            left: keys(&["Left"]),
+            right: keys(&["Right"]),
+            up: keys(&["Up"]),
+            down: keys(&["Down"]),
+            select: keys(&["Return", "Space"]),
+            back: keys(&["Escape"]),
+            refresh: keys(&["R"]),
+            quit: keys(&["Q"]),
+            page_back: keys(&["PageUp"]),
+            page_forward: keys(&["PageDown"]),
+            fullscreen: keys(&["F11"]),
+            next_tab: keys(&["Tab"]),
+            jump_to_date: keys(&["D"]),
+            collapse: keys(&["C"]),
+            diagnostics: keys(&["F3"]),
+            toggle_audio: keys(&["PlayPause", "M"]),
+            volume_up: keys(&["VolumeUp", "Equals", "Add"]),
+            volume_down: keys(&["VolumeDown", "Minus", "Subtract"]),
+            tabs: keys(&["Key1", "Key2", "Key3", "Key4", "Key5", "Key6", "Key7", "Key8", "Key9"]),
+        }
+    }
+}
+
+/// An enumeration of the ways a key can be identified.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+enum KeyId {
+    /// The lowercase name of a virtual key code.
+    Name(String),
+    /// A raw scancode.
+    Scancode(u32),
+}
+
+impl KeyId {
+    /// Parses the given configured key. Returns None if it is an invalid scancode, which is logged.
+    fn parse(key: &str) -> Option<Self> {
+        let key = key.trim();
+        if key.len() > SCANCODE_PREFIX.len() && key[..SCANCODE_PREFIX.len()].eq_ignore_ascii_case(SCANCODE_PREFIX) {
+            return match key[SCANCODE_PREFIX.len()..].trim().parse() {
+                Ok(scancode) => Some(KeyId::Scancode(scancode)),
+                Err(ex) => {
+                    warn!("Ignoring invalid key binding {}: {}", key, ex);
+                    None
+                }
+            };
+        }
+        Some(KeyId::Name(key.to_lowercase()))
+    }
+}
+
+/// A mapping of keys to the actions of the UI.
+#[derive(Clone, Debug, Default)]
+pub struct Keymap {
+    bindings: HashMap<KeyId, Action>,
+}
+
+impl Keymap {
+    /// Creates the keymap described by the given configuration. A key bound to several actions performs the last.
+    pub fn from_config(config: &KeymapConfig) -> Self {
+        let actions = [
+            (&config.left, Action::Move(FocusDirection::Left)),
+            (&config.right, Action::Move(FocusDirection::Right)),
+            (&config.up, Action::Move(FocusDirection::Up)),
+            (&config.down, Action::Move(FocusDirection::Down)),
+            (&config.select, Action::Select),
+            (&config.back, Action::Back),
+            (&config.refresh, Action::Refresh),
+            (&config.quit, Action::Quit),
+            (&config.page_back, Action::PageBack),
+            (&config.page_forward, Action::PageForward),
+            (&config.fullscreen, Action::Fullscreen),
+            (&config.next_tab, Action::NextTab),
+            (&config.jump_to_date, Action::JumpToDate),
+            (&config.collapse, Action::Collapse),
+            (&config.diagnostics, Action::Diagnostics),
+            (&config.toggle_audio, Action::ToggleAudio),
+            (&config.volume_up, Action::VolumeUp),
+            (&config.volume_down, Action::VolumeDown),
+        ];
+        let tabs = config
+            .tabs
+            .iter()
+            .enumerate()
+            .map(|(tab, key)| (key, Action::SelectTab(tab)));
+        let mut bindings = HashMap::new();
+        for (keys, action) in actions.iter() {
+            for key in keys.iter().filter_map(|key| KeyId::parse(key)) {
+                bindings.insert(key, *action);
+            }
+        }
+        for (key, action) in tabs {
+            if let Some(key) = KeyId::parse(key) {
+                bindings.insert(key, action);
+            }
+        }
+        Keymap { bindings }
+    }
+
+    /// Determines the action bound to the key of the given input, by name if it has one and otherwise by scancode.
+    pub fn action(&self, input: &KeyboardInput) -> Option<Action> {
+        let by_name = input
+            .virtual_keycode
+            .and_then(|code| self.bindings.get(&KeyId::Name(format!("{:?}", code).to_lowercase())));
+        by_name
+            .or_else(|| self.bindings.get(&KeyId::Scancode(input.scancode)))
+            .copied()
+    }
+
+    /// Determines the action bound to the given key, named as in the configuration file.
+    pub fn action_for_key(&self, key: &str) -> Option<Action> {
+        KeyId::parse(key).and_then(|key| self.bindings.get(&key)).copied()
+    }
+}
@@ -0,0 +1,218 @@
+//! The grids of leagues supplied by a schedule provider, each displayed in its own tab beside the MLB grid.
+
+use crate::diagnostics::TextureStats;
+use crate::gl_utils::{self, GlyphBrush};
+use crate::locale::Strings;
+use crate::rail::{self, Caption, GridFocus, Rail, RailGrid, RailPainter};
+use crate::tween::{self, Easing, Tween};
+use dss_core::{RailEntry, ScheduleProvider};
+use dss_nfl::NflProvider;
+use glium::backend::Facade;
+use glium::texture::{RawImage2d, SrgbTexture2d};
+use log::{debug, error};
+use std::rc::Rc;
+
+/// Creates the schedule providers of the leagues displayed in tabs after MLB, in tab order.
+pub fn providers() -> Vec<Box<dyn ScheduleProvider>> {
+    vec![Box::new(NflProvider)]
+}
+
+/// An enumeration of the retrieval states of the rails of a league.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LeagueState {
+    /// The rails have not been requested.
+    NotLoaded,
+    /// The rails have been requested and not yet delivered.
+    Loading,
+    /// The rails have been retrieved.
+    Loaded,
+    /// The rails could not be retrieved.
+    Unavailable,
+}
+
+/// A container for backing information for a single entry.
+struct EntryTile {
+    entry: RailEntry,
+    texture: Option<Rc<SrgbTexture2d>>,
+    image_failed: bool,
+    focus: Tween,
+}
+
+impl EntryTile {
+    /// Lazily initializes the texture for the image of the entry, if it has an image that can be decoded.
+    fn get_texture(&mut self, display: &dyn Facade) -> Option<Rc<SrgbTexture2d>> {
+        if self.texture.is_none() && !self.image_failed {
+            let decoded = self.entry.image.as_ref().and_then(|bytes| {
+                image::load_from_memory(bytes)
+                    .map_err(|ex| error!("Could not decode image for {}:\n{}", self.entry.title, ex))
+                    .ok()
+            });
+            match decoded {
+                Some(decoded) => {
+                    let rgba = decoded.into_rgba();
+                    let dimensions = rgba.dimensions();
+                    let raw = RawImage2d::from_raw_rgba_reversed(&rgba.into_raw(), dimensions);
+                    let texture = gl_utils::mipmapped_texture(display, raw).unwrap_or_else(|ex| {
+                        let msg = "Could not create entry texture";
+                        error!("{}:\n{}", msg, ex);
+                        panic!("{}.", msg);
+                    });
+                    self.texture = Some(Rc::new(texture));
+                }
+                None => self.image_failed = true,
+            }
+        }
+        self.texture.clone()
+    }
+}
+
+/// A rail of the entries supplied by a schedule provider. Entries with an image are displayed as tiles, and entries
+/// without one as cards showing their title.
+pub struct EntryRail {
+    label: String,
+    tiles: Vec<EntryTile>,
+    begin_index: usize,
+    scroll: Tween,
+}
+
+impl From<dss_core::Rail> for EntryRail {
+    fn from(orig: dss_core::Rail) -> Self {
+        EntryRail {
+            label: orig.label,
+            tiles: orig
+                .entries
+                .into_iter()
+                .map(|entry| EntryTile {
+                    entry,
+                    texture: None,
+                    image_failed: false,
+                    focus: Tween::new(0.0, tween::FOCUS_DURATION),
+                })
+                .collect(),
+            begin_index: 0,
+            scroll: Tween::new(0.0, tween::PAGE_DURATION).with_easing(Easing::InOut),
+        }
+    }
+}
+
+impl Rail for EntryRail {
+    fn label(&self, _strings: &Strings) -> String {
+        self.label.clone()
+    }
+
+    fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    fn begin_index(&self) -> usize {
+        self.begin_index
+    }
+
+    fn set_begin_index(&mut self, begin_index: usize) {
+        self.begin_index = begin_index;
+    }
+
+    fn draw(&mut self, painter: &mut RailPainter, y: f32, focused: Option<usize>) {
+        let scroll = painter.animate(&mut self.scroll, self.begin_index as f32);
+        for (index, x) in rail::visible_columns(scroll) {
+            if let Some(tile) = self.tiles.get_mut(index) {
+                let focus = painter.animate(&mut tile.focus, if focused == Some(index) { 1.0 } else { 0.0 });
+                match tile.get_texture(painter.display) {
+                    Some(texture) => painter.queue_tile(&[(texture, 1.0)], (x, y), focus, false, Vec::new()),
+                    None => painter.queue_placeholder((x, y), focus),
+                }
+                debug!("entry at {}, {} drawn", x, y);
+            }
+        }
+    }
+
+    /// Queues the titles of the entries displayed as cards.
+    fn queue_text(&self, painter: &RailPainter, text_brush: &mut GlyphBrush, y: f32, _focused: Option<usize>) {
+        let scroll = self.scroll.value();
+        for (index, x) in rail::visible_columns(scroll) {
+            if let Some(tile) = self.tiles.get(index) {
+                if tile.texture.is_none() {
+                    let focus = tile.focus.value();
+                    painter.queue_tile_text(text_brush, &tile.entry.title, painter.theme.text_color, (x, y), focus);
+                }
+            }
+        }
+    }
+
+    fn caption(&self, index: usize, _strings: &Strings) -> Option<Caption> {
+        let entry = &self.tiles.get(index)?.entry;
+        Some(Caption {
+            title: entry.title.clone(),
+            summary: entry.summary.clone(),
+            accent: false,
+        })
+    }
+
+    fn select(&self, _index: usize) -> Option<u64> {
+        None
+    }
+}
+
+/// A container for the backing information of a league displayed in its own tab.
+pub struct LeagueUiInfo {
+    pub name: String,
+    pub state: LeagueState,
+    rails: Vec<EntryRail>,
+    grid: GridFocus,
+}
+
+impl LeagueUiInfo {
+    /// Initializes the backing information of the league with the given name, whose rails have not been requested.
+    pub fn new(name: &str) -> Self {
+        LeagueUiInfo {
+            name: name.to_owned(),
+            state: LeagueState::NotLoaded,
+            rails: Vec::new(),
+            grid: GridFocus::new(),
+        }
+    }
+
+    /// Replaces the rails of the league with the given retrieved rails, or marks them unavailable if None. Rails that
+    /// could not be retrieved again are kept.
+    pub fn set_rails(&mut self, rails: Option<Vec<dss_core::Rail>>) {
+        match rails {
+            Some(rails) => {
+                self.rails = rails.into_iter().map(EntryRail::from).collect();
+                self.state = LeagueState::Loaded;
+                self.clamp_focus();
+            }
+            None if self.rails.is_empty() => self.state = LeagueState::Unavailable,
+            None => self.state = LeagueState::Loaded,
+        }
+    }
+
+    /// Counts the textures of the entries of the league in the given statistics.
+    pub fn count_textures(&self, stats: &mut TextureStats) {
+        let textures = self
+            .rails
+            .iter()
+            .flat_map(|rail| &rail.tiles)
+            .filter_map(|tile| tile.texture.as_deref());
+        for texture in textures {
+            stats.add(texture);
+        }
+    }
+}
+
+impl RailGrid for LeagueUiInfo {
+    fn rails(&self) -> Vec<&dyn Rail> {
+        self.rails.iter().map(|rail| rail as &dyn Rail).collect()
+    }
+
+    fn rails_mut(&mut self) -> Vec<&mut dyn Rail> {
+        self.rails.iter_mut().map(|rail| rail as &mut dyn Rail).collect()
+    }
+
+    fn grid(&self) -> &GridFocus {
+        &self.grid
+    }
+
+    fn grid_mut(&mut self) -> &mut GridFocus {
+        &mut self.grid
+    }
+}
@@ -0,0 +1,137 @@
+//! Navigation with infrared remotes through LIRC on Linux. The buttons decoded by `lircd` are read from its socket on a
+//! background thread, mapped to keys of the keymap, and delivered to the event loop as `UiEvent::Lirc`, so that the UI
+//! can be driven by the remote of an HTPC.
+
+use crate::keymap::{Action, Keymap};
+use crate::loader::UiEvent;
+use glium::glutin::event_loop::EventLoopProxy;
+use log::{debug, error, warn};
+use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+#[cfg(unix)]
+use std::time::Duration;
+
+/// The time to wait before connecting to `lircd` again after the connection fails or ends.
+#[cfg(unix)]
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// A container for the LIRC settings read from the configuration file.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct LircConfig {
+    /// Whether to read buttons from `lircd`.
+    pub enabled: bool,
+    /// The path of the socket to which `lircd` writes decoded buttons.
+    pub socket: PathBuf,
+    /// The key of the keymap pressed by each button, by the name of the button in the LIRC configuration of the remote
+    /// (e.g. "KEY_OK" = "Return").
+    pub buttons: BTreeMap<String, String>,
+}
+
+impl Default for LircConfig {
+    fn default() -> Self {
+        let mut buttons: BTreeMap<String, String> = [
+            ("KEY_UP", "Up"),
+            ("KEY_DOWN", "Down"),
+            ("KEY_LEFT", "Left"),
+            ("KEY_RIGHT", "Right"),
+            ("KEY_OK", "Return"),
+            ("KEY_ENTER", "Return"),
+            ("KEY_SELECT", "Return"),
+            ("KEY_BACK", "Escape"),
+            ("KEY_EXIT", "Escape"),
+            ("KEY_PAGEUP", "PageUp"),
+            ("KEY_PAGEDOWN", "PageDown"),
+            ("KEY_CHANNELUP", "PageUp"),
+            ("KEY_CHANNELDOWN", "PageDown"),
+            ("KEY_PLAYPAUSE", "PlayPause"),
+            ("KEY_VOLUMEUP", "VolumeUp"),
+            ("KEY_VOLUMEDOWN", "VolumeDown"),
+            ("KEY_INFO", "F3"),
+        ]
+        .iter()
+        .map(|(button, key)| ((*button).to_owned(), (*key).to_owned()))
+        .collect();
+        for digit in 1..=9 {
+            buttons.insert(format!("KEY_{}", digit), format!("Key{}", digit));
+        }
+        LircConfig {
+            enabled: false,
+            socket: PathBuf::from("/var/run/lirc/lircd"),
+            buttons,
+        }
+    }
+}
+
+/// Starts the background thread, which delivers the buttons decoded by `lircd` to the event loop of the given proxy as
+/// the actions they are bound to by the given settings and keymap. The connection is retried if `lircd` is not running
+/// or restarts, leaving the UI usable without it.
+#[cfg(unix)]
+pub fn spawn(proxy: EventLoopProxy<UiEvent>, config: &LircConfig, keymap: &Keymap) {
+    use std::io::{BufRead, BufReader};
+    use std::os::unix::net::UnixStream;
+    use std::thread;
+
+    let config = config.clone();
+    let keymap = keymap.clone();
+    let spawned = thread::Builder::new().name("lirc".to_owned()).spawn(move || {
+        // failures to connect are only logged once until a connection succeeds, as they are retried indefinitely
+        let mut warned = false;
+        loop {
+            match UnixStream::connect(&config.socket) {
+                Ok(stream) => {
+                    debug!("connected to lircd at {}", config.socket.display());
+                    warned = false;
+                    for line in BufReader::new(stream).lines() {
+                        let line = match line {
+                            Ok(line) => line,
+                            Err(ex) => {
+                                warn!("Could not read from lircd:\n{}", ex);
+                                break;
+                            }
+                        };
+                        if let Some(action) = parse_line(&line, &config.buttons, &keymap) {
+                            debug!("lirc input {:?}", action);
+                            if proxy.send_event(UiEvent::Lirc(action)).is_err() {
+                                debug!("ending lirc");
+                                return;
+                            }
+                        }
+                    }
+                }
+                Err(ex) if !warned => {
+                    warn!("Could not connect to lircd at {}:\n{}", config.socket.display(), ex);
+                    warned = true;
+                }
+                Err(_) => (),
+            }
+            thread::sleep(RECONNECT_DELAY);
+        }
+    });
+    if let Err(ex) = spawned {
+        error!("Could not create lirc thread:\n{}", ex);
+    }
+}
+
+/// Logs that LIRC is only available on Linux, leaving the UI usable without it.
+#[cfg(not(unix))]
+pub fn spawn(_proxy: EventLoopProxy<UiEvent>, _config: &LircConfig, _keymap: &Keymap) {
+    error!("LIRC is only supported on Linux");
+}
+
+#[cfg(unix)]
+/// Determines the action of the given line written by `lircd`, a button decoded as "<code> <repeat> <button> <remote>"
+/// with the repeat count in hexadecimal, mapped through the given buttons and keymap. Repeats are only acted on for
+/// actions that repeat while a key is held. Returns None if the line is not a button bound to an action.
+fn parse_line(line: &str, buttons: &BTreeMap<String, String>, keymap: &Keymap) -> Option<Action> {
+    let mut fields = line.split_whitespace();
+    let repeat = u32::from_str_radix(fields.nth(1)?, 16).ok()?;
+    let button = fields.next()?;
+    let action = keymap.action_for_key(buttons.get(button)?)?;
+    match action {
+        Action::Move(_) | Action::VolumeUp | Action::VolumeDown => Some(action),
+        _ if repeat == 0 => Some(action),
+        _ => None,
+    }
+}
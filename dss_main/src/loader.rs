@@ -0,0 +1,377 @@
+//! Background retrieval of data requested by the UI, so that the event loop is never blocked by network requests.
+
+use crate::decode::DecodedImage;
+use crate::gamepad::GamepadInput;
+use crate::keymap::Action;
+use crate::league;
+use crate::options::Options;
+use crate::remote::RemoteCommand;
+use chrono::{Duration, Local, NaiveDate};
+use dss_core::{AggregateResult, Aggregator};
+use dss_mlb::detail::MlbGameDetail;
+use dss_mlb::standings::MlbDivisionStandings;
+use dss_mlb::{CacheStats, MlbClient, MlbGameClientInfo, MlbLoadProgress};
+use futures::future::{AbortHandle, Abortable};
+use glium::glutin::event_loop::EventLoopProxy;
+use log::{debug, error};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration as StdDuration, Instant};
+
+/// An enumeration of the data the UI can request from the background.
+#[derive(Debug)]
+pub enum LoadRequest {
+    /// The games of the given number of days, ending with today. Each day is retrieved independently, so that days are
+    /// delivered as soon as they are complete.
+    Games(u32),
+    /// The games of the given days, such as days that could not be retrieved.
+    Days(Vec<NaiveDate>),
+    /// The games of today, bypassing any cached data.
+    Refresh,
+    /// The recap and line score of the game with the given identifier.
+    GameDetail(u64),
+    /// The image at each given URL, for the game with the paired identifier, downscaled to fit within the given size in
+    /// pixels if known.
+    Images(Vec<(u64, String)>, Option<(u32, u32)>),
+    /// The current standings of every division.
+    Standings,
+    /// The rails of every league among `league::providers`, retrieved concurrently.
+    Leagues,
+}
+
+/// An enumeration of the events delivered to the event loop when requested data has been retrieved.
+#[derive(Debug)]
+pub enum UiEvent {
+    /// The retrieval of games for the given days, most recent first, has started.
+    DaysPending(Vec<NaiveDate>),
+    /// The retrieval of games has progressed, summed over every pending day.
+    LoadProgress(MlbLoadProgress),
+    /// The games of the given day have been retrieved.
+    DayLoaded(NaiveDate, Vec<MlbGameClientInfo>),
+    /// The games of the given day could not be retrieved.
+    DayFailed(NaiveDate),
+    /// The detail of the game with the given identifier, or `None` if it could not be retrieved.
+    GameDetailLoaded(u64, Option<MlbGameDetail>),
+    /// The image of the game with the given identifier, or `None` if it could not be retrieved.
+    ImageLoaded(u64, Option<DecodedImage>),
+    /// The standings of every division, or `None` if they could not be retrieved.
+    StandingsLoaded(Option<Vec<MlbDivisionStandings>>),
+    /// The rails of the leagues among `league::providers` that could be retrieved, and the failures of the others.
+    LeaguesLoaded(AggregateResult),
+    /// A gamepad has performed the given action.
+    Gamepad(GamepadInput),
+    /// A remote has sent the given command.
+    Remote(RemoteCommand),
+    /// An infrared remote has pressed a button bound to the given action.
+    Lirc(Action),
+    /// A frame of a playing video has been decoded.
+    VideoFrame,
+    /// The video with the given identifier has ended.
+    VideoFinished(u64),
+    /// A shader file has changed while being watched in a debug build.
+    ShadersChanged,
+}
+
+/// A container for measurements of the retrievals of the loader, for diagnostics.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LoaderStats {
+    /// The number of requested days answered from the cache and the number retrieved.
+    pub cache: CacheStats,
+    /// The time taken by the most recently completed request, or None before any request completes.
+    pub last_fetch: Option<StdDuration>,
+}
+
+/// The handle to the background thread that retrieves data requested by the UI.
+pub struct Loader {
+    sender: Option<Sender<LoadRequest>>,
+    cancellation: Arc<Mutex<Cancellation>>,
+    stats: Arc<Mutex<LoaderStats>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+/// A container for the state shared with the background thread to cancel its retrievals.
+#[derive(Debug, Default)]
+struct Cancellation {
+    stopped: bool,
+    current: Option<AbortHandle>,
+}
+
+/// A runtime on which retrievals are run until complete or cancelled.
+struct Jobs {
+    runtime: tokio::runtime::Runtime,
+    cancellation: Arc<Mutex<Cancellation>>,
+}
+
+impl Jobs {
+    /// Runs the given future to completion. Returns None if the loader is shut down before it completes.
+    fn block_on<F: Future>(&mut self, future: F) -> Option<F::Output> {
+        let (handle, registration) = AbortHandle::new_pair();
+        {
+            let mut cancellation = self.cancellation.lock().unwrap();
+            if cancellation.stopped {
+                return None;
+            }
+            cancellation.current = Some(handle);
+        }
+        let output = self.runtime.block_on(Abortable::new(future, registration)).ok();
+        self.cancellation.lock().unwrap().current = None;
+        output
+    }
+}
+
+impl Loader {
+    /// Starts the background thread, which retrieves data according to the given options and delivers it to the event
+    /// loop of the given proxy. Today's games are refreshed at the interval set by the options.
+    ///
+    /// # Errors
+    /// Panics if the background thread or its runtime cannot be created.
+    pub fn spawn(proxy: EventLoopProxy<UiEvent>, options: &Options) -> Self {
+        let cache_dir = options.cache_dir.clone();
+        let cache_policy = options.cache_policy.clone();
+        let language = options.content_language();
+        let refresh_interval = options.refresh_interval;
+        let (sender, receiver) = mpsc::channel::<LoadRequest>();
+        let cancellation = Arc::new(Mutex::new(Cancellation::default()));
+        let thread_cancellation = Arc::clone(&cancellation);
+        let stats = Arc::new(Mutex::new(LoaderStats::default()));
+        let thread_stats = Arc::clone(&stats);
+        let thread = thread::Builder::new()
+            .name("loader".to_owned())
+            .spawn(move || {
+                let runtime = tokio::runtime::Builder::new()
+                    .basic_scheduler()
+                    .enable_all()
+                    .build()
+                    .unwrap_or_else(|ex| {
+                        let msg = "Could not create loader runtime";
+                        error!("{}:\n{}", msg, ex);
+                        panic!("{}.", msg);
+                    });
+                let mut jobs = Jobs {
+                    runtime,
+                    cancellation: thread_cancellation,
+                };
+                let mut client = MlbClient::new().with_lazy_images().with_cache_policy(cache_policy);
+                if let Some(language) = &language {
+                    client = client.with_language(language);
+                }
+                if let Some(cache_dir) = cache_dir {
+                    client = client.with_cache_dir(cache_dir);
+                }
+                let mut aggregator = Aggregator::new();
+                for provider in league::providers() {
+                    aggregator.register(provider);
+                }
+                let mut next_refresh = refresh_interval.map(|interval| Instant::now() + interval);
+                loop {
+                    let received = match next_refresh {
+                        Some(at) => receiver.recv_timeout(at.saturating_duration_since(Instant::now())),
+                        None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+                    };
+                    let request = match received {
+                        Ok(request) => request,
+                        Err(RecvTimeoutError::Timeout) => {
+                            next_refresh = refresh_interval.map(|interval| Instant::now() + interval);
+                            LoadRequest::Refresh
+                        }
+                        Err(RecvTimeoutError::Disconnected) => break,
+                    };
+                    debug!("starting {:?}", request);
+                    let started_at = Instant::now();
+                    let delivered = match request {
+                        LoadRequest::Games(days) => load_games(&mut jobs, &client, &proxy, days),
+                        LoadRequest::Days(dates) => load_days(&mut jobs, &client, &proxy, dates),
+                        LoadRequest::Refresh => refresh_today(&mut jobs, &client, &proxy),
+                        LoadRequest::GameDetail(game_pk) => match jobs.block_on(client.get_game_detail(game_pk)) {
+                            Some(Ok(detail)) => proxy
+                                .send_event(UiEvent::GameDetailLoaded(game_pk, Some(detail)))
+                                .is_ok(),
+                            Some(Err(ex)) => {
+                                error!("Error while retrieving detail for {}:\n{}", game_pk, ex);
+                                proxy.send_event(UiEvent::GameDetailLoaded(game_pk, None)).is_ok()
+                            }
+                            None => false,
+                        },
+                        LoadRequest::Images(images, max_size) => {
+                            load_images(&mut jobs, &client, &proxy, images, max_size)
+                        }
+                        LoadRequest::Standings => match jobs.block_on(client.get_standings()) {
+                            Some(Ok(standings)) => proxy.send_event(UiEvent::StandingsLoaded(Some(standings))).is_ok(),
+                            Some(Err(ex)) => {
+                                error!("Error while retrieving standings:\n{}", ex);
+                                proxy.send_event(UiEvent::StandingsLoaded(None)).is_ok()
+                            }
+                            None => false,
+                        },
+                        LoadRequest::Leagues => match jobs.block_on(aggregator.fetch_all()) {
+                            Some(result) => proxy.send_event(UiEvent::LeaguesLoaded(result)).is_ok(),
+                            None => false,
+                        },
+                    };
+                    *thread_stats.lock().unwrap() = LoaderStats {
+                        cache: client.cache_stats(),
+                        last_fetch: Some(started_at.elapsed()),
+                    };
+                    if !delivered {
+                        break;
+                    }
+                }
+                debug!("ending loader");
+            })
+            .unwrap_or_else(|ex| {
+                let msg = "Could not create loader thread";
+                error!("{}:\n{}", msg, ex);
+                panic!("{}.", msg);
+            });
+        Loader {
+            sender: Some(sender),
+            cancellation,
+            stats,
+            thread: Some(thread),
+        }
+    }
+
+    /// Determines the measurements of the retrievals completed so far.
+    pub fn stats(&self) -> LoaderStats {
+        *self.stats.lock().unwrap()
+    }
+
+    /// Queues the given request. The result will be delivered to the event loop as a `UiEvent`. Requests made after
+    /// the loader has been shut down are ignored.
+    pub fn request(&self, request: LoadRequest) {
+        if let Some(sender) = &self.sender {
+            if let Err(ex) = sender.send(request) {
+                error!("Could not queue load request:\n{}", ex);
+            }
+        }
+    }
+
+    /// Cancels the retrieval in progress and any queued requests, and waits for the background thread to end. Days
+    /// are persisted to the cache directory as they are retrieved, so nothing retrieved is lost.
+    pub fn shutdown(&mut self) {
+        {
+            let mut cancellation = self.cancellation.lock().unwrap();
+            cancellation.stopped = true;
+            if let Some(current) = cancellation.current.take() {
+                current.abort();
+            }
+        }
+        self.sender = None;
+        if let Some(thread) = self.thread.take() {
+            if thread.join().is_err() {
+                error!("Loader thread panicked");
+            }
+        }
+    }
+}
+
+/// Retrieves the games of the given number of days, ending with today, delivering each day to the event loop of the
+/// given proxy as soon as it is complete. Returns false if the event loop has closed or the loader has been shut down.
+fn load_games(jobs: &mut Jobs, client: &MlbClient, proxy: &EventLoopProxy<UiEvent>, days: u32) -> bool {
+    let today = Local::today().naive_local();
+    let dates: Vec<NaiveDate> = (0..days.max(1))
+        .map(|offset| today - Duration::days(i64::from(offset)))
+        .collect();
+    if proxy.send_event(UiEvent::DaysPending(dates.clone())).is_err() {
+        return false;
+    }
+    load_days(jobs, client, proxy, dates)
+}
+
+/// Retrieves the games of the given days, delivering each day to the event loop of the given proxy as soon as it is
+/// complete. Returns false if the event loop has closed or the loader has been shut down.
+fn load_days(jobs: &mut Jobs, client: &MlbClient, proxy: &EventLoopProxy<UiEvent>, dates: Vec<NaiveDate>) -> bool {
+    let progress_by_day = RefCell::new(BTreeMap::new());
+    let futures = dates.into_iter().map(|date| {
+        let progress_by_day = &progress_by_day;
+        async move {
+            let progress = |progress| {
+                let mut progress_by_day = progress_by_day.borrow_mut();
+                progress_by_day.insert(date, progress);
+                let summed = progress_by_day.values().fold(
+                    MlbLoadProgress { completed: 0, total: 0 },
+                    |sum: MlbLoadProgress, day: &MlbLoadProgress| MlbLoadProgress {
+                        completed: sum.completed + day.completed,
+                        total: sum.total + day.total,
+                    },
+                );
+                let _ = proxy.send_event(UiEvent::LoadProgress(summed));
+            };
+            match client.get_games_in_range_with_progress(date..=date, &progress).await {
+                Ok(mut results) => {
+                    let games = results.remove(&date).unwrap_or_default();
+                    debug!("loaded {} games for {}", games.len(), date);
+                    proxy.send_event(UiEvent::DayLoaded(date, games)).is_ok()
+                }
+                Err(ex) => {
+                    error!("Error while retrieving games for {}:\n{}", date, ex);
+                    proxy.send_event(UiEvent::DayFailed(date)).is_ok()
+                }
+            }
+        }
+    });
+    jobs.block_on(futures::future::join_all(futures))
+        .map(|delivered| delivered.into_iter().all(|delivered| delivered))
+        .unwrap_or(false)
+}
+
+/// Retrieves the games of today, bypassing any cached data, and delivers them to the event loop of the given proxy.
+/// Errors are logged and reported to the event loop, which keeps the displayed games. Returns false if the event loop
+/// has closed or the loader has been shut down.
+fn refresh_today(jobs: &mut Jobs, client: &MlbClient, proxy: &EventLoopProxy<UiEvent>) -> bool {
+    let today = Local::today().naive_local();
+    client.invalidate(today);
+    match jobs.block_on(client.get_games_in_range(today..=today)) {
+        Some(Ok(mut results)) => {
+            let games = results.remove(&today).unwrap_or_default();
+            debug!("refreshed {} games for {}", games.len(), today);
+            proxy.send_event(UiEvent::DayLoaded(today, games)).is_ok()
+        }
+        Some(Err(ex)) => {
+            error!("Error while refreshing games for {}:\n{}", today, ex);
+            proxy.send_event(UiEvent::DayFailed(today)).is_ok()
+        }
+        None => false,
+    }
+}
+
+/// Retrieves the given images concurrently and decodes them, downscaled to fit within the given size in pixels if known,
+/// on the blocking pool of the runtime, delivering each to the event loop of the given proxy as soon as it is decoded.
+/// Returns false if the event loop has closed or the loader has been shut down.
+fn load_images(
+    jobs: &mut Jobs,
+    client: &MlbClient,
+    proxy: &EventLoopProxy<UiEvent>,
+    images: Vec<(u64, String)>,
+    max_size: Option<(u32, u32)>,
+) -> bool {
+    let futures = images.into_iter().map(|(game_pk, url)| async move {
+        let image = match client.get_image(&url).await {
+            Ok(image) => {
+                match tokio::task::spawn_blocking(move || DecodedImage::decode_retrieved(&image, max_size)).await {
+                    Ok(Ok(decoded)) => Some(decoded),
+                    Ok(Err(ex)) => {
+                        error!("Could not decode image {}:\n{}", url, ex);
+                        None
+                    }
+                    Err(ex) => {
+                        error!("Error while decoding image {}:\n{}", url, ex);
+                        None
+                    }
+                }
+            }
+            Err(ex) => {
+                error!("Error while retrieving image {}:\n{}", url, ex);
+                None
+            }
+        };
+        proxy.send_event(UiEvent::ImageLoaded(game_pk, image)).is_ok()
+    });
+    jobs.block_on(futures::future::join_all(futures))
+        .map(|delivered| delivered.into_iter().all(|delivered| delivered))
+        .unwrap_or(false)
+}
@@ -0,0 +1,134 @@
+//! The catalog of user-visible text, selected by the configured locale so that the UI can be displayed in languages
+//! other than English. Catalogs are Fluent files named for their locale (e.g. "es-MX.ftl"), read from the directory of
+//! the configuration file; any message a catalog does not define is displayed in English.
+
+use chrono::{DateTime, Datelike, Local, NaiveDate, Timelike, Weekday};
+use fluent_bundle::{FluentArgs, FluentBundle, FluentResource, FluentValue};
+use log::{error, warn};
+use std::fs;
+use std::path::Path;
+use unic_langid::LanguageIdentifier;
+
+/// The locale of the embedded catalog.
+pub const DEFAULT_LOCALE: &str = "en-US";
+/// The embedded catalog, used for messages missing from the catalog of the configured locale.
+const DEFAULT_CATALOG: &str = include_str!("en-US.ftl");
+/// The extension of catalog files.
+const CATALOG_EXTENSION: &str = "ftl";
+
+/// The user-visible text of the configured locale.
+pub struct Strings {
+    bundle: FluentBundle<FluentResource>,
+}
+
+impl Strings {
+    /// Loads the catalog of the given locale from the given directory over the embedded English catalog. If the locale
+    /// is invalid or its catalog cannot be read, a warning is logged and English is used.
+    ///
+    /// # Errors
+    /// Panics if the embedded catalog is invalid.
+    pub fn load(locale: &str, dir: Option<&Path>) -> Self {
+        let langid = locale.parse::<LanguageIdentifier>().unwrap_or_else(|ex| {
+            warn!("Ignoring invalid locale {}: {}", locale, ex);
+            LanguageIdentifier::default()
+        });
+        let mut bundle = FluentBundle::new(vec![langid]);
+        // isolation marks around arguments are not in most fonts, and would be drawn as boxes
+        bundle.set_use_isolating(false);
+        let default = FluentResource::try_new(DEFAULT_CATALOG.to_owned()).unwrap_or_else(|(_, errors)| {
+            let msg = "Could not parse embedded catalog";
+            error!("{}:\n{:?}", msg, errors);
+            panic!("{}.", msg);
+        });
+        bundle.add_resource(default).unwrap_or_else(|errors| {
+            let msg = "Could not add embedded catalog";
+            error!("{}:\n{:?}", msg, errors);
+            panic!("{}.", msg);
+        });
+        if locale != DEFAULT_LOCALE {
+            if let Some(dir) = dir {
+                let path = dir.join(locale).with_extension(CATALOG_EXTENSION);
+                match fs::read_to_string(&path) {
+                    Ok(text) => {
+                        let resource = FluentResource::try_new(text).unwrap_or_else(|(resource, errors)| {
+                            warn!("Ignoring invalid entries of catalog {}: {:?}", path.display(), errors);
+                            resource
+                        });
+                        bundle.add_resource_overriding(resource);
+                    }
+                    Err(ex) => warn!("Could not read catalog {}, using English: {}", path.display(), ex),
+                }
+            }
+        }
+        Strings { bundle }
+    }
+
+    /// Retrieves the text of the message with the given identifier, or the identifier if there is no such message.
+    pub fn get(&self, id: &str) -> String {
+        self.format(id, &[])
+    }
+
+    /// Retrieves the text of the message with the given identifier with the given named arguments, or the identifier
+    /// if there is no such message.
+    pub fn format(&self, id: &str, args: &[(&str, FluentValue)]) -> String {
+        let pattern = match self.bundle.get_message(id).and_then(|message| message.value()) {
+            Some(pattern) => pattern,
+            None => {
+                warn!("Missing text for {}", id);
+                return id.to_owned();
+            }
+        };
+        let mut fluent_args = FluentArgs::new();
+        for (name, value) in args {
+            fluent_args.set(*name, value.clone());
+        }
+        let mut errors = Vec::new();
+        let text = self.bundle.format_pattern(pattern, Some(&fluent_args), &mut errors);
+        if !errors.is_empty() {
+            warn!("Could not format text for {}: {:?}", id, errors);
+        }
+        text.into_owned()
+    }
+
+    /// Formats the given date with the format of the message with the given identifier, naming the weekday and month
+    /// in the configured locale.
+    pub fn format_date(&self, date: NaiveDate, format_id: &str) -> String {
+        let format = self.localize_format(&self.get(format_id), date.weekday(), date.month(), None);
+        date.format(&format).to_string()
+    }
+
+    /// Formats the given time with the format of the message with the given identifier, naming the weekday, month,
+    /// and period of the day in the configured locale.
+    pub fn format_time(&self, time: DateTime<Local>, format_id: &str) -> String {
+        let format = self.localize_format(&self.get(format_id), time.weekday(), time.month(), Some(time.hour()));
+        time.format(&format).to_string()
+    }
+
+    /// Replaces the weekday (%A), month (%B), and period of the day (%p) of the given chrono format with their names in
+    /// the configured locale, for the given weekday, month, and hour if known.
+    fn localize_format(&self, format: &str, weekday: Weekday, month: u32, hour: Option<u32>) -> String {
+        let mut localized = String::with_capacity(format.len());
+        let mut chars = format.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                localized.push(c);
+                continue;
+            }
+            let specifier = chars.next();
+            let name = match (specifier, hour) {
+                (Some('A'), _) => Some(self.get(&format!("weekday-{}", weekday.number_from_monday()))),
+                (Some('B'), _) => Some(self.get(&format!("month-{}", month))),
+                (Some('p'), Some(hour)) => Some(self.get(if hour < 12 { "time-am" } else { "time-pm" })),
+                _ => None,
+            };
+            match name {
+                Some(name) => localized.push_str(&name.replace('%', "%%")),
+                None => {
+                    localized.push(c);
+                    localized.extend(specifier);
+                }
+            }
+        }
+        localized
+    }
+}
@@ -0,0 +1,166 @@
+//! The logging of the application to a log file, rolled over as it grows and optionally each day, and to the console,
+//! configured from the configuration file.
+
+use chrono::{Local, NaiveDate};
+use log::LevelFilter;
+use log4rs::append::console::{ConsoleAppender, Target};
+use log4rs::append::file::FileAppender;
+use log4rs::append::rolling_file::policy::compound::roll::fixed_window::FixedWindowRoller;
+use log4rs::append::rolling_file::policy::compound::trigger::size::SizeTrigger;
+use log4rs::append::rolling_file::policy::compound::CompoundPolicy;
+use log4rs::append::rolling_file::RollingFileAppender;
+use log4rs::append::Append;
+use log4rs::config::{Appender, Config, Logger, Root};
+use log4rs::encode::pattern::PatternEncoder;
+use log4rs::Handle;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// The layout of each logged message.
+const LOG_PATTERN: &str = "{d(%Y-%m-%d %H:%M:%S)} - {({l}):5.5}{n}    {m}{n}{n}";
+/// The layout of each message logged to the console, which is kept to a single line.
+const CONSOLE_PATTERN: &str = "{d(%H:%M:%S)} {h({l}):5.5} {t} - {m}{n}";
+
+/// A container for the logging settings read from the configuration file.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct LoggingConfig {
+    /// Whether messages are also logged to the console.
+    pub console: bool,
+    /// The level at which messages are logged for each module (e.g. "dss_mlb" = "debug"), overriding the log level.
+    pub modules: BTreeMap<String, String>,
+    /// The path of the log file.
+    pub file: PathBuf,
+    /// The size in kilobytes at which the log file is rolled over, or 0 to never roll it over.
+    pub max_size_kb: u64,
+    /// The number of rolled over log files kept.
+    pub max_files: u32,
+    /// Whether a new log file, named with its date, is started each day.
+    pub daily: bool,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        LoggingConfig {
+            console: false,
+            modules: BTreeMap::new(),
+            file: PathBuf::from("log/dss.log"),
+            max_size_kb: 10 * 1024,
+            max_files: 5,
+            daily: false,
+        }
+    }
+}
+
+/// The active logging of the application, which starts a new log file each day if configured to.
+pub struct Logging {
+    handle: Handle,
+    config: LoggingConfig,
+    level: LevelFilter,
+    date: NaiveDate,
+}
+
+impl Logging {
+    /// Starts logging according to the given settings, at the given level for modules without their own level.
+    ///
+    /// # Errors
+    /// Panics if the log file cannot be created or logging has already started.
+    pub fn init(config: &LoggingConfig, level: LevelFilter) -> Self {
+        let date = Local::today().naive_local();
+        let handle =
+            log4rs::init_config(build_config(config, level, date)).expect("Unable to apply logging configuration.");
+        Logging {
+            handle,
+            config: config.clone(),
+            level,
+            date,
+        }
+    }
+
+    /// Starts a new log file if the logs are kept daily and the date has changed since the current file was started.
+    pub fn update(&mut self) {
+        let date = Local::today().naive_local();
+        if self.config.daily && date != self.date {
+            self.date = date;
+            self.handle.set_config(build_config(&self.config, self.level, date));
+        }
+    }
+}
+
+/// Builds the logging configuration for the given settings, at the given level for modules without their own level,
+/// logging to the file of the given date if the logs are kept daily.
+///
+/// # Errors
+/// Panics if the log file cannot be created.
+fn build_config(config: &LoggingConfig, level: LevelFilter, date: NaiveDate) -> Config {
+    let path = log_path(config, date);
+    let log_file: Box<dyn Append> = if config.max_size_kb == 0 {
+        Box::new(
+            FileAppender::builder()
+                .encoder(Box::new(PatternEncoder::new(LOG_PATTERN)))
+                .build(&path)
+                .expect("Unable to create log file appender."),
+        )
+    } else {
+        let roller = FixedWindowRoller::builder()
+            .build(&roll_pattern(&path), config.max_files)
+            .unwrap_or_else(|ex| panic!("Unable to create log roller: {}.", ex));
+        let policy = CompoundPolicy::new(Box::new(SizeTrigger::new(config.max_size_kb * 1024)), Box::new(roller));
+        Box::new(
+            RollingFileAppender::builder()
+                .encoder(Box::new(PatternEncoder::new(LOG_PATTERN)))
+                .build(&path, Box::new(policy))
+                .expect("Unable to create log file appender."),
+        )
+    };
+    let mut builder = Config::builder().appender(Appender::builder().build("log_file", log_file));
+    let mut root = Root::builder().appender("log_file");
+    if config.console {
+        let console = ConsoleAppender::builder()
+            .target(Target::Stderr)
+            .encoder(Box::new(PatternEncoder::new(CONSOLE_PATTERN)))
+            .build();
+        builder = builder.appender(Appender::builder().build("console", Box::new(console)));
+        root = root.appender("console");
+    }
+    for (module, module_level) in &config.modules {
+        match module_level.parse::<LevelFilter>() {
+            Ok(module_level) => builder = builder.logger(Logger::builder().build(module, module_level)),
+            Err(_) => eprintln!("Ignoring invalid log level {} for {}", module_level, module),
+        }
+    }
+    builder
+        .build(root.build(level))
+        .expect("Unable to create log configuration.")
+}
+
+/// Determines the path of the log file currently written to according to the given settings.
+pub fn current_path(config: &LoggingConfig) -> PathBuf {
+    log_path(config, Local::today().naive_local())
+}
+
+/// Determines the path of the log file of the given date: the configured path, with the date appended to its name if
+/// the logs are kept daily.
+fn log_path(config: &LoggingConfig, date: NaiveDate) -> PathBuf {
+    if !config.daily {
+        return config.file.clone();
+    }
+    let stem = config.file.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match config.file.extension() {
+        Some(extension) => format!("{}-{}.{}", stem, date, extension.to_string_lossy()),
+        None => format!("{}-{}", stem, date),
+    };
+    config.file.with_file_name(name)
+}
+
+/// Determines the pattern of the paths to which the log file at the given path is rolled over, numbering them before
+/// the extension (e.g. "dss.1.log").
+fn roll_pattern(path: &Path) -> String {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(extension) => format!("{}.{{}}.{}", stem, extension.to_string_lossy()),
+        None => format!("{}.{{}}", stem),
+    };
+    path.with_file_name(name).to_string_lossy().into_owned()
+}
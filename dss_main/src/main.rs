@@ -2,68 +2,170 @@
 
 //! OpenGL implementation of the DSS UI.
 
+mod access;
+mod audio;
+mod config;
+mod crash;
+mod decode;
+mod diagnostics;
+mod gamepad;
 mod gl_mlb;
 mod gl_utils;
+mod headless;
+mod key_repeat;
+mod keymap;
+mod league;
+mod lirc;
+mod loader;
+mod locale;
+mod logging;
+mod menu;
+mod offscreen;
+mod options;
+mod rail;
+mod remote;
+mod replay;
+mod session;
+mod shaders;
+mod standings;
+mod stories;
+mod theme;
+mod touch;
+mod tween;
+mod video;
 
 #[macro_use]
 extern crate glium;
 
-use gl_mlb::{MlbGlUi, MlbUiInfo};
+use access::Accessibility;
+use audio::AmbientAudio;
+use diagnostics::{Diagnostics, FrameTimer};
+use gamepad::GamepadInput;
+use gl_mlb::{MlbGlUi, MlbUiState};
 use gl_utils::FocusDirection;
-use glium::glutin::event::{ElementState, Event, KeyboardInput, VirtualKeyCode, WindowEvent};
-use glium::glutin::event_loop::{ControlFlow, EventLoop};
+use glium::glutin::dpi::PhysicalSize;
+use glium::glutin::event::{
+    ElementState, Event, KeyboardInput, ModifiersState, MouseButton, MouseScrollDelta, StartCause, VirtualKeyCode,
+    WindowEvent,
+};
+use glium::glutin::event_loop::{ControlFlow, EventLoop, EventLoopProxy, EventLoopWindowTarget};
+use glium::glutin::monitor::MonitorHandle;
 use glium::glutin::window::{Fullscreen, WindowBuilder};
 use glium::glutin::ContextBuilder;
-use glium::{Display, Surface};
-use glyph_brush::ab_glyph::FontArc;
-use log::{error, info};
-use log4rs::append::file::FileAppender;
-use log4rs::config::{Appender, Config, Root};
-use log4rs::encode::pattern::PatternEncoder;
+use glium::{Display, Surface, SwapBuffersError};
+use key_repeat::KeyRepeat;
+use keymap::Action;
+use loader::{LoadRequest, Loader, UiEvent};
+use log::{error, info, warn};
+use logging::Logging;
+use options::Options;
+use remote::{RemoteCommand, RemoteGame, RemoteStatus};
+use session::Session;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use touch::{TouchGesture, TouchTracker};
+
+/// The distance in logical pixels that a touchpad must scroll to be treated as one notch of a scroll wheel.
+const SCROLL_PIXELS_PER_NOTCH: f64 = 100.0;
+/// The number of consecutive days that may fail to be retrieved in kiosk mode before the loader is restarted.
+const KIOSK_FAILURE_LIMIT: u32 = 3;
+/// The time without movement of the cursor after which it is hidden in kiosk mode.
+const KIOSK_CURSOR_TIMEOUT: Duration = Duration::from_secs(3);
+/// The time between frames drawn while the diagnostics overlay is displayed, so that its measurements stay current.
+const DIAGNOSTICS_INTERVAL: Duration = Duration::from_secs(1);
 
 #[tokio::main]
 async fn main() {
+    let options = Options::parse();
+
     // setup logging
-    let log_file = FileAppender::builder()
-        .encoder(Box::new(PatternEncoder::new(
-            "{d(%Y-%m-%d %H:%M:%S)} - {({l}):5.5}{n}    {m}{n}{n}",
-        )))
-        .build("log/dss.log")
-        .expect("Unable to create log file appender.");
-    let config = Config::builder()
-        .appender(Appender::builder().build("log_file", Box::new(log_file)))
-        .build(Root::builder().appender("log_file").build(log::LevelFilter::Info))
-        .expect("Unable to create log configuration.");
-    log4rs::init_config(config).expect("Unable to apply logging configuration.");
+    let mut logging = Logging::init(&options.logging, options.log_level);
+    let crash_dir = options.crash_dir();
+    let crashed = crash::take_pending(&crash_dir);
+    crash::install(crash_dir, options.logging.clone());
 
     info!("starting application");
-
-    // load backing data
-    let mlb_ui_info = MlbUiInfo::init().await;
-    info!("data loaded");
+    if let Some(report) = &crashed {
+        warn!("previous run crashed, report saved to {}", report.display());
+    }
+    if let Some(config_path) = &options.config_path {
+        info!("configuration read from {}", config_path.display());
+    }
+    if options.screenshot.is_some() || options.replay.is_some() {
+        if !headless::run(&options).await {
+            std::process::exit(1);
+        }
+        return;
+    }
 
     // initialize window/display
-    let event_loop = EventLoop::new();
-    let monitor = event_loop.primary_monitor();
-    let wb = WindowBuilder::new()
-        .with_title("JDN DSS Solution")
-        .with_inner_size(monitor.size())
-        .with_fullscreen(Some(Fullscreen::Borderless(monitor)));
-    let cb = ContextBuilder::new();
-    let display = Display::new(wb, cb, &event_loop).unwrap_or_else(|ex| {
-        let msg = "Could not create Display";
-        error!("{}:\n{}", msg, ex);
-        panic!("{}.", msg);
-    });
+    let event_loop = EventLoop::<UiEvent>::with_user_event();
+    let monitor = options
+        .monitor
+        .and_then(|index| event_loop.available_monitors().nth(index))
+        .unwrap_or_else(|| event_loop.primary_monitor());
+    // the window is shown once screen readers can be answered, which some platforms require
+    let wb = WindowBuilder::new().with_title("JDN DSS Solution").with_visible(false);
+    let wb = if options.windowed {
+        wb.with_inner_size(windowed_size(&monitor))
+    } else {
+        wb.with_inner_size(monitor.size())
+            .with_fullscreen(Some(Fullscreen::Borderless(monitor.clone())))
+    };
+    let mut fullscreen = !options.windowed;
+    let mut display = create_display(wb.clone(), &event_loop, &options);
+    let mut accessibility = Accessibility::init(display.gl_window().window());
+    display.gl_window().window().set_visible(true);
     info!("display created");
 
     // initialize individual UIs
-    let mut mlb_gl = MlbGlUi::init(mlb_ui_info, &display);
+    let background = options.theme.load_background().await;
+    let proxy = event_loop.create_proxy();
+    let mut mlb_gl = create_ui(&display, &proxy, &options, background.clone());
+    if let Some(report) = &crashed {
+        mlb_gl.show_crash_notice(report);
+    }
     info!("MLB GUI initialized");
 
+    // resume where the last run left off, as the days it refers to are retrieved
+    let session_path = options.session_path();
+    let session = match &session_path {
+        Some(path) => Session::load(path).unwrap_or_else(|ex| {
+            warn!("Could not load session {}, starting afresh:\n{}", path.display(), ex);
+            Session::default()
+        }),
+        None => Session::default(),
+    };
+    let mut pending_tab = Some(session.tab).filter(|tab| *tab > 0);
+    let mut saved_session = session.clone();
+    mlb_gl.restore_session(session);
+
+    // start retrieving data in the background
+    let mut loader = Loader::spawn(proxy.clone(), &options);
+    loader.request(LoadRequest::Games(options.days));
+    loader.request(LoadRequest::Standings);
+    gamepad::spawn(event_loop.create_proxy());
+    if options.lirc.enabled {
+        lirc::spawn(event_loop.create_proxy(), &options.lirc, &options.keymap);
+    }
+    if cfg!(debug_assertions) {
+        shaders::watch(event_loop.create_proxy());
+    }
+    let remote_status = Arc::new(Mutex::new(RemoteStatus::default()));
+    if let Some(port) = options.remote_port {
+        remote::spawn(event_loop.create_proxy(), port, Arc::clone(&remote_status));
+    }
+    let mut ambient_audio = options.ambient_audio.as_ref().and_then(|url| {
+        AmbientAudio::start(url, options.ambient_volume)
+            .map_err(|ex| error!("Could not play ambient audio {}:\n{}", url, ex))
+            .ok()
+    });
+
     // first pass before event loop
+    let [red, green, blue, alpha] = options.theme.clear_color;
     let mut target = display.draw();
-    target.clear_color(0.0, 0.0, 0.0, 0.0);
+    target.clear_color(red, green, blue, alpha);
     mlb_gl.draw(&display, &mut target, None);
     target.finish().unwrap_or_else(|ex| {
         let msg = "Target could not finish initial pass";
@@ -73,21 +175,162 @@ async fn main() {
     info!("first pass drawn");
 
     // load text brush after first pass to prevent black screen
-    let font = FontArc::try_from_slice(include_bytes!("tahoma.ttf")).unwrap_or_else(|ex| {
-        let msg = "Could not load font";
-        error!("{}:\n{}", msg, ex);
-        panic!("{}.", msg);
-    });
-    info!("font loaded");
-    let mut text_brush = gl_utils::GlyphBrush::build(font, &display);
+    let fonts = options.theme.load_fonts();
+    info!("fonts loaded");
+    let mut text_brush = gl_utils::GlyphBrush::build(fonts, &display)
+        .with_effect(options.theme.text_effect, options.theme.text_effect_color);
     info!("text brush built");
 
-    event_loop.run(move |event, _, control_flow| {
-        *control_flow = ControlFlow::Wait;
+    let mut cursor = (0.0, 0.0);
+    let mut scroll_pixels = 0.0;
+    let mut modifiers = ModifiersState::empty();
+    let mut key_repeat = KeyRepeat::new();
+    let mut touches = TouchTracker::new();
+    // whether the displayed state has changed since the last frame was drawn
+    let mut dirty = true;
+    let mut last_frame = Instant::now();
+    let mut frame_timer = FrameTimer::new();
+    let mut show_diagnostics = false;
+    let mut consecutive_failures = 0;
+    // in kiosk mode, the cursor is displayed only while it is moving
+    let mut cursor_moved_at = None;
+    if options.kiosk {
+        display.gl_window().window().set_cursor_visible(false);
+        info!("kiosk mode enabled");
+    }
+    event_loop.run(move |event, window_target, control_flow| {
+        match &event {
+            Event::WindowEvent {
+                event:
+                    WindowEvent::CursorMoved { .. }
+                    | WindowEvent::CursorEntered { .. }
+                    | WindowEvent::CursorLeft { .. }
+                    | WindowEvent::AxisMotion { .. }
+                    | WindowEvent::ModifiersChanged(_)
+                    | WindowEvent::Moved(_),
+                ..
+            } => (),
+            Event::WindowEvent { .. }
+            | Event::UserEvent(_)
+            | Event::NewEvents(StartCause::ResumeTimeReached { .. }) => dirty = true,
+            _ => (),
+        }
+
+        // the buttons of infrared remotes are pressed and released at once, as remotes repeat buttons while held
+        let key_actions = match &event {
+            Event::WindowEvent {
+                event: WindowEvent::KeyboardInput { input, .. },
+                ..
+            } if !mlb_gl.is_searching() => vec![(options.keymap.action(input), input.state)],
+            Event::UserEvent(UiEvent::Lirc(action)) => vec![
+                (Some(*action), ElementState::Pressed),
+                (Some(*action), ElementState::Released),
+            ],
+            _ => Vec::new(),
+        };
+        for key_action in key_actions {
+            match key_action {
+                (Some(Action::Move(direction)), state) => move_held(&mut mlb_gl, &mut key_repeat, direction, state),
+                (Some(Action::Select), ElementState::Released) => select(&mut mlb_gl, &loader),
+                (Some(Action::Back), ElementState::Released) => {
+                    if !mlb_gl.is_top_level() {
+                        mlb_gl.back();
+                    } else if !options.kiosk {
+                        *control_flow = ControlFlow::Exit;
+                    }
+                }
+                (Some(Action::PageBack), ElementState::Released) => mlb_gl.page(if modifiers.ctrl() {
+                    FocusDirection::Up
+                } else {
+                    FocusDirection::Left
+                }),
+                (Some(Action::PageForward), ElementState::Released) => mlb_gl.page(if modifiers.ctrl() {
+                    FocusDirection::Down
+                } else {
+                    FocusDirection::Right
+                }),
+                (Some(Action::Quit), ElementState::Released) if !options.kiosk => *control_flow = ControlFlow::Exit,
+                (Some(Action::Refresh), ElementState::Released) => {
+                    let dates = mlb_gl.retry();
+                    if dates.is_empty() {
+                        loader.request(LoadRequest::Refresh);
+                    } else {
+                        loader.request(LoadRequest::Days(dates));
+                    }
+                    loader.request(LoadRequest::Standings);
+                }
+                (Some(Action::Fullscreen), ElementState::Released) => {
+                    fullscreen = !fullscreen;
+                    set_fullscreen(&display, &monitor, fullscreen);
+                }
+                (Some(Action::NextTab), ElementState::Released) => {
+                    let retrieve_leagues = mlb_gl.next_tab();
+                    if retrieve_leagues {
+                        loader.request(LoadRequest::Leagues);
+                    }
+                }
+                (Some(Action::SelectTab(tab)), ElementState::Released) => {
+                    let retrieve_leagues = mlb_gl.select_tab(tab);
+                    if retrieve_leagues {
+                        loader.request(LoadRequest::Leagues);
+                    }
+                }
+                (Some(Action::JumpToDate), ElementState::Released) => mlb_gl.open_date_picker(),
+                (Some(Action::Collapse), ElementState::Released) => mlb_gl.toggle_collapsed(),
+                (Some(Action::ToggleAudio), ElementState::Released) => {
+                    if let Some(audio) = &ambient_audio {
+                        audio.toggle_pause();
+                    }
+                }
+                (Some(Action::VolumeUp), ElementState::Pressed) => {
+                    if let Some(audio) = &ambient_audio {
+                        info!("ambient volume raised to {:.0}%", audio.step_volume(true) * 100.0);
+                    }
+                }
+                (Some(Action::VolumeDown), ElementState::Pressed) => {
+                    if let Some(audio) = &ambient_audio {
+                        info!("ambient volume lowered to {:.0}%", audio.step_volume(false) * 100.0);
+                    }
+                }
+                (Some(Action::Diagnostics), ElementState::Released) => {
+                    show_diagnostics = !show_diagnostics;
+                    if !show_diagnostics {
+                        mlb_gl.set_diagnostics(None);
+                    }
+                }
+                _ => (),
+            }
+        }
 
-        if let Event::WindowEvent { event, .. } = event {
-            match event {
+        match event {
+            Event::WindowEvent { event, .. } => match event {
                 WindowEvent::CloseRequested => *control_flow = ControlFlow::Exit,
+                WindowEvent::Resized(size) => {
+                    display.gl_window().resize(size);
+                    info!("display resized to {}x{}", size.width, size.height);
+                }
+                WindowEvent::ScaleFactorChanged {
+                    scale_factor,
+                    new_inner_size,
+                } => {
+                    display.gl_window().resize(*new_inner_size);
+                    mlb_gl.set_scale_factor(scale_factor);
+                    info!("scale factor changed to {}", scale_factor);
+                }
+                WindowEvent::ModifiersChanged(state) => modifiers = state,
+                WindowEvent::Focused(focused) => {
+                    if !focused {
+                        key_repeat.release_all();
+                    }
+                    accessibility.set_window_focused(focused);
+                }
+                WindowEvent::ReceivedCharacter(c) => {
+                    if mlb_gl.is_searching() {
+                        mlb_gl.type_search(c);
+                    } else if c == '/' {
+                        mlb_gl.start_search();
+                    }
+                }
                 WindowEvent::KeyboardInput {
                     input:
                         KeyboardInput {
@@ -96,24 +339,323 @@ async fn main() {
                             ..
                         },
                     ..
-                } => match (virtual_code, state) {
-                    (VirtualKeyCode::Left, ElementState::Released) => mlb_gl.move_focus(FocusDirection::Left),
-                    (VirtualKeyCode::Right, ElementState::Released) => mlb_gl.move_focus(FocusDirection::Right),
-                    (VirtualKeyCode::Up, ElementState::Released) => mlb_gl.move_focus(FocusDirection::Up),
-                    (VirtualKeyCode::Down, ElementState::Released) => mlb_gl.move_focus(FocusDirection::Down),
+                } if mlb_gl.is_searching() => match (virtual_code, state) {
+                    (VirtualKeyCode::Escape, ElementState::Released) => mlb_gl.clear_search(),
+                    (VirtualKeyCode::Back, ElementState::Pressed) => mlb_gl.erase_search(),
+                    (VirtualKeyCode::Return, ElementState::Released) => mlb_gl.finish_search(),
                     _ => (),
                 },
+                WindowEvent::CursorMoved { position, .. } => {
+                    if options.kiosk {
+                        if cursor_moved_at.is_none() {
+                            display.gl_window().window().set_cursor_visible(true);
+                        }
+                        cursor_moved_at = Some(Instant::now());
+                    }
+                    cursor = mlb_gl.content_point(position.x as f32, position.y as f32);
+                    dirty |= mlb_gl.hover(cursor.0, cursor.1);
+                }
+                WindowEvent::MouseInput {
+                    state: ElementState::Released,
+                    button,
+                    ..
+                } => match button {
+                    MouseButton::Left => {
+                        if let Some(game_pk) = mlb_gl.click(cursor.0, cursor.1) {
+                            loader.request(LoadRequest::GameDetail(game_pk));
+                        }
+                    }
+                    MouseButton::Right => mlb_gl.back(),
+                    _ => (),
+                },
+                WindowEvent::Touch(touch) => {
+                    let (width, height) = display.get_framebuffer_dimensions();
+                    let (width, height) = (width as f32, height as f32);
+                    match touches.update(&touch, width, height) {
+                        Some(TouchGesture::Tap(x, y)) => {
+                            let (x, y) = mlb_gl.content_point(x * width, y * height);
+                            if let Some(game_pk) = mlb_gl.tap(x, y) {
+                                loader.request(LoadRequest::GameDetail(game_pk));
+                            }
+                        }
+                        Some(TouchGesture::Swipe(x, y, direction)) => {
+                            let (_, y) = mlb_gl.content_point(x * width, y * height);
+                            mlb_gl.swipe(y, direction)
+                        }
+                        None => (),
+                    }
+                }
+                WindowEvent::MouseWheel { delta, .. } => {
+                    let notches = match delta {
+                        MouseScrollDelta::LineDelta(_, lines) => lines.round() as i32,
+                        MouseScrollDelta::PixelDelta(position) => {
+                            scroll_pixels += position.y;
+                            let notches = (scroll_pixels / SCROLL_PIXELS_PER_NOTCH).trunc();
+                            scroll_pixels -= notches * SCROLL_PIXELS_PER_NOTCH;
+                            notches as i32
+                        }
+                    };
+                    if notches != 0 {
+                        mlb_gl.scroll(cursor.1, notches);
+                    }
+                }
                 _ => (),
+            },
+            Event::UserEvent(UiEvent::Gamepad(input)) => match input {
+                GamepadInput::Move(direction) => mlb_gl.move_focus(direction),
+                GamepadInput::Select => select(&mut mlb_gl, &loader),
+                GamepadInput::Back => mlb_gl.back(),
+            },
+            Event::UserEvent(UiEvent::Remote(command)) => match command {
+                RemoteCommand::Move(direction) => mlb_gl.move_focus(direction),
+                RemoteCommand::Select => select(&mut mlb_gl, &loader),
+                RemoteCommand::Back => mlb_gl.back(),
+                RemoteCommand::PageBack => mlb_gl.page(FocusDirection::Left),
+                RemoteCommand::PageForward => mlb_gl.page(FocusDirection::Right),
+            },
+            Event::UserEvent(UiEvent::DaysPending(dates)) => {
+                mlb_gl.set_pending_days(dates);
+                if matches!(pending_tab.take(), Some(tab) if mlb_gl.select_tab(tab)) {
+                    loader.request(LoadRequest::Leagues);
+                }
+            }
+            Event::UserEvent(UiEvent::LoadProgress(progress)) => mlb_gl.set_progress(progress),
+            Event::UserEvent(UiEvent::DayLoaded(date, games)) => {
+                info!("data loaded for {}", date);
+                consecutive_failures = 0;
+                mlb_gl.set_day(date, games, &options);
+            }
+            Event::UserEvent(UiEvent::DayFailed(date)) => {
+                info!("data failed to load for {}", date);
+                mlb_gl.set_day_failed(date);
+                consecutive_failures += 1;
+                if options.kiosk && consecutive_failures >= KIOSK_FAILURE_LIMIT {
+                    warn!(
+                        "{} days failed to load in a row, restarting the loader",
+                        consecutive_failures
+                    );
+                    consecutive_failures = 0;
+                    loader.shutdown();
+                    loader = Loader::spawn(proxy.clone(), &options);
+                    loader.request(LoadRequest::Days(mlb_gl.retry()));
+                    loader.request(LoadRequest::Standings);
+                }
+            }
+            Event::UserEvent(UiEvent::GameDetailLoaded(game_pk, detail)) => mlb_gl.set_detail(game_pk, detail),
+            Event::UserEvent(UiEvent::ImageLoaded(game_pk, image)) => mlb_gl.set_image(game_pk, image),
+            Event::UserEvent(UiEvent::StandingsLoaded(Some(standings))) => mlb_gl.set_standings(standings),
+            Event::UserEvent(UiEvent::LeaguesLoaded(result)) => mlb_gl.set_leagues(result),
+            Event::UserEvent(UiEvent::VideoFinished(id)) => mlb_gl.finish_video(id),
+            Event::UserEvent(UiEvent::ShadersChanged) => {
+                mlb_gl.reload_shaders(&display);
+                text_brush.reload_shaders(&display);
             }
+            Event::MainEventsCleared => {
+                if let Some(direction) = key_repeat.due(Instant::now()) {
+                    mlb_gl.move_focus(direction);
+                    dirty = true;
+                }
+                let frame_allowed = match options.frame_interval {
+                    Some(interval) => Instant::now() >= last_frame + interval,
+                    None => true,
+                };
+                if dirty && frame_allowed {
+                    display.gl_window().window().request_redraw();
+                    dirty = false;
+                }
+            }
+            Event::RedrawRequested(_) => {
+                last_frame = Instant::now();
+                if show_diagnostics {
+                    mlb_gl.set_diagnostics(Some(Diagnostics {
+                        frame_time: frame_timer.frame_time(),
+                        fps: frame_timer.fps(),
+                        loader: loader.stats(),
+                    }));
+                }
+                let mut target = display.draw();
+                target.clear_color(red, green, blue, alpha);
+                mlb_gl.draw(&display, &mut target, Some(&mut text_brush));
+                match target.finish() {
+                    Ok(()) => frame_timer.record(last_frame),
+                    Err(SwapBuffersError::ContextLost) if options.kiosk => {
+                        // the UI and its textures belong to the lost context, so both are created anew and the
+                        // retrieved data is requested again, resuming at the same focus
+                        error!("Display context lost, recreating the display");
+                        let session = mlb_gl.session();
+                        mlb_gl.shutdown();
+                        display = create_display(wb.clone(), window_target, &options);
+                        accessibility = Accessibility::init(display.gl_window().window());
+                        display.gl_window().window().set_visible(true);
+                        display.gl_window().window().set_cursor_visible(false);
+                        cursor_moved_at = None;
+                        fullscreen = !options.windowed;
+                        mlb_gl = create_ui(&display, &proxy, &options, background.clone());
+                        pending_tab = Some(session.tab).filter(|tab| *tab > 0);
+                        mlb_gl.restore_session(session);
+                        text_brush = gl_utils::GlyphBrush::build(options.theme.load_fonts(), &display)
+                            .with_effect(options.theme.text_effect, options.theme.text_effect_color);
+                        loader.request(LoadRequest::Games(options.days));
+                        loader.request(LoadRequest::Standings);
+                        dirty = true;
+                        return;
+                    }
+                    Err(ex) => {
+                        let msg = "Target could not finish";
+                        error!("{}:\n{}", msg, ex);
+                        panic!("{}.", msg);
+                    }
+                }
+                accessibility.update(mlb_gl.access_tree());
+                let days = mlb_gl.take_day_requests();
+                if !days.is_empty() {
+                    loader.request(LoadRequest::Days(days));
+                }
+                let images = mlb_gl.take_image_requests();
+                if !images.is_empty() {
+                    loader.request(LoadRequest::Images(images, mlb_gl.image_size()));
+                }
+                save_session(session_path.as_deref(), mlb_gl.session(), &mut saved_session);
+                if options.remote_port.is_some() {
+                    *remote_status.lock().unwrap() = RemoteStatus {
+                        screen: mlb_gl.focus_state().screen.to_owned(),
+                        game: mlb_gl.focused_game().map(RemoteGame::from),
+                    };
+                }
+            }
+            Event::RedrawEventsCleared if *control_flow != ControlFlow::Exit => {
+                logging.update();
+                let mut next_redraw = mlb_gl.next_redraw();
+                if let (true, Some(interval)) = (dirty, options.frame_interval) {
+                    // a change arrived too soon after the last frame, so it is drawn once the frame rate allows
+                    next_redraw = next_redraw.min(last_frame + interval);
+                }
+                if show_diagnostics {
+                    next_redraw = next_redraw.min(last_frame + DIAGNOSTICS_INTERVAL);
+                }
+                let mut next_redraw = key_repeat
+                    .next_repeat()
+                    .map_or(next_redraw, |next| next.min(next_redraw));
+                if let Some(moved_at) = cursor_moved_at {
+                    let hide_at = moved_at + KIOSK_CURSOR_TIMEOUT;
+                    if Instant::now() >= hide_at {
+                        display.gl_window().window().set_cursor_visible(false);
+                        cursor_moved_at = None;
+                    } else {
+                        next_redraw = next_redraw.min(hide_at);
+                    }
+                }
+                *control_flow = ControlFlow::WaitUntil(next_redraw);
+            }
+            Event::LoopDestroyed => {
+                save_session(session_path.as_deref(), mlb_gl.session(), &mut saved_session);
+                mlb_gl.shutdown();
+                loader.shutdown();
+                ambient_audio = None;
+                info!("ending application");
+            }
+            _ => (),
         }
-        let mut target = display.draw();
-        target.clear_color(0.0, 0.0, 0.0, 0.0);
-        mlb_gl.draw(&display, &mut target, Some(&mut text_brush));
-
-        target.finish().unwrap_or_else(|ex| {
-            let msg = "Target could not finish";
-            error!("{}:\n{}", msg, ex);
-            panic!("{}.", msg);
-        });
     });
 }
+
+/// Saves the given session to the given path, if any, when it differs from the last session saved.
+fn save_session(path: Option<&Path>, session: Session, saved: &mut Session) {
+    let path = match path {
+        Some(path) if session != *saved => path,
+        _ => return,
+    };
+    if let Err(ex) = session.save(path) {
+        error!("Could not save session {}:\n{}", path.display(), ex);
+    }
+    *saved = session;
+}
+
+/// Selects the focused item of the given UI, or goes to the day chosen in its date picker, requesting what needs to be
+/// retrieved from the given loader.
+fn select(mlb_gl: &mut MlbGlUi, loader: &Loader) {
+    if mlb_gl.is_picking_date() {
+        if let Some(date) = mlb_gl.pick_date() {
+            loader.request(LoadRequest::Days(vec![date]));
+        }
+    } else if let Some(game_pk) = mlb_gl.select() {
+        loader.request(LoadRequest::GameDetail(game_pk));
+    }
+}
+
+/// Moves the focus of the given UI in the given direction when its key is pressed, repeating while it is held.
+fn move_held(mlb_gl: &mut MlbGlUi, key_repeat: &mut KeyRepeat, direction: FocusDirection, state: ElementState) {
+    match state {
+        ElementState::Pressed => {
+            if key_repeat.press(direction, Instant::now()) {
+                mlb_gl.move_focus(direction);
+            }
+        }
+        ElementState::Released => key_repeat.release(direction),
+    }
+}
+
+/// Creates the window and its context according to the given options. If the configured multisampling is not
+/// supported, the display is created without antialiasing instead.
+///
+/// # Errors
+/// Panics if the display cannot be created even without multisampling.
+fn create_display(wb: WindowBuilder, event_loop: &EventLoopWindowTarget<UiEvent>, options: &Options) -> Display {
+    // textures and colors are blended in linear space, which the framebuffer encodes as sRGB
+    let cb = ContextBuilder::new().with_vsync(options.vsync).with_srgb(true);
+    if options.multisampling > 0 {
+        match Display::new(
+            wb.clone(),
+            cb.clone().with_multisampling(options.multisampling),
+            event_loop,
+        ) {
+            Ok(display) => return display,
+            Err(ex) => warn!(
+                "Could not create display with {}x multisampling, continuing without antialiasing:\n{}",
+                options.multisampling, ex
+            ),
+        }
+    }
+    Display::new(wb, cb, event_loop).unwrap_or_else(|ex| {
+        let msg = "Could not create Display";
+        error!("{}:\n{}", msg, ex);
+        panic!("{}.", msg);
+    })
+}
+
+/// Creates the MLB UI on the given display according to the given options, notifying the event loop of the given proxy.
+/// The background is decoded from the given bytes, or the embedded background if None.
+fn create_ui(
+    display: &Display,
+    proxy: &EventLoopProxy<UiEvent>,
+    options: &Options,
+    background: Option<Vec<u8>>,
+) -> MlbGlUi {
+    let strings = options.load_strings();
+    let settings = options.settings(&strings);
+    let mut state = MlbUiState::new(Some(proxy.clone()), options.theme.clone(), strings);
+    state.set_scale_factor(display.gl_window().window().scale_factor());
+    state.set_settings(settings);
+    state.set_frame_interval(options.frame_interval);
+    state.set_content_area(options.content_aspect, options.safe_area);
+    MlbGlUi::init(display, state, background)
+}
+
+/// Determines the size of the window when not fullscreen, which covers most of the given monitor.
+fn windowed_size(monitor: &MonitorHandle) -> PhysicalSize<u32> {
+    let size = monitor.size();
+    PhysicalSize::new(size.width * 3 / 4, size.height * 3 / 4)
+}
+
+/// Switches the window of the given display between borderless fullscreen on the given monitor and a resizable window.
+fn set_fullscreen(display: &Display, monitor: &MonitorHandle, fullscreen: bool) {
+    let gl_window = display.gl_window();
+    let window = gl_window.window();
+    if fullscreen {
+        window.set_fullscreen(Some(Fullscreen::Borderless(monitor.clone())));
+    } else {
+        window.set_fullscreen(None);
+        window.set_inner_size(windowed_size(monitor));
+    }
+    info!("fullscreen set to {}", fullscreen);
+}
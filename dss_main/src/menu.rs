@@ -0,0 +1,138 @@
+//! The menu strip at the top of the screen, from which each section of the application is opened, and the text of
+//! the sections that are not drawn elsewhere.
+
+use crate::gl_utils::{Bounds, Focusable, GlyphBrush, TextStyle};
+use crate::locale::Strings;
+use crate::rail::{text_scale, LEFT_INDENT};
+use crate::theme::{Color, Theme};
+use glyph_brush::{Section, Text};
+
+/// The percentage from the top of the screen at which to display the menu.
+pub const MENU_TOP: f32 = 0.05;
+/// The percentage from the top of the screen at which to start displaying the content of a section.
+pub const SECTION_TOP: f32 = 0.2;
+/// The nominal size in pixels of the horizontal spacing between the labels of a strip.
+const STRIP_GAP: f32 = 48.0;
+/// The percentage of the screen taken up by the name column of the settings.
+const SETTINGS_NAME_WIDTH: f32 = 0.25;
+/// The percentage of the screen taken up by each row of the settings.
+const SETTINGS_ROW_HEIGHT: f32 = 0.05;
+
+/// An enumeration of the sections that can be opened from the menu.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MenuSection {
+    /// The grid of rails.
+    Home,
+    /// The scoreboard of today's games.
+    Scores,
+    /// The full standings of every division.
+    Standings,
+    /// The settings the application is running with.
+    Settings,
+}
+
+impl MenuSection {
+    /// Every section, in the order displayed in the menu.
+    pub const ALL: [MenuSection; 4] = [
+        MenuSection::Home,
+        MenuSection::Scores,
+        MenuSection::Standings,
+        MenuSection::Settings,
+    ];
+
+    /// Determines the identifier of the message describing the section in the menu.
+    pub fn label_id(self) -> &'static str {
+        match self {
+            MenuSection::Home => "menu-home",
+            MenuSection::Scores => "menu-scores",
+            MenuSection::Standings => "menu-standings",
+            MenuSection::Settings => "menu-settings",
+        }
+    }
+}
+
+/// Lays out every section side by side, in the order displayed in the menu, for moving the focus between them.
+pub fn focusables() -> Vec<Focusable<MenuSection>> {
+    MenuSection::ALL
+        .iter()
+        .enumerate()
+        .map(|(index, section)| Focusable {
+            key: *section,
+            bounds: Bounds::new(index as f32, 0.0, 1.0, 1.0),
+        })
+        .collect()
+}
+
+/// Queues the given labels side by side in the given colors, starting at the given position in pixels, at the given
+/// nominal size with the given ratio of displayed to nominal text size.
+pub fn queue_strip<S: AsRef<str>>(
+    text_brush: &mut GlyphBrush,
+    labels: impl Iterator<Item = (S, Color)>,
+    position: (f32, f32),
+    size: f32,
+    text_ratio: f32,
+) {
+    let scale = text_scale(size, text_ratio);
+    let gap = text_scale(STRIP_GAP, text_ratio);
+    let mut left = position.0;
+    for (label, color) in labels {
+        let section = Section {
+            text: vec![Text::new(label.as_ref()).with_color(color).with_scale(scale)],
+            screen_position: (left, position.1),
+            bounds: (f32::INFINITY, f32::INFINITY),
+            ..Section::default()
+        };
+        if let Some(bounds) = text_brush.glyph_bounds(&section) {
+            left = bounds.max.x + gap;
+        }
+        text_brush.queue(section);
+    }
+}
+
+/// Queues the given settings, each a name and its value, as a table in the colors of the given theme and the text of
+/// the given catalog.
+pub fn queue_settings(
+    text_brush: &mut GlyphBrush,
+    theme: &Theme,
+    strings: &Strings,
+    settings: &[(String, String)],
+    screen_width: f32,
+    screen_height: f32,
+    text_ratio: f32,
+) {
+    let scale = text_scale(32.0, text_ratio);
+    let value_left = LEFT_INDENT + SETTINGS_NAME_WIDTH;
+    let mut top = SECTION_TOP;
+    for (name, value) in settings {
+        let name = text_brush
+            .bounded_section(
+                name,
+                TextStyle::new(theme.secondary_text_color, scale),
+                SETTINGS_NAME_WIDTH * screen_width,
+                1,
+            )
+            .with_screen_position((LEFT_INDENT * screen_width, top * screen_height));
+        let value = text_brush
+            .bounded_section(
+                value,
+                TextStyle::new(theme.text_color, scale),
+                (1.0 - value_left - LEFT_INDENT) * screen_width,
+                1,
+            )
+            .with_screen_position((value_left * screen_width, top * screen_height));
+        text_brush.queue(name.to_borrowed());
+        text_brush.queue(value.to_borrowed());
+        top += SETTINGS_ROW_HEIGHT;
+    }
+    text_brush.queue(Section {
+        text: vec![Text::new(&strings.get("settings-hint"))
+            .with_color(theme.secondary_text_color)
+            .with_scale(text_scale(24.0, text_ratio))],
+        screen_position: (LEFT_INDENT * screen_width, (top + SETTINGS_ROW_HEIGHT) * screen_height),
+        bounds: (
+            (1.0 - 2.0 * LEFT_INDENT) * screen_width,
+            SETTINGS_ROW_HEIGHT * screen_height,
+        ),
+        ..Section::default()
+    });
+}
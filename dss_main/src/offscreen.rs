@@ -0,0 +1,182 @@
+//! OpenGL contexts that render offscreen, without a window or a display server. On Linux the context is provided by
+//! Mesa through EGL on its surfaceless platform, which renders in software where there is no GPU. The EGL library is
+//! loaded when a context is created, so that the application runs without it.
+
+use glium::backend::{Context, Facade};
+use glium::Frame;
+use std::ops::Deref;
+use std::rc::Rc;
+
+/// A container for an OpenGL context that draws frames of a fixed size offscreen.
+pub struct OffscreenRenderer {
+    context: Rc<Context>,
+}
+
+impl OffscreenRenderer {
+    /// Creates a context that draws frames of the given size in pixels offscreen.
+    ///
+    /// # Errors
+    /// Returns an error if the EGL library cannot be loaded, or Mesa's surfaceless platform or an OpenGL context
+    /// cannot be initialized.
+    #[cfg(target_os = "linux")]
+    pub fn new(size: (u32, u32)) -> Result<Self, String> {
+        let backend = egl_backend::EglBackend::new(size)?;
+        let context = unsafe { Context::new(backend, true, glium::debug::DebugCallbackBehavior::Ignore) }
+            .map_err(|ex| ex.to_string())?;
+        Ok(OffscreenRenderer { context })
+    }
+
+    /// Creates a context that draws frames of the given size in pixels offscreen, which requires Mesa's surfaceless
+    /// platform and is only supported on Linux.
+    ///
+    /// # Errors
+    /// Always returns an error.
+    #[cfg(not(target_os = "linux"))]
+    pub fn new(_size: (u32, u32)) -> Result<Self, String> {
+        Err("offscreen rendering requires Mesa's surfaceless EGL platform, which is only available on Linux".to_owned())
+    }
+
+    /// Starts drawing a frame, which is read back with `read_front_buffer` once it is finished.
+    pub fn draw(&self) -> Frame {
+        Frame::new(self.context.clone(), self.context.get_framebuffer_dimensions())
+    }
+}
+
+impl Deref for OffscreenRenderer {
+    type Target = Context;
+
+    fn deref(&self) -> &Context {
+        &self.context
+    }
+}
+
+impl Facade for OffscreenRenderer {
+    fn get_context(&self) -> &Rc<Context> {
+        &self.context
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod egl_backend {
+    use glium::backend::Backend;
+    use glium::SwapBuffersError;
+    use khronos_egl as egl;
+    use log::error;
+    use std::os::raw::c_void;
+    use std::ptr;
+
+    /// The platform of Mesa's EGL implementation that needs no display server, from `EGL_MESA_platform_surfaceless`.
+    const PLATFORM_SURFACELESS_MESA: egl::Enum = 0x31DD;
+
+    /// A container for an EGL context and the pixel buffer surface it draws to.
+    pub struct EglBackend {
+        egl: egl::DynamicInstance<egl::EGL1_5>,
+        display: egl::Display,
+        surface: egl::Surface,
+        context: egl::Context,
+        size: (u32, u32),
+    }
+
+    impl EglBackend {
+        /// Creates a current context drawing to a pixel buffer of the given size in pixels.
+        ///
+        /// # Errors
+        /// Returns an error if the EGL library cannot be loaded, or any step of creating the context fails.
+        pub fn new(size: (u32, u32)) -> Result<Self, String> {
+            let egl = unsafe { egl::DynamicInstance::<egl::EGL1_5>::load_required() }
+                .map_err(|ex| format!("Could not load EGL: {}", ex))?;
+            let display = egl
+                .get_platform_display(PLATFORM_SURFACELESS_MESA, egl::DEFAULT_DISPLAY, &[egl::ATTRIB_NONE])
+                .map_err(|ex| format!("Could not open surfaceless display: {}", ex))?;
+            egl.initialize(display)
+                .map_err(|ex| format!("Could not initialize EGL: {}", ex))?;
+            egl.bind_api(egl::OPENGL_API)
+                .map_err(|ex| format!("Could not bind OpenGL: {}", ex))?;
+            let config_attributes = [
+                egl::SURFACE_TYPE,
+                egl::PBUFFER_BIT,
+                egl::RENDERABLE_TYPE,
+                egl::OPENGL_BIT,
+                egl::RED_SIZE,
+                8,
+                egl::GREEN_SIZE,
+                8,
+                egl::BLUE_SIZE,
+                8,
+                egl::ALPHA_SIZE,
+                8,
+                egl::DEPTH_SIZE,
+                24,
+                egl::STENCIL_SIZE,
+                8,
+                egl::NONE,
+            ];
+            let config = egl
+                .choose_first_config(display, &config_attributes)
+                .map_err(|ex| format!("Could not choose EGL config: {}", ex))?
+                .ok_or_else(|| "No EGL config supports offscreen OpenGL".to_owned())?;
+            let surface_attributes = [
+                egl::WIDTH,
+                size.0 as egl::Int,
+                egl::HEIGHT,
+                size.1 as egl::Int,
+                egl::NONE,
+            ];
+            let surface = egl
+                .create_pbuffer_surface(display, config, &surface_attributes)
+                .map_err(|ex| format!("Could not create pixel buffer: {}", ex))?;
+            let context = egl
+                .create_context(display, config, None, &[egl::NONE])
+                .map_err(|ex| format!("Could not create EGL context: {}", ex))?;
+            egl.make_current(display, Some(surface), Some(surface), Some(context))
+                .map_err(|ex| format!("Could not make EGL context current: {}", ex))?;
+            Ok(EglBackend {
+                egl,
+                display,
+                surface,
+                context,
+                size,
+            })
+        }
+    }
+
+    unsafe impl Backend for EglBackend {
+        /// Does nothing, as frames are read from the pixel buffer they are drawn to.
+        fn swap_buffers(&self) -> Result<(), SwapBuffersError> {
+            Ok(())
+        }
+
+        unsafe fn get_proc_address(&self, symbol: &str) -> *const c_void {
+            self.egl
+                .get_proc_address(symbol)
+                .map_or(ptr::null(), |address| address as *const c_void)
+        }
+
+        fn get_framebuffer_dimensions(&self) -> (u32, u32) {
+            self.size
+        }
+
+        fn is_current(&self) -> bool {
+            self.egl.get_current_context() == Some(self.context)
+        }
+
+        unsafe fn make_current(&self) {
+            if let Err(ex) =
+                self.egl
+                    .make_current(self.display, Some(self.surface), Some(self.surface), Some(self.context))
+            {
+                error!("Could not make EGL context current:\n{}", ex);
+            }
+        }
+    }
+
+    impl Drop for EglBackend {
+        /// Releases the context and its surface, but leaves the display initialized, as it is shared by the contexts of
+        /// every thread.
+        fn drop(&mut self) {
+            let _ = self.egl.make_current(self.display, None, None, None);
+            let _ = self.egl.destroy_surface(self.display, self.surface);
+            let _ = self.egl.destroy_context(self.display, self.context);
+        }
+    }
+}
@@ -0,0 +1,399 @@
+//! Runtime options of the application, read from the configuration file and overridden by the command line.
+
+use crate::config::Config;
+use crate::headless::Scene;
+use crate::keymap::Keymap;
+use crate::lirc::LircConfig;
+use crate::locale::Strings;
+use crate::logging::LoggingConfig;
+use crate::rail::REFERENCE_ASPECT;
+use crate::theme::Theme;
+use clap::{App, Arg};
+use dss_mlb::CachePolicy;
+use log::LevelFilter;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use unic_langid::LanguageIdentifier;
+
+/// The largest percentage of each dimension of the window that can be reserved as a safe area.
+const MAX_SAFE_AREA: f32 = 25.0;
+/// The name of the file, beside the configuration file, to which the session is saved between runs.
+const SESSION_FILE_NAME: &str = "session.toml";
+/// The name of the directory, beside the log file, to which crash reports are written.
+const CRASH_DIR_NAME: &str = "crash";
+
+/// A container for the runtime options of the application.
+#[derive(Clone, Debug)]
+pub struct Options {
+    /// The number of days of games to display, ending with today.
+    pub days: u32,
+    /// Whether to display in a window rather than fullscreen.
+    pub windowed: bool,
+    /// Whether to run unattended, recovering from failures rather than exiting.
+    pub kiosk: bool,
+    /// The index of the monitor on which to display, or None for the primary monitor.
+    pub monitor: Option<usize>,
+    /// The level at which messages are logged.
+    pub log_level: LevelFilter,
+    /// The destinations of logged messages and the rolling over of the log file.
+    pub logging: LoggingConfig,
+    /// The directory to which retrieved data is persisted, or None to only cache in memory.
+    pub cache_dir: Option<PathBuf>,
+    /// How long each class of retrieved day is cached.
+    pub cache_policy: CachePolicy,
+    /// The names of teams whose games are highlighted.
+    pub favorite_teams: Vec<String>,
+    /// The time between refreshes of today's games, or None to never refresh.
+    pub refresh_interval: Option<Duration>,
+    /// Whether to synchronize frames with the refresh rate of the display.
+    pub vsync: bool,
+    /// The number of samples per pixel with which edges are antialiased, or 0 to not antialias.
+    pub multisampling: u16,
+    /// The shortest time between frames, or None to not limit the frame rate.
+    pub frame_interval: Option<Duration>,
+    /// The aspect ratio of the area in which content is laid out, or None to lay out content across the window.
+    pub content_aspect: Option<f32>,
+    /// The fraction of each dimension of the window by which its edges are inset before content is laid out.
+    pub safe_area: f32,
+    /// The path or URL of audio played behind the UI, or None to play no audio.
+    pub ambient_audio: Option<String>,
+    /// The volume between 0 and 1 at which ambient audio starts playing.
+    pub ambient_volume: f32,
+    /// The port on which remotes can control the UI over HTTP, or None to not accept remotes.
+    pub remote_port: Option<u16>,
+    /// The colors and font of the UI.
+    pub theme: Theme,
+    /// The actions bound to keys.
+    pub keymap: Keymap,
+    /// The reading of infrared remotes through LIRC and the keys their buttons press.
+    pub lirc: LircConfig,
+    /// The path of the configuration file that was read, if any.
+    pub config_path: Option<PathBuf>,
+    /// The locale in which text is displayed.
+    pub locale: String,
+    /// The path to which a synthetic scene is rendered without a window, or None to display the UI.
+    pub screenshot: Option<PathBuf>,
+    /// The input script replayed against the synthetic scene without a window, or None to display the UI.
+    pub replay: Option<PathBuf>,
+    /// The synthetic scene rendered to the screenshot or replayed against.
+    pub scene: Scene,
+}
+
+impl Options {
+    /// Parses the options from the configuration file and the command line. Exits the process with a usage message if
+    /// the arguments are invalid or help is requested. If the configuration file cannot be read, a warning is printed
+    /// and the default settings are used.
+    pub fn parse() -> Self {
+        let matches = App::new("JDN DSS Solution")
+            .version(env!("CARGO_PKG_VERSION"))
+            .arg(
+                Arg::with_name("config")
+                    .long("config")
+                    .takes_value(true)
+                    .value_name("FILE")
+                    .help("The configuration file to read [default: config.toml in the platform config directory]"),
+            )
+            .arg(
+                Arg::with_name("days")
+                    .long("days")
+                    .takes_value(true)
+                    .value_name("COUNT")
+                    .validator(|value| match value.parse::<u32>() {
+                        Ok(days) if days > 0 => Ok(()),
+                        _ => Err("must be a positive number".to_owned()),
+                    })
+                    .help("The number of days of games to display, ending with today"),
+            )
+            .arg(
+                Arg::with_name("windowed")
+                    .long("windowed")
+                    .help("Displays in a window rather than fullscreen; F11 toggles between them at runtime"),
+            )
+            .arg(
+                Arg::with_name("kiosk")
+                    .long("kiosk")
+                    .help("Runs unattended: ignores exit keys, hides the cursor, and recovers from failures"),
+            )
+            .arg(
+                Arg::with_name("monitor")
+                    .long("monitor")
+                    .takes_value(true)
+                    .value_name("INDEX")
+                    .validator(|value| value.parse::<usize>().map(|_| ()).map_err(|ex| ex.to_string()))
+                    .help("The index of the monitor on which to display [default: the primary monitor]"),
+            )
+            .arg(
+                Arg::with_name("log-level")
+                    .long("log-level")
+                    .takes_value(true)
+                    .value_name("LEVEL")
+                    .possible_values(&["off", "error", "warn", "info", "debug", "trace"])
+                    .help("The level at which messages are logged"),
+            )
+            .arg(
+                Arg::with_name("cache-dir")
+                    .long("cache-dir")
+                    .takes_value(true)
+                    .value_name("DIR")
+                    .help("The directory to which retrieved data is persisted between runs"),
+            )
+            .arg(
+                Arg::with_name("favorite")
+                    .long("favorite")
+                    .takes_value(true)
+                    .multiple(true)
+                    .number_of_values(1)
+                    .value_name("TEAM")
+                    .help("The name of a team whose games are highlighted; may be repeated"),
+            )
+            .arg(
+                Arg::with_name("refresh")
+                    .long("refresh")
+                    .takes_value(true)
+                    .value_name("SECONDS")
+                    .validator(|value| value.parse::<u64>().map(|_| ()).map_err(|ex| ex.to_string()))
+                    .help("The time between refreshes of today's games, or 0 to never refresh"),
+            )
+            .arg(
+                Arg::with_name("screenshot")
+                    .long("screenshot")
+                    .takes_value(true)
+                    .value_name("FILE")
+                    .help("Renders a synthetic scene to a PNG image without a window, then exits"),
+            )
+            .arg(
+                Arg::with_name("scene")
+                    .long("scene")
+                    .takes_value(true)
+                    .value_name("NAME")
+                    .possible_values(&Scene::ALL.iter().map(|scene| scene.name()).collect::<Vec<_>>())
+                    .help("The synthetic scene rendered to the screenshot or replayed against [default: grid]"),
+            )
+            .arg(
+                Arg::with_name("replay")
+                    .long("replay")
+                    .takes_value(true)
+                    .value_name("SCRIPT")
+                    .help("Replays the key events of a script against a synthetic scene without a window, then exits"),
+            )
+            .get_matches();
+
+        let config_path = matches
+            .value_of("config")
+            .map(PathBuf::from)
+            .or_else(Config::default_path);
+        let config = match &config_path {
+            Some(path) => Config::load_or_create(path).unwrap_or_else(|ex| {
+                eprintln!(
+                    "Could not load configuration {}, using defaults: {}",
+                    path.display(),
+                    ex
+                );
+                Config::default()
+            }),
+            None => Config::default(),
+        };
+
+        let mut options = Options::from_config(config, config_path);
+        if let Some(days) = matches.value_of("days").and_then(|value| value.parse::<u32>().ok()) {
+            options.days = days.max(1);
+        }
+        options.windowed |= matches.is_present("windowed");
+        options.kiosk |= matches.is_present("kiosk");
+        if let Some(monitor) = matches.value_of("monitor").and_then(|value| value.parse().ok()) {
+            options.monitor = Some(monitor);
+        }
+        if let Some(log_level) = matches.value_of("log-level").and_then(|value| value.parse().ok()) {
+            options.log_level = log_level;
+        }
+        if let Some(cache_dir) = matches.value_of("cache-dir") {
+            options.cache_dir = Some(PathBuf::from(cache_dir));
+        }
+        if let Some(values) = matches.values_of("favorite") {
+            options.favorite_teams.extend(values.map(str::to_owned));
+        }
+        if let Some(refresh_secs) = matches.value_of("refresh").and_then(|value| value.parse().ok()) {
+            options.refresh_interval = refresh_interval(refresh_secs);
+        }
+        options.screenshot = matches.value_of("screenshot").map(PathBuf::from);
+        options.replay = matches.value_of("replay").map(PathBuf::from);
+        if let Some(scene) = matches.value_of("scene").and_then(Scene::from_name) {
+            options.scene = scene;
+        }
+        options
+    }
+
+    /// Determines the options from the given configuration, read from the given path if any, without any overrides
+    /// from the command line.
+    pub fn from_config(config: Config, config_path: Option<PathBuf>) -> Self {
+        Options {
+            days: config.days.max(1),
+            windowed: config.windowed,
+            kiosk: config.kiosk,
+            monitor: config.monitor,
+            log_level: config.log_level.parse().unwrap_or(LevelFilter::Info),
+            logging: config.logging,
+            cache_dir: config.cache_dir,
+            cache_policy: CachePolicy {
+                complete_ttl: config.cache_complete_secs.map(Duration::from_secs),
+                pending_ttl: Some(Duration::from_secs(config.cache_pending_secs)),
+                live_ttl: Some(Duration::from_secs(config.cache_live_secs)),
+            },
+            favorite_teams: config.favorite_teams,
+            refresh_interval: refresh_interval(config.refresh_secs),
+            vsync: config.vsync,
+            multisampling: config.multisampling,
+            frame_interval: Some(config.max_fps)
+                .filter(|fps| *fps > 0)
+                .map(|fps| Duration::from_secs(1) / fps),
+            content_aspect: content_aspect(&config.aspect_ratio),
+            safe_area: config.safe_area.clamp(0.0, MAX_SAFE_AREA) / 100.0,
+            ambient_audio: config.ambient_audio,
+            ambient_volume: config.ambient_volume.min(100) as f32 / 100.0,
+            remote_port: config.remote_port,
+            theme: Theme::from_config(&config.theme),
+            keymap: Keymap::from_config(&config.keys),
+            lirc: config.lirc,
+            config_path,
+            locale: config.locale,
+            screenshot: None,
+            replay: None,
+            scene: Scene::Grid,
+        }
+    }
+
+    /// Determines the path of the session saved between runs, beside the configuration file, if there is one.
+    pub fn session_path(&self) -> Option<PathBuf> {
+        let dir = self.config_path.as_deref().and_then(Path::parent)?;
+        Some(dir.join(SESSION_FILE_NAME))
+    }
+
+    /// Determines the path of the directory to which crash reports are written, beside the log file.
+    pub fn crash_dir(&self) -> PathBuf {
+        self.logging.file.with_file_name(CRASH_DIR_NAME)
+    }
+
+    /// Loads the catalog of text for the configured locale, from the directory of the configuration file.
+    pub fn load_strings(&self) -> Strings {
+        Strings::load(&self.locale, self.config_path.as_deref().and_then(Path::parent))
+    }
+
+    /// Determines the language in which editorial content is requested, the primary language of the configured locale
+    /// (e.g. "es" for "es-MX"). Returns None if the locale is invalid.
+    pub fn content_language(&self) -> Option<String> {
+        self.locale
+            .parse::<LanguageIdentifier>()
+            .ok()
+            .map(|langid| langid.language.as_str().to_owned())
+    }
+
+    /// Describes the options as pairs of a setting name and its value, in the order displayed in the settings section,
+    /// in the text of the given catalog.
+    pub fn settings(&self, strings: &Strings) -> Vec<(String, String)> {
+        let setting = |name: &str, value: String| (strings.get(name), value);
+        vec![
+            setting("setting-days", self.days.to_string()),
+            setting(
+                "setting-favorites",
+                if self.favorite_teams.is_empty() {
+                    strings.get("setting-none")
+                } else {
+                    self.favorite_teams.join(", ")
+                },
+            ),
+            setting(
+                "setting-refresh",
+                match self.refresh_interval {
+                    Some(interval) => {
+                        strings.format("setting-refresh-interval", &[("seconds", interval.as_secs().into())])
+                    }
+                    None => strings.get("setting-never"),
+                },
+            ),
+            setting(
+                "setting-display",
+                strings.get(if self.windowed {
+                    "setting-windowed"
+                } else {
+                    "setting-fullscreen"
+                }),
+            ),
+            setting(
+                "setting-vsync",
+                strings.get(if self.vsync { "setting-on" } else { "setting-off" }),
+            ),
+            setting(
+                "setting-antialiasing",
+                match self.multisampling {
+                    0 => strings.get("setting-off"),
+                    samples => strings.format("setting-antialiasing-samples", &[("samples", samples.into())]),
+                },
+            ),
+            setting(
+                "setting-frame-rate",
+                match self.frame_interval {
+                    Some(interval) => {
+                        let fps = (1.0 / interval.as_secs_f64()).round() as u64;
+                        strings.format("setting-frame-rate-limit", &[("fps", fps.into())])
+                    }
+                    None => strings.get("setting-unlimited"),
+                },
+            ),
+            setting(
+                "setting-ambient-audio",
+                match &self.ambient_audio {
+                    Some(url) => url.clone(),
+                    None => strings.get("setting-off"),
+                },
+            ),
+            setting(
+                "setting-remote",
+                match self.remote_port {
+                    Some(port) => strings.format("setting-remote-port", &[("port", port.into())]),
+                    None => strings.get("setting-off"),
+                },
+            ),
+            setting(
+                "setting-cache-dir",
+                match &self.cache_dir {
+                    Some(dir) => dir.display().to_string(),
+                    None => strings.get("setting-memory-only"),
+                },
+            ),
+            setting(
+                "setting-config-file",
+                match &self.config_path {
+                    Some(path) => path.display().to_string(),
+                    None => strings.get("setting-none"),
+                },
+            ),
+            setting("setting-locale", self.locale.clone()),
+            setting("setting-log-level", self.log_level.to_string()),
+        ]
+    }
+
+    /// Determines whether the given team is one of the favorite teams, ignoring case.
+    pub fn is_favorite(&self, team: &str) -> bool {
+        self.favorite_teams
+            .iter()
+            .any(|favorite| favorite.eq_ignore_ascii_case(team))
+    }
+}
+
+/// Parses the given aspect ratio as a width and height separated by a colon, where "fill" is no aspect ratio. Ratios that
+/// cannot be parsed are the reference aspect ratio of the layout.
+fn content_aspect(aspect_ratio: &str) -> Option<f32> {
+    if aspect_ratio.trim().eq_ignore_ascii_case("fill") {
+        return None;
+    }
+    let mut parts = aspect_ratio.splitn(2, ':').map(|part| part.trim().parse::<f32>().ok());
+    match (parts.next().flatten(), parts.next().flatten()) {
+        (Some(width), Some(height)) if width > 0.0 && height > 0.0 => Some(width / height),
+        _ => Some(REFERENCE_ASPECT),
+    }
+}
+
+/// Converts the given number of seconds between refreshes to the time between refreshes, where 0 is never.
+fn refresh_interval(secs: u64) -> Option<Duration> {
+    Some(secs).filter(|secs| *secs > 0).map(Duration::from_secs)
+}
@@ -0,0 +1,514 @@
+//! The rows of focusable tiles displayed in the grid, such as the games of a day, and the layout and drawing they share
+//! so that new kinds of content only need to describe their items.
+
+use crate::gl_utils::{self, Bounds, FocusDirection, Focusable, GlyphBrush, SpriteBatch};
+use crate::locale::Strings;
+use crate::theme::{Color, Theme};
+use crate::tween::{self, Easing, Tween};
+use glium::backend::Facade;
+use glium::texture::SrgbTexture2d;
+use glyph_brush::{HorizontalAlign, Layout, Section, Text, VerticalAlign};
+use std::rc::Rc;
+use std::time::Duration;
+
+/// The aspect ratio of the screen for which the layout is designed, which is also the aspect ratio of tile images.
+pub const REFERENCE_ASPECT: f32 = 16.0 / 9.0;
+/// The number of tiles to display at a time for each rail.
+pub const X_PAGE_SIZE: usize = 5;
+/// The number of rails to display at a time.
+pub const Y_PAGE_SIZE: usize = 3;
+/// The percentage from the left of the screen at which to start displaying tiles.
+pub const LEFT_INDENT: f32 = 0.05;
+/// The percentage from the top of the screen at which to start displaying tiles.
+pub const TOP_INDENT: f32 = 0.24;
+/// The percentage of the screen taken up by a focused tile.
+pub const FOCUSED_TILE_SCALE: f32 = 0.15;
+/// The percentage of the screen for horizontal spacing between tiles (assuming both are focused).
+pub const TILE_X_PADDING: f32 = 0.0375;
+/// The percentage of the screen for vertical spacing between tiles (assuming both are focused).
+pub const TILE_Y_PADDING: f32 = 0.05;
+/// The percentage of the screen taken up by a non-focused tile.
+pub const TILE_SCALE: f32 = 0.10;
+/// The percentage of the screen added to horizontal and vertical padding to account for non-focused tiles.
+pub const NON_FOCUSED_OFFSET: f32 = 0.025;
+/// The fraction of the height of a rail taken up by a rail collapsed to its label.
+pub const COLLAPSED_ROW_HEIGHT: f32 = 0.3;
+/// The nominal size of the text of badges and cards on a focused tile, which shrinks with unfocused tiles.
+pub const BADGE_TEXT_SIZE: f32 = 28.0;
+/// The nominal size in pixels of the accent border around tiles, such as games involving a favorite team.
+pub const ACCENT_BORDER_WIDTH: f32 = 4.0;
+
+/// An enumeration of the badges overlaid on tiles.
+pub enum BadgeKind {
+    /// The given score, in the bottom-right corner.
+    Score(String),
+    /// The pulsing indicator of a game in progress, in the top-left corner.
+    Live,
+}
+
+/// A container for a badge and the bounds of the tile it is overlaid on.
+pub struct TileBadge {
+    pub kind: BadgeKind,
+    /// The percentages of the screen from the top-left corner at which the tile starts.
+    pub top_left: (f32, f32),
+    /// The percentages of the screen from the top-left corner at which the tile ends.
+    pub bottom_right: (f32, f32),
+    /// The size of the tile relative to a focused tile.
+    pub size: f32,
+}
+
+/// A container for the text displayed beside the focused item of a rail.
+pub struct Caption {
+    pub title: String,
+    pub summary: String,
+    /// Whether the title is displayed in the accent color, such as for games involving a favorite team.
+    pub accent: bool,
+}
+
+/// A row of focusable items displayed in the grid. The grid lays out each rail as a row of tiles below its label,
+/// pages through its items `X_PAGE_SIZE` at a time, and moves the focus between rails.
+pub trait Rail {
+    /// Describes the rail in the label displayed above it, in the text of the given catalog.
+    fn label(&self, strings: &Strings) -> String;
+
+    /// Determines the number of items that can be focused. A rail without items still occupies a single slot, so that
+    /// it can be focused.
+    fn len(&self) -> usize;
+
+    /// Determines whether the rail has no items.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Determines the index of the first displayed item.
+    fn begin_index(&self) -> usize;
+
+    /// Sets the index of the first displayed item, which the grid keeps within range.
+    fn set_begin_index(&mut self, begin_index: usize);
+
+    /// Determines whether the items of the rail are being retrieved, which is displayed beside its label.
+    fn is_busy(&self) -> bool {
+        false
+    }
+
+    /// Determines whether the rail is collapsed to its label, hiding its items. A collapsed rail can still be focused as
+    /// a single slot.
+    fn is_collapsed(&self) -> bool {
+        false
+    }
+
+    /// Queues the tiles of the rail at the given (possibly fractional) row on screen in the batch of the given painter,
+    /// with the item at the given index focused if any. Badges and animations in progress are recorded by the painter.
+    fn draw(&mut self, painter: &mut RailPainter, y: f32, focused: Option<usize>);
+
+    /// Queues the text displayed within the tiles of the rail at the given (possibly fractional) row on screen, with
+    /// the item at the given index focused if any.
+    fn queue_text(&self, _painter: &RailPainter, _text_brush: &mut GlyphBrush, _y: f32, _focused: Option<usize>) {}
+
+    /// Describes the item at the given index in the caption displayed beside it while it is focused, in the text of the
+    /// given catalog.
+    fn caption(&self, index: usize, strings: &Strings) -> Option<Caption>;
+
+    /// Describes the status of the item at the given index announced by screen readers, such as the state and score of a
+    /// game, if it has one.
+    fn status(&self, _index: usize, _strings: &Strings) -> Option<String> {
+        None
+    }
+
+    /// Determines the identifier of the game that the item at the given index leads to when selected, if any.
+    fn select(&self, index: usize) -> Option<u64>;
+}
+
+/// A container for the position of the focus within a grid of rails, and the first rail displayed.
+pub struct GridFocus {
+    pub focused_row: usize,
+    /// The index of the focused item among the displayed items of the focused rail.
+    pub focused_index: usize,
+    pub begin_row: usize,
+    pub row_scroll: Tween,
+}
+
+impl GridFocus {
+    /// Initializes the focus on the first item of the first rail.
+    pub fn new() -> Self {
+        GridFocus {
+            focused_row: 0,
+            focused_index: 0,
+            begin_row: 0,
+            row_scroll: Tween::new(0.0, tween::PAGE_DURATION).with_easing(Easing::InOut),
+        }
+    }
+}
+
+/// A set of rails displayed together in the grid, such as the rails of a single league. Each grid keeps its own focus,
+/// so that it can be switched away from and back to without losing its place.
+pub trait RailGrid {
+    /// Lists the rails displayed in the grid, in order.
+    fn rails(&self) -> Vec<&dyn Rail>;
+
+    /// Lists the rails displayed in the grid, in the same order as `rails`.
+    fn rails_mut(&mut self) -> Vec<&mut dyn Rail>;
+
+    /// Retrieves the position of the focus within the grid.
+    fn grid(&self) -> &GridFocus;
+
+    /// Retrieves the position of the focus within the grid.
+    fn grid_mut(&mut self) -> &mut GridFocus;
+
+    /// Determines the position of each rail in rows from the first rail, where a collapsed rail only takes up its
+    /// label, followed by the height of all the rails.
+    fn row_offsets(&self) -> Vec<f32> {
+        let mut offset = 0.0;
+        let mut offsets = vec![offset];
+        for rail in self.rails() {
+            offset += row_height(rail);
+            offsets.push(offset);
+        }
+        offsets
+    }
+
+    /// Determines the position of the rail at the given row on screen, in rows from the first displayed rail.
+    fn row_position(&self, row: usize) -> f32 {
+        let offsets = self.row_offsets();
+        offsets[row] - offsets[self.grid().begin_row]
+    }
+
+    /// Lists the items, and the rails without items, that focus can move to in the given direction, positioned as if
+    /// every rail were displayed from its first displayed item. Moving left or right only considers the items of the
+    /// focused rail. Each is identified by its row and its index within the rail.
+    fn focusables(&self, direction: FocusDirection) -> Vec<Focusable<(usize, usize)>> {
+        let horizontal = direction == FocusDirection::Left || direction == FocusDirection::Right;
+        let grid = self.grid();
+        let offsets = self.row_offsets();
+        let mut focusables = Vec::new();
+        for (row, rail) in self.rails().into_iter().enumerate() {
+            if horizontal && row != grid.focused_row {
+                continue;
+            }
+            let y = offsets[row] - offsets[grid.begin_row];
+            if rail.is_collapsed() {
+                focusables.push(Focusable {
+                    key: (row, rail.begin_index()),
+                    bounds: calc_tile_bounds(0.0, y),
+                });
+                continue;
+            }
+            for index in 0..rail.len().max(1) {
+                let x = index as f32 - rail.begin_index() as f32;
+                focusables.push(Focusable {
+                    key: (row, index),
+                    bounds: calc_tile_bounds(x, y),
+                });
+            }
+        }
+        focusables
+    }
+
+    /// Focuses the item at the given index within the rail at the given row, paging the items and rails so that it is
+    /// displayed.
+    fn focus(&mut self, row: usize, index: usize) {
+        let mut begin_index = self.rails()[row].begin_index();
+        if index < begin_index {
+            begin_index = index;
+        } else if index >= begin_index + X_PAGE_SIZE {
+            begin_index = index + 1 - X_PAGE_SIZE;
+        }
+        self.rails_mut()[row].set_begin_index(begin_index);
+        let grid = self.grid_mut();
+        grid.focused_row = row;
+        grid.focused_index = index - begin_index;
+        self.clamp_focus();
+    }
+
+    /// Pages the items of the rail at the given row by the given number of pages, towards later items if positive.
+    fn page_row(&mut self, row: usize, pages: i32) {
+        let mut rails = self.rails_mut();
+        let rail = match rails.get_mut(row) {
+            Some(rail) => rail,
+            None => return,
+        };
+        let last_begin = rail.len().saturating_sub(X_PAGE_SIZE);
+        let offset = X_PAGE_SIZE * pages.unsigned_abs() as usize;
+        let begin_index = if pages < 0 {
+            rail.begin_index().saturating_sub(offset)
+        } else {
+            (rail.begin_index() + offset).min(last_begin)
+        };
+        rail.set_begin_index(begin_index);
+        if row == self.grid().focused_row {
+            self.clamp_focus();
+        }
+    }
+
+    /// Pages the rails by the given number of pages, towards later rails if positive, keeping the focus in the same
+    /// position on screen where possible.
+    fn page_rows(&mut self, pages: i32) {
+        let row_count = self.rails().len();
+        if row_count == 0 {
+            return;
+        }
+        let offset = Y_PAGE_SIZE * pages.unsigned_abs() as usize;
+        let last_row = row_count - 1;
+        let last_begin = self.last_begin_row();
+        let grid = self.grid_mut();
+        if pages < 0 {
+            grid.focused_row = grid.focused_row.saturating_sub(offset);
+            grid.begin_row = grid.begin_row.saturating_sub(offset);
+        } else {
+            grid.focused_row = (grid.focused_row + offset).min(last_row);
+            grid.begin_row = (grid.begin_row + offset).min(last_begin);
+        }
+        self.clamp_focus();
+    }
+
+    /// Determines the last rail that can be displayed first, below which the remaining rails fill the screen.
+    fn last_begin_row(&self) -> usize {
+        let offsets = self.row_offsets();
+        let total = offsets[offsets.len() - 1];
+        offsets
+            .iter()
+            .position(|offset| total - offset <= Y_PAGE_SIZE as f32)
+            .unwrap_or(0)
+            .min(offsets.len().saturating_sub(2))
+    }
+
+    /// Moves the focus to the last rail if it is beyond the displayed rails, and to the last displayed item of the
+    /// focused rail if it is beyond the items of the rail, or to the first slot if the rail has no items displayed or
+    /// is collapsed. The rails are then paged so that the focused rail is displayed.
+    fn clamp_focus(&mut self) {
+        let grid = self.grid();
+        let rails = self.rails();
+        let focused_row = grid.focused_row.min(rails.len().saturating_sub(1));
+        let focused_index = match rails.get(focused_row) {
+            Some(rail) if rail.is_collapsed() => 0,
+            Some(rail) => {
+                let on_page = rail.len().saturating_sub(rail.begin_index()).min(X_PAGE_SIZE);
+                grid.focused_index.min(on_page.saturating_sub(1))
+            }
+            None => grid.focused_index,
+        };
+        let offsets = self.row_offsets();
+        let grid = self.grid_mut();
+        grid.focused_row = focused_row;
+        grid.focused_index = focused_index;
+        if grid.focused_row < grid.begin_row {
+            grid.begin_row = grid.focused_row;
+        }
+        // rails are paged one at a time until the focused rail fits, as collapsed rails take up less of the screen
+        while grid.begin_row < grid.focused_row
+            && offsets[grid.focused_row + 1] - offsets[grid.begin_row] > Y_PAGE_SIZE as f32
+        {
+            grid.begin_row += 1;
+        }
+    }
+}
+
+/// The resources and dimensions with which rails are drawn during a single frame, recording the tiles to draw in a
+/// batch, the badges to overlay on them, and whether any of their animations are in progress.
+pub struct RailPainter<'a> {
+    pub display: &'a dyn Facade,
+    pub theme: &'a Theme,
+    pub strings: &'a Strings,
+    /// The tiles queued by the rails, drawn once every rail has been queued.
+    pub batch: SpriteBatch,
+    pub screen_width: f32,
+    pub screen_height: f32,
+    pub text_ratio: f32,
+    /// The time elapsed since the previous frame, by which animations are advanced.
+    pub delta: Duration,
+    pub badges: Vec<TileBadge>,
+    pub animating: bool,
+}
+
+impl RailPainter<'_> {
+    /// Advances the given tween to this frame and animates it towards the given target, recording whether the animation
+    /// is in progress. Returns the value of the tween for this frame.
+    pub fn animate(&mut self, tween: &mut Tween, target: f32) -> f32 {
+        tween.advance(self.delta);
+        tween.set_target(target);
+        self.animating |= !tween.is_finished();
+        tween.value()
+    }
+
+    /// Queues the given textures, each at its opacity and over the one before it, as a tile in the slot at the given
+    /// (possibly fractional) column and row on screen, enlarged by the given fraction of focus, surrounded by an accent
+    /// border if requested. The given badges are recorded to be overlaid on the tile.
+    pub fn queue_tile(
+        &mut self,
+        images: &[(Rc<SrgbTexture2d>, f32)],
+        slot: (f32, f32),
+        focus: f32,
+        accent: bool,
+        badges: Vec<BadgeKind>,
+    ) {
+        let (translate_x, translate_y, tile_scale) = calc_tile_geometry(slot.0, slot.1, focus);
+        let (x_scale, y_scale) = fit_aspect(tile_scale, self.screen_width, self.screen_height);
+        let radius = self.corner_radius(tile_scale);
+        if accent {
+            let border_width = 2.0 * text_scale(ACCENT_BORDER_WIDTH, self.text_ratio);
+            let (accent_width, accent_height) = (
+                x_scale + border_width / self.screen_width,
+                y_scale + border_width / self.screen_height,
+            );
+            let accent_bounds = Bounds::new(
+                translate_x + (tile_scale - accent_width) / 2.0,
+                translate_y + (tile_scale - accent_height) / 2.0,
+                accent_width,
+                accent_height,
+            );
+            self.batch.queue(
+                None,
+                accent_bounds,
+                gl_utils::FULL_TEXTURE,
+                self.theme.favorite_color,
+                radius + border_width / 2.0,
+            );
+        }
+        let bounds = Bounds::new(
+            translate_x + (tile_scale - x_scale) / 2.0,
+            translate_y + (tile_scale - y_scale) / 2.0,
+            x_scale,
+            y_scale,
+        );
+        for (texture, alpha) in images {
+            let tint = [1.0, 1.0, 1.0, *alpha];
+            self.batch
+                .queue(Some(Rc::clone(texture)), bounds, gl_utils::FULL_TEXTURE, tint, radius);
+        }
+        let center = (translate_x + tile_scale / 2.0, translate_y + tile_scale / 2.0);
+        let top_left = (center.0 - x_scale / 2.0, center.1 - y_scale / 2.0);
+        let bottom_right = (center.0 + x_scale / 2.0, center.1 + y_scale / 2.0);
+        let size = tile_scale / FOCUSED_TILE_SCALE;
+        self.badges.extend(badges.into_iter().map(|kind| TileBadge {
+            kind,
+            top_left,
+            bottom_right,
+            size,
+        }));
+    }
+
+    /// Determines the radius in pixels of the corners of a tile of the given scale, in proportion to the radius of the
+    /// theme at the scale of a focused tile.
+    fn corner_radius(&self, tile_scale: f32) -> f32 {
+        text_scale(self.theme.corner_radius, self.text_ratio) * tile_scale / FOCUSED_TILE_SCALE
+    }
+
+    /// Queues a placeholder tile in the slot at the given (possibly fractional) column and row on screen, enlarged by
+    /// the given fraction of focus. Placeholders also serve as the background of tiles made of text.
+    pub fn queue_placeholder(&mut self, slot: (f32, f32), focus: f32) {
+        let (translate_x, translate_y, tile_scale) = calc_tile_geometry(slot.0, slot.1, focus);
+        let (x_scale, y_scale) = fit_aspect(tile_scale, self.screen_width, self.screen_height);
+        let bounds = Bounds::new(
+            translate_x + (tile_scale - x_scale) / 2.0,
+            translate_y + (tile_scale - y_scale) / 2.0,
+            x_scale,
+            y_scale,
+        );
+        let radius = self.corner_radius(tile_scale);
+        self.batch.queue(
+            None,
+            bounds,
+            gl_utils::FULL_TEXTURE,
+            self.theme.placeholder_color,
+            radius,
+        );
+    }
+
+    /// Queues the given text centered within the tile in the slot at the given (possibly fractional) column and row on
+    /// screen, enlarged by the given fraction of focus. Text that does not fit is wrapped and ellipsized.
+    pub fn queue_tile_text(&self, text_brush: &mut GlyphBrush, text: &str, color: Color, slot: (f32, f32), focus: f32) {
+        let (translate_x, translate_y, tile_scale) = calc_tile_geometry(slot.0, slot.1, focus);
+        let (x_scale, y_scale) = fit_aspect(tile_scale, self.screen_width, self.screen_height);
+        let scale = text_scale(BADGE_TEXT_SIZE, self.text_ratio) * tile_scale / FOCUSED_TILE_SCALE;
+        let bounds = (x_scale * self.screen_width, y_scale * self.screen_height);
+        let text = text_brush.fit_text(text, scale, bounds);
+        text_brush.queue(Section {
+            text: vec![Text::new(&text).with_color(color).with_scale(scale)],
+            screen_position: (
+                (translate_x + tile_scale / 2.0) * self.screen_width,
+                (translate_y + tile_scale / 2.0) * self.screen_height,
+            ),
+            bounds,
+            layout: Layout::default()
+                .h_align(HorizontalAlign::Center)
+                .v_align(VerticalAlign::Center),
+        });
+    }
+}
+
+/// Lists the indices of the items displayed when a rail is scrolled to the given (possibly fractional) first item,
+/// each paired with its (possibly fractional) column on screen.
+pub fn visible_columns(scroll: f32) -> impl Iterator<Item = (usize, f32)> {
+    let first_index = scroll.max(0.0).floor() as usize;
+    (first_index..(scroll.max(0.0).ceil() as usize + X_PAGE_SIZE))
+        .map(move |index| (index, index as f32 - scroll))
+        .filter(|(_, x)| *x > -1.0 && *x < X_PAGE_SIZE as f32)
+}
+
+/// Determines the horizontal and vertical scales of an image that fits within a square of the given scale, as a
+/// percentage of a screen of the given dimensions, while keeping the aspect ratio of `REFERENCE_ASPECT` on screen.
+pub fn fit_aspect(scale: f32, screen_width: f32, screen_height: f32) -> (f32, f32) {
+    let aspect = screen_width / screen_height / REFERENCE_ASPECT;
+    if aspect > 1.0 {
+        (scale / aspect, scale)
+    } else {
+        (scale, scale * aspect)
+    }
+}
+
+/// Determines the size in pixels at which to display text of the given nominal size with the given ratio of displayed
+/// to nominal text size.
+pub fn text_scale(size: f32, text_ratio: f32) -> f32 {
+    size * text_ratio
+}
+
+/// Calculates the percentage of the screen (assuming (0, 0) is the top-left corner) at which the top-left of the
+/// tile at the given indices should be rendered.
+pub fn calc_tile_location_percentage(focused: bool, x: f32, y: f32) -> (f32, f32) {
+    if focused {
+        let translate_x = LEFT_INDENT + (FOCUSED_TILE_SCALE * x) + (TILE_X_PADDING * x);
+        let translate_y = TOP_INDENT + (FOCUSED_TILE_SCALE * y) + (TILE_Y_PADDING * 2.0 * y);
+        (translate_x, translate_y)
+    } else {
+        let translate_x = LEFT_INDENT
+            + (NON_FOCUSED_OFFSET * (x + 1.0))
+            + (TILE_SCALE * x)
+            + (NON_FOCUSED_OFFSET * x)
+            + (TILE_X_PADDING * x);
+        let translate_y = TOP_INDENT
+            + (NON_FOCUSED_OFFSET * (y + 1.0))
+            + (TILE_SCALE * y)
+            + (NON_FOCUSED_OFFSET * y)
+            + (TILE_Y_PADDING * 2.0 * y);
+        (translate_x, translate_y)
+    }
+}
+
+/// Determines the number of rows taken up by the given rail, which is less than a row if it is collapsed to its label.
+pub fn row_height(rail: &dyn Rail) -> f32 {
+    if rail.is_collapsed() {
+        COLLAPSED_ROW_HEIGHT
+    } else {
+        1.0
+    }
+}
+
+/// Calculates the percentages of the screen from the top-left corner at which the tile at the given (possibly
+/// fractional) column and row starts, and its scale, interpolated between its unfocused and focused layouts by the
+/// given fraction of focus.
+pub fn calc_tile_geometry(x: f32, y: f32, focus: f32) -> (f32, f32, f32) {
+    let (focused_x, focused_y) = calc_tile_location_percentage(true, x, y);
+    let (unfocused_x, unfocused_y) = calc_tile_location_percentage(false, x, y);
+    (
+        unfocused_x + (focused_x - unfocused_x) * focus,
+        unfocused_y + (focused_y - unfocused_y) * focus,
+        TILE_SCALE + (FOCUSED_TILE_SCALE - TILE_SCALE) * focus,
+    )
+}
+
+/// Calculates the bounds of an unfocused tile at the given (possibly fractional or off-screen) column and row, which
+/// places every tile on a regular grid regardless of which is focused.
+pub fn calc_tile_bounds(x: f32, y: f32) -> Bounds {
+    let (left, top) = calc_tile_location_percentage(false, x, y);
+    Bounds::new(left, top, TILE_SCALE, TILE_SCALE)
+}
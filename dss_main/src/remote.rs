@@ -0,0 +1,176 @@
+//! Remote control of the UI over HTTP, so that it can be navigated from another device on the network, such as the
+//! browser of a phone, when the application runs on a PC connected to a TV without a keyboard. The server is run on a
+//! background thread, and commands are delivered to the event loop as `UiEvent::Remote`.
+//!
+//! The server accepts commands from any device that can reach it and should only be enabled on trusted networks.
+//! * `GET /` serves a page of buttons that send commands.
+//! * `POST /command/{name}` performs the named command, such as `up`, `select`, or `page-forward`.
+//! * `GET /focused` describes the displayed screen and the focused game as JSON.
+
+use crate::gl_utils::FocusDirection;
+use crate::loader::UiEvent;
+use dss_mlb::MlbGameClientInfo;
+use glium::glutin::event_loop::EventLoopProxy;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{header, Body, Method, Request, Response, Server, StatusCode};
+use log::{debug, error, info};
+use serde_derive::Serialize;
+use std::convert::Infallible;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// The page of buttons served to browsers.
+const REMOTE_PAGE: &str = include_str!("remote.html");
+/// The prefix of the path of each command.
+const COMMAND_PREFIX: &str = "/command/";
+
+/// An enumeration of the commands a remote can perform on the UI.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RemoteCommand {
+    /// Move the focus in the given direction.
+    Move(FocusDirection),
+    /// Select the focused item.
+    Select,
+    /// Return to the previous screen.
+    Back,
+    /// Page towards earlier games.
+    PageBack,
+    /// Page towards later games.
+    PageForward,
+}
+
+impl RemoteCommand {
+    /// Parses the given name of a command in the path of a request. Returns None if there is no such command.
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "up" => Some(RemoteCommand::Move(FocusDirection::Up)),
+            "down" => Some(RemoteCommand::Move(FocusDirection::Down)),
+            "left" => Some(RemoteCommand::Move(FocusDirection::Left)),
+            "right" => Some(RemoteCommand::Move(FocusDirection::Right)),
+            "select" => Some(RemoteCommand::Select),
+            "back" => Some(RemoteCommand::Back),
+            "page-back" => Some(RemoteCommand::PageBack),
+            "page-forward" => Some(RemoteCommand::PageForward),
+            _ => None,
+        }
+    }
+}
+
+/// A container for the state of the UI reported to remotes, updated by the event loop as it draws.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct RemoteStatus {
+    /// The name of the displayed screen.
+    pub screen: String,
+    /// The focused game, or None if no game is focused.
+    pub game: Option<RemoteGame>,
+}
+
+/// A container for the description of a game reported to remotes.
+#[derive(Clone, Debug, Serialize)]
+pub struct RemoteGame {
+    pub game_pk: u64,
+    pub title: String,
+    pub state: String,
+    pub away_team: String,
+    pub home_team: String,
+    pub away_score: Option<u32>,
+    pub home_score: Option<u32>,
+}
+
+impl From<&MlbGameClientInfo> for RemoteGame {
+    fn from(orig: &MlbGameClientInfo) -> Self {
+        RemoteGame {
+            game_pk: orig.game_pk,
+            title: orig.title.clone(),
+            state: orig.detailed_state.clone(),
+            away_team: orig.away_team.clone(),
+            home_team: orig.home_team.clone(),
+            away_score: orig.away_score,
+            home_score: orig.home_score,
+        }
+    }
+}
+
+/// Starts the background thread, which serves remotes on the given port of every network interface, delivering their
+/// commands to the event loop of the given proxy and reporting the given status. Failures to start the server are
+/// logged, leaving the UI usable without it.
+pub fn spawn(proxy: EventLoopProxy<UiEvent>, port: u16, status: Arc<Mutex<RemoteStatus>>) {
+    let spawned = thread::Builder::new().name("remote".to_owned()).spawn(move || {
+        let mut runtime = match tokio::runtime::Builder::new().basic_scheduler().enable_all().build() {
+            Ok(runtime) => runtime,
+            Err(ex) => {
+                error!("Could not create remote control runtime:\n{}", ex);
+                return;
+            }
+        };
+        let proxy = Arc::new(Mutex::new(proxy));
+        let addr = SocketAddr::from((Ipv4Addr::UNSPECIFIED, port));
+        let served = runtime.block_on(async {
+            let make_service = make_service_fn(move |_| {
+                let proxy = Arc::clone(&proxy);
+                let status = Arc::clone(&status);
+                async move {
+                    Ok::<_, Infallible>(service_fn(move |request| {
+                        let response = respond(&request, &proxy, &status);
+                        async move { Ok::<_, Infallible>(response) }
+                    }))
+                }
+            });
+            let server = Server::try_bind(&addr)?.serve(make_service);
+            info!("remote control listening on {}", addr);
+            server.await
+        });
+        if let Err(ex) = served {
+            error!("Could not serve remote control on {}:\n{}", addr, ex);
+        }
+        debug!("ending remote control");
+    });
+    if let Err(ex) = spawned {
+        error!("Could not create remote control thread:\n{}", ex);
+    }
+}
+
+/// Responds to the given request, delivering any command to the event loop of the given proxy and reporting the given
+/// status.
+fn respond(
+    request: &Request<Body>,
+    proxy: &Mutex<EventLoopProxy<UiEvent>>,
+    status: &Mutex<RemoteStatus>,
+) -> Response<Body> {
+    let path = request.uri().path();
+    match (request.method(), path) {
+        (&Method::GET, "/") => Response::builder()
+            .header(header::CONTENT_TYPE, "text/html; charset=utf-8")
+            .body(Body::from(REMOTE_PAGE)),
+        (&Method::GET, "/focused") => match serde_json::to_string(&*status.lock().unwrap()) {
+            Ok(json) => Response::builder()
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(json)),
+            Err(ex) => {
+                error!("Could not describe remote status:\n{}", ex);
+                Response::builder()
+                    .status(StatusCode::INTERNAL_SERVER_ERROR)
+                    .body(Body::empty())
+            }
+        },
+        (&Method::POST, _) if path.starts_with(COMMAND_PREFIX) => {
+            match RemoteCommand::from_name(&path[COMMAND_PREFIX.len()..]) {
+                Some(command) => {
+                    debug!("remote command {:?}", command);
+                    let code = match proxy.lock().unwrap().send_event(UiEvent::Remote(command)) {
+                        Ok(()) => StatusCode::NO_CONTENT,
+                        Err(_) => StatusCode::SERVICE_UNAVAILABLE,
+                    };
+                    Response::builder().status(code).body(Body::empty())
+                }
+                None => Response::builder().status(StatusCode::NOT_FOUND).body(Body::empty()),
+            }
+        }
+        _ => Response::builder().status(StatusCode::NOT_FOUND).body(Body::empty()),
+    }
+    .unwrap_or_else(|ex| {
+        error!("Could not build remote control response:\n{}", ex);
+        Response::new(Body::empty())
+    })
+}
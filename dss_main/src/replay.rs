@@ -0,0 +1,400 @@
+//! Replay of scripted key events against the UI, for verifying navigation without a person at the keyboard. A script
+//! lists key events and the expected position of the focus, each at a time in milliseconds from the start of the
+//! script, one per line:
+//!
+//! ```text
+//! # hold right long enough to repeat three times
+//! 0 press Right
+//! 650 release Right
+//! 700 expect focus 0 4
+//! 700 expect begin 0 0
+//! 800 tap Ctrl+PageDown
+//! 900 expect screen grid
+//! ```
+//!
+//! Keys are named as in the configuration file and performed through the configured keymap, repeating while held as
+//! they do at runtime; "tap" presses and releases a key. Actions that affect the window or the retrieval of data, such
+//! as Quit and Refresh, are ignored. The expectations are of the focused row and item (`focus ROW INDEX`), the first
+//! displayed row and item of the focused row (`begin ROW INDEX`), the focused section of the menu (`menu SECTION` or
+//! `menu none`), the displayed screen (`screen NAME`), and the displayed tab (`tab INDEX`).
+
+use crate::gl_mlb::{FocusState, MlbUiState};
+use crate::gl_utils::FocusDirection;
+use crate::key_repeat::KeyRepeat;
+use crate::keymap::{Action, Keymap};
+use crate::menu::MenuSection;
+use glium::glutin::event::ElementState;
+use log::{debug, info};
+use std::time::{Duration, Instant};
+
+/// The prefix of a key performed with Ctrl held.
+const CTRL_PREFIX: &str = "ctrl+";
+
+/// A key of a script, named as in the configuration file.
+#[derive(Clone, Debug, PartialEq)]
+struct Key {
+    name: String,
+    ctrl: bool,
+}
+
+impl Key {
+    /// Parses the given key, which may be prefixed with "Ctrl+", ignoring case.
+    fn parse(key: &str) -> Self {
+        if key.len() > CTRL_PREFIX.len() && key[..CTRL_PREFIX.len()].eq_ignore_ascii_case(CTRL_PREFIX) {
+            return Key {
+                name: key[CTRL_PREFIX.len()..].to_owned(),
+                ctrl: true,
+            };
+        }
+        Key {
+            name: key.to_owned(),
+            ctrl: false,
+        }
+    }
+}
+
+/// An enumeration of the states of the UI that a script can expect.
+#[derive(Clone, Debug, PartialEq)]
+enum Expectation {
+    /// The focused row and index of the focused item within it.
+    Focus(usize, usize),
+    /// The first displayed row and index of the first displayed item of the focused row.
+    Begin(usize, usize),
+    /// The focused section of the menu, or None if the menu is not focused.
+    Menu(Option<MenuSection>),
+    /// The name of the displayed screen.
+    Screen(String),
+    /// The index of the displayed tab.
+    Tab(usize),
+}
+
+impl Expectation {
+    /// Parses the given words of an expectation, following "expect".
+    fn parse(words: &[&str]) -> Result<Self, String> {
+        let number = |word: Option<&&str>| -> Result<usize, String> {
+            let word = word.ok_or("missing number")?;
+            word.parse().map_err(|ex| format!("invalid number {}: {}", word, ex))
+        };
+        match words.first().copied() {
+            Some("focus") if words.len() == 3 => Ok(Expectation::Focus(number(words.get(1))?, number(words.get(2))?)),
+            Some("begin") if words.len() == 3 => Ok(Expectation::Begin(number(words.get(1))?, number(words.get(2))?)),
+            Some("menu") if words.len() == 2 && words[1].eq_ignore_ascii_case("none") => Ok(Expectation::Menu(None)),
+            Some("menu") if words.len() == 2 => MenuSection::ALL
+                .iter()
+                .find(|section| section_name(**section).eq_ignore_ascii_case(words[1]))
+                .map(|section| Expectation::Menu(Some(*section)))
+                .ok_or_else(|| format!("unknown menu section {}", words[1])),
+            Some("screen") if words.len() == 2 => Ok(Expectation::Screen(words[1].to_lowercase())),
+            Some("tab") if words.len() == 2 => Ok(Expectation::Tab(number(words.get(1))?)),
+            _ => Err(format!("invalid expectation {}", words.join(" "))),
+        }
+    }
+
+    /// Describes how the given state differs from the expectation, or None if it is as expected.
+    fn verify(&self, state: &FocusState) -> Option<String> {
+        let (expected, actual) = match self {
+            Expectation::Focus(row, index) => (
+                format!("focus {} {}", row, index),
+                format!("focus {} {}", state.focused_row, state.focused_index),
+            ),
+            Expectation::Begin(row, index) => (
+                format!("begin {} {}", row, index),
+                format!("begin {} {}", state.begin_row, state.begin_index),
+            ),
+            Expectation::Menu(section) => (
+                format!("menu {}", section.map_or("none", section_name)),
+                format!("menu {}", state.menu.map_or("none", section_name)),
+            ),
+            Expectation::Screen(screen) => (format!("screen {}", screen), format!("screen {}", state.screen)),
+            Expectation::Tab(tab) => (format!("tab {}", tab), format!("tab {}", state.tab)),
+        };
+        if expected == actual {
+            None
+        } else {
+            Some(format!("expected {}, found {}", expected, actual))
+        }
+    }
+}
+
+/// Determines the name by which the given section of the menu is described in scripts.
+fn section_name(section: MenuSection) -> &'static str {
+    match section {
+        MenuSection::Home => "home",
+        MenuSection::Scores => "scores",
+        MenuSection::Standings => "standings",
+        MenuSection::Settings => "settings",
+    }
+}
+
+/// An enumeration of the commands of a script.
+#[derive(Clone, Debug, PartialEq)]
+enum Command {
+    /// Press or release the given key.
+    Key(Key, ElementState),
+    /// Press and immediately release the given key.
+    Tap(Key),
+    /// Verify the state of the UI.
+    Expect(Expectation),
+}
+
+/// A container for a single command of a script, with the line it was read from and when it is performed.
+#[derive(Clone, Debug, PartialEq)]
+struct Step {
+    line: usize,
+    at: Duration,
+    command: Command,
+}
+
+/// A sequence of key events and expectations to replay against the UI.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Script {
+    steps: Vec<Step>,
+}
+
+impl Script {
+    /// Parses the given script. Blank lines and lines starting with '#' are ignored.
+    ///
+    /// # Errors
+    /// Returns a description of the first invalid line, including a step that is earlier than the step before it.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut steps: Vec<Step> = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let words: Vec<&str> = line.split_whitespace().collect();
+            let step = parse_step(&words)
+                .map(|(at, command)| Step {
+                    line: line_number,
+                    at,
+                    command,
+                })
+                .map_err(|ex| format!("line {}: {}", line_number, ex))?;
+            if let Some(previous) = steps.last() {
+                if step.at < previous.at {
+                    return Err(format!("line {}: step is earlier than the step before it", line_number));
+                }
+            }
+            steps.push(step);
+        }
+        Ok(Script { steps })
+    }
+
+    /// Performs the steps of the script against the given UI with the given keymap, as if each were performed at its
+    /// time. Returns a description of each step that failed, which is empty if every expectation was met.
+    pub fn run(&self, ui: &mut MlbUiState, keymap: &Keymap) -> Vec<String> {
+        let start = Instant::now();
+        let mut key_repeat = KeyRepeat::new();
+        let mut failures = Vec::new();
+        for step in self.steps.iter() {
+            let now = start + step.at;
+            while let Some(next) = key_repeat.next_repeat().filter(|next| *next <= now) {
+                if let Some(direction) = key_repeat.due(next) {
+                    ui.move_focus(direction);
+                }
+            }
+            let failure = match &step.command {
+                Command::Key(key, state) => perform(ui, keymap, &mut key_repeat, key, *state, now),
+                Command::Tap(key) => perform(ui, keymap, &mut key_repeat, key, ElementState::Pressed, now)
+                    .or_else(|| perform(ui, keymap, &mut key_repeat, key, ElementState::Released, now)),
+                Command::Expect(expectation) => expectation.verify(&ui.focus_state()),
+            };
+            if let Some(failure) = failure {
+                failures.push(format!("line {}: {}", step.line, failure));
+            }
+        }
+        info!("replayed {} steps with {} failures", self.steps.len(), failures.len());
+        failures
+    }
+}
+
+/// Parses the given words of a step into its time and command.
+fn parse_step(words: &[&str]) -> Result<(Duration, Command), String> {
+    let millis = words.first().ok_or("missing time")?;
+    let at = millis
+        .parse()
+        .map(Duration::from_millis)
+        .map_err(|ex| format!("invalid time {}: {}", millis, ex))?;
+    let command = match (words.get(1).copied(), words.get(2)) {
+        (Some("press"), Some(key)) if words.len() == 3 => Command::Key(Key::parse(key), ElementState::Pressed),
+        (Some("release"), Some(key)) if words.len() == 3 => Command::Key(Key::parse(key), ElementState::Released),
+        (Some("tap"), Some(key)) if words.len() == 3 => Command::Tap(Key::parse(key)),
+        (Some("expect"), _) => Command::Expect(Expectation::parse(&words[2..])?),
+        _ => return Err(format!("invalid command {}", words[1..].join(" "))),
+    };
+    Ok((at, command))
+}
+
+/// Performs the given event of the given key against the given UI at the given time, as the event loop does. Returns
+/// a failure if no action is bound to the key.
+fn perform(
+    ui: &mut MlbUiState,
+    keymap: &Keymap,
+    key_repeat: &mut KeyRepeat,
+    key: &Key,
+    state: ElementState,
+    now: Instant,
+) -> Option<String> {
+    let action = match keymap.action_for_key(&key.name) {
+        Some(action) => action,
+        None => return Some(format!("no action is bound to {}", key.name)),
+    };
+    match (action, state) {
+        (Action::Move(direction), ElementState::Pressed) => {
+            if key_repeat.press(direction, now) {
+                ui.move_focus(direction);
+            }
+        }
+        (Action::Move(direction), ElementState::Released) => key_repeat.release(direction),
+        (Action::Select, ElementState::Released) => {
+            ui.select();
+        }
+        (Action::Back, ElementState::Released) if !ui.is_top_level() => ui.back(),
+        (Action::PageBack, ElementState::Released) => ui.page(if key.ctrl {
+            FocusDirection::Up
+        } else {
+            FocusDirection::Left
+        }),
+        (Action::PageForward, ElementState::Released) => ui.page(if key.ctrl {
+            FocusDirection::Down
+        } else {
+            FocusDirection::Right
+        }),
+        (Action::NextTab, ElementState::Released) => {
+            ui.next_tab();
+        }
+        (Action::SelectTab(tab), ElementState::Released) => {
+            ui.select_tab(tab);
+        }
+        (action, ElementState::Released) => debug!("ignoring replayed action {:?}", action),
+        (_, ElementState::Pressed) => (),
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::headless::{self, Scene};
+    use crate::options::Options;
+
+    /// Replays the given script against the given scene with the default options, failing with the description of
+    /// each step that failed.
+    fn assert_replays(scene: Scene, script: &str) {
+        let script = Script::parse(script).expect("Unable to parse script.");
+        let options = Options::from_config(Config::default(), None);
+        let mut ui = headless::build_state(&options, scene);
+        let failures = script.run(&mut ui, &options.keymap);
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+
+    #[test]
+    fn parse_reads_steps() {
+        let script = Script::parse("# comment\n\n0 press Right\n650 release right\n700 tap Ctrl+PageDown\n").unwrap();
+        let key = |name: &str, ctrl| Key {
+            name: name.to_owned(),
+            ctrl,
+        };
+        assert_eq!(
+            script.steps.iter().map(|step| step.line).collect::<Vec<_>>(),
+            vec![3, 4, 5]
+        );
+        assert_eq!(script.steps[1].at, Duration::from_millis(650));
+        assert_eq!(
+            script.steps[1].command,
+            Command::Key(key("right", false), ElementState::Released)
+        );
+        assert_eq!(script.steps[2].command, Command::Tap(key("PageDown", true)));
+    }
+
+    #[test]
+    fn parse_reads_expectations() {
+        let script =
+            Script::parse("0 expect focus 1 2\n0 expect menu Home\n0 expect menu none\n0 expect screen Grid").unwrap();
+        let expectations: Vec<Command> = script.steps.into_iter().map(|step| step.command).collect();
+        assert_eq!(
+            expectations,
+            vec![
+                Command::Expect(Expectation::Focus(1, 2)),
+                Command::Expect(Expectation::Menu(Some(MenuSection::Home))),
+                Command::Expect(Expectation::Menu(None)),
+                Command::Expect(Expectation::Screen("grid".to_owned())),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_rejects_invalid_lines() {
+        assert_eq!(
+            Script::parse("0 tap Right\nsoon tap Left").unwrap_err(),
+            "line 2: invalid time soon: invalid digit found in string"
+        );
+        assert_eq!(
+            Script::parse("100 tap Right\n50 tap Left").unwrap_err(),
+            "line 2: step is earlier than the step before it"
+        );
+        assert_eq!(
+            Script::parse("0 expect focus 1").unwrap_err(),
+            "line 1: invalid expectation focus 1"
+        );
+        assert_eq!(
+            Script::parse("0 hold Right").unwrap_err(),
+            "line 1: invalid command hold Right"
+        );
+    }
+
+    #[test]
+    fn verify_describes_differences() {
+        let state = FocusState {
+            screen: "grid",
+            menu: None,
+            tab: 0,
+            focused_row: 1,
+            focused_index: 6,
+            begin_row: 0,
+            begin_index: 3,
+        };
+        assert_eq!(Expectation::Focus(1, 6).verify(&state), None);
+        assert_eq!(
+            Expectation::Begin(0, 0).verify(&state),
+            Some("expected begin 0 0, found begin 0 3".to_owned())
+        );
+        assert_eq!(
+            Expectation::Menu(Some(MenuSection::Home)).verify(&state),
+            Some("expected menu home, found menu none".to_owned())
+        );
+    }
+
+    #[test]
+    fn held_key_repeats() {
+        assert_replays(
+            Scene::Grid,
+            "0 press Right\n650 release Right\n700 expect focus 0 4\n700 expect begin 0 0\n\
+             800 tap Right\n900 expect focus 0 5\n900 expect begin 0 1",
+        );
+    }
+
+    #[test]
+    fn moving_up_from_the_grid_focuses_the_menu() {
+        assert_replays(
+            Scene::Grid,
+            "0 tap Down\n100 expect focus 1 0\n200 tap Up\n300 expect focus 0 0\n\
+             # the day after the most recent day is added above it before the menu is reached\n\
+             400 tap Up\n500 expect focus 0 0\n600 tap Up\n700 expect menu home\n\
+             800 tap Right\n900 expect menu scores\n1000 tap Down\n1100 expect menu none\n1100 expect screen grid",
+        );
+    }
+
+    #[test]
+    fn paging_moves_rows_and_items() {
+        assert_replays(
+            Scene::Grid,
+            "0 tap PageDown\n100 expect focus 0 3\n100 expect begin 0 3\n\
+             200 tap Ctrl+PageDown\n300 expect focus 2 0\n300 expect begin 0 0\n\
+             400 tap Ctrl+PageUp\n500 expect focus 0 3\n500 expect begin 0 3",
+        );
+    }
+}
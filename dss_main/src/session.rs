@@ -0,0 +1,61 @@
+//! The state of the UI persisted between runs, so that a restart resumes with the same tab, focus, and paging rather
+//! than at the first game of the first day.
+
+use serde_derive::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+/// A container for the state of the UI persisted between runs. Days are identified by their dates (e.g. "2020-09-01"),
+/// so that the state can be restored to days that are displayed in a different order or not at all.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct Session {
+    /// The displayed tab: 0 for the MLB grid, otherwise the grid of a league.
+    pub tab: usize,
+    /// The date of the focused day, or None if a row other than a day is focused.
+    pub focused_day: Option<String>,
+    /// The index of the focused game within the focused day, counting games paged out of view.
+    pub focused_index: usize,
+    /// The date of the first displayed day, or None if the first displayed row is not a day.
+    pub begin_day: Option<String>,
+    /// The index of the first displayed game of each day, by date.
+    pub begin_indices: BTreeMap<String, usize>,
+    /// The dates of the days collapsed to their labels.
+    pub collapsed_days: BTreeSet<String>,
+}
+
+impl Session {
+    /// Loads the session saved at the given path, or the default session if none has been saved.
+    ///
+    /// # Errors
+    /// * If the file cannot be read.
+    /// * If the file is not a valid session.
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        if !path.exists() {
+            return Ok(Session::default());
+        }
+        let text = fs::read_to_string(path)?;
+        Ok(toml::from_str(&text)?)
+    }
+
+    /// Saves the session to the given path. The session is written beside the path first and then moved over it, so
+    /// that the saved session is never left partially written.
+    ///
+    /// # Errors
+    /// * If the session cannot be written.
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let partial = path.with_extension("toml.partial");
+        fs::write(&partial, toml::to_string_pretty(self)?)?;
+        fs::rename(&partial, path)?;
+        Ok(())
+    }
+
+    /// Determines whether any of the session remains to be restored.
+    pub fn is_pending(&self) -> bool {
+        self.focused_day.is_some() || self.begin_day.is_some() || !self.begin_indices.is_empty()
+    }
+}
@@ -0,0 +1,147 @@
+//! The shader programs with which the UI is drawn. Their sources are embedded from the files of `src/shaders`, and in
+//! debug builds those files are watched while the application runs, so that edited shaders are recompiled and drawn
+//! without restarting the application and retrieving its data again.
+
+use crate::loader::UiEvent;
+use glium::backend::Facade;
+use glium::glutin::event_loop::EventLoopProxy;
+use glium::Program;
+use log::{debug, error, info};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// The time to wait between checks for changes to the shader files.
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A container for the name and the embedded vertex and fragment sources of a shader program.
+pub struct Shader {
+    /// The name of the program, which is also the stem of the names of its source files.
+    name: &'static str,
+    vertex: &'static str,
+    fragment: &'static str,
+}
+
+/// The program used to render an image with the given opacity, masked to a rectangle of the given size in pixels with
+/// corners of the given radius in pixels, or unmasked if the radius is 0.
+pub const IMAGE: Shader = Shader {
+    name: "image",
+    vertex: include_str!("shaders/image.vert"),
+    fragment: include_str!("shaders/image.frag"),
+};
+
+/// The program used to render points with colors in sRGB.
+pub const RECT: Shader = Shader {
+    name: "rect",
+    vertex: include_str!("shaders/rect.vert"),
+    fragment: include_str!("shaders/rect.frag"),
+};
+
+/// The program used to fill a rectangle of the given size in pixels in the given sRGB color, with corners of the given
+/// radius in pixels, or only its outline if the given line width in pixels is greater than 0.
+pub const ROUNDED_RECT: Shader = Shader {
+    name: "rounded_rect",
+    vertex: include_str!("shaders/rounded_rect.vert"),
+    fragment: include_str!("shaders/rounded_rect.frag"),
+};
+
+/// The program used to render a batch of sprites sampled from the given texture, each tinted by its own sRGB color and
+/// masked to its size in pixels with corners of its radius in pixels, or unmasked if the radius is 0.
+pub const SPRITE: Shader = Shader {
+    name: "sprite",
+    vertex: include_str!("shaders/sprite.vert"),
+    fragment: include_str!("shaders/sprite.frag"),
+};
+
+/// The program used to render glyphs from their distance fields in sRGB colors, with any effect behind them.
+pub const GLYPH: Shader = Shader {
+    name: "glyph",
+    vertex: include_str!("shaders/glyph.vert"),
+    fragment: include_str!("shaders/glyph.frag"),
+};
+
+impl Shader {
+    /// Compiles the embedded sources of the program with the given display.
+    ///
+    /// # Errors
+    /// Panics if the given display cannot be used to create the program.
+    pub fn compile(&self, display: &dyn Facade) -> Program {
+        Program::from_source(display, self.vertex, self.fragment, None).unwrap_or_else(|ex| {
+            let msg = format!("Could not create OpenGL {} program", self.name);
+            error!("{}:\n{}", msg, ex);
+            panic!("{}.", msg);
+        })
+    }
+
+    /// Compiles the sources of the program read from the shader files with the given display. Returns None if the
+    /// files cannot be read or the program cannot be created, which is logged, so that the program in use is kept
+    /// while a shader is being edited.
+    pub fn reload(&self, display: &dyn Facade) -> Option<Program> {
+        let read = |extension: &str| {
+            let path = source_dir().join(format!("{}.{}", self.name, extension));
+            fs::read_to_string(&path)
+                .map_err(|ex| error!("Could not read shader {}:\n{}", path.display(), ex))
+                .ok()
+        };
+        let (vertex, fragment) = (read("vert")?, read("frag")?);
+        Program::from_source(display, &vertex, &fragment, None)
+            .map_err(|ex| error!("Could not recompile OpenGL {} program:\n{}", self.name, ex))
+            .ok()
+    }
+
+    /// Replaces the given program with the program compiled from the shader files, unless it cannot be recompiled.
+    pub fn reload_into(&self, display: &dyn Facade, program: &mut Program) {
+        if let Some(reloaded) = self.reload(display) {
+            debug!("recompiled {} program", self.name);
+            *program = reloaded;
+        }
+    }
+}
+
+/// Determines the directory of the shader files in the source tree the application was built from.
+fn source_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("src").join("shaders")
+}
+
+/// Determines the time each shader file was last modified, in the order of their paths. Files that cannot be read are
+/// omitted.
+fn modified_times() -> Vec<(PathBuf, SystemTime)> {
+    let mut times: Vec<(PathBuf, SystemTime)> = fs::read_dir(source_dir())
+        .map(|entries| {
+            entries
+                .filter_map(|entry| {
+                    let entry = entry.ok()?;
+                    let modified = entry.metadata().and_then(|metadata| metadata.modified()).ok()?;
+                    Some((entry.path(), modified))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    times.sort();
+    times
+}
+
+/// Starts the background thread, which notifies the event loop of the given proxy with `UiEvent::ShadersChanged` when
+/// a shader file is created, modified, or removed. Intended for debug builds, in which the source tree is at hand.
+pub fn watch(proxy: EventLoopProxy<UiEvent>) {
+    let spawned = thread::Builder::new().name("shaders".to_owned()).spawn(move || {
+        info!("watching shaders in {}", source_dir().display());
+        let mut times = modified_times();
+        loop {
+            thread::sleep(WATCH_INTERVAL);
+            let current = modified_times();
+            if current != times {
+                times = current;
+                debug!("shaders changed");
+                if proxy.send_event(UiEvent::ShadersChanged).is_err() {
+                    debug!("ending shader watch");
+                    return;
+                }
+            }
+        }
+    });
+    if let Err(ex) = spawned {
+        error!("Could not create shader watch thread:\n{}", ex);
+    }
+}
@@ -0,0 +1,20 @@
+#version 150
+
+uniform sampler2D font_tex;
+
+in vec2 f_tex_pos;
+in vec4 f_color;
+
+out vec4 Target0;
+
+// the texture holds the distance fields of glyphs, in which 0.5 lies on their outlines, so that the edge is
+// smoothed over the size of a pixel at any scale
+void main() {
+    float distance = texture(font_tex, f_tex_pos).r;
+    float smoothing = fwidth(distance);
+    float alpha = smoothstep(0.5 - smoothing, 0.5 + smoothing, distance);
+    if (alpha <= 0.0) {
+        discard;
+    }
+    Target0 = f_color * vec4(1.0, 1.0, 1.0, alpha);
+}
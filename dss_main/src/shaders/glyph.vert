@@ -0,0 +1,61 @@
+#version 150
+
+const mat4 INVERT_Y_AXIS = mat4(
+    vec4(1.0, 0.0, 0.0, 0.0),
+    vec4(0.0, -1.0, 0.0, 0.0),
+    vec4(0.0, 0.0, 1.0, 0.0),
+    vec4(0.0, 0.0, 0.0, 1.0)
+);
+
+uniform mat4 transform;
+// the offset in pixels and color of an effect drawn behind the text, which replaces the color of the text if its
+// weight is 1
+uniform vec2 offset;
+uniform vec4 effect_color;
+uniform float effect_weight;
+
+in vec3 left_top;
+in vec2 right_bottom;
+in vec2 tex_left_top;
+in vec2 tex_right_bottom;
+in vec4 color;
+
+out vec2 f_tex_pos;
+out vec4 f_color;
+
+// vertex colors are sRGB, and are converted so that they blend in linear space like sampled textures
+vec3 to_linear(vec3 srgb) {
+    return mix(srgb / 12.92, pow((srgb + 0.055) / 1.055, vec3(2.4)), step(0.04045, srgb));
+}
+
+// generate positional data based on vertex ID
+void main() {
+    vec2 pos = vec2(0.0);
+    float left = left_top.x;
+    float right = right_bottom.x;
+    float top = left_top.y;
+    float bottom = right_bottom.y;
+
+    switch (gl_VertexID) {
+        case 0:
+            pos = vec2(left, top);
+            f_tex_pos = tex_left_top;
+            break;
+        case 1:
+            pos = vec2(right, top);
+            f_tex_pos = vec2(tex_right_bottom.x, tex_left_top.y);
+            break;
+        case 2:
+            pos = vec2(left, bottom);
+            f_tex_pos = vec2(tex_left_top.x, tex_right_bottom.y);
+            break;
+        case 3:
+            pos = vec2(right, bottom);
+            f_tex_pos = tex_right_bottom;
+            break;
+    }
+
+    vec4 srgb = mix(color, vec4(effect_color.rgb, effect_color.a * color.a), effect_weight);
+    f_color = vec4(to_linear(srgb.rgb), srgb.a);
+    gl_Position = INVERT_Y_AXIS * transform * vec4(pos + offset, left_top.z, 1.0);
+}
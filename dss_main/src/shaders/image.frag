@@ -0,0 +1,23 @@
+#version 140
+
+uniform sampler2D tex;
+uniform float alpha;
+uniform vec2 size;
+uniform float radius;
+
+in vec2 v_tex_coords;
+
+out vec4 color;
+
+void main() {
+    vec4 tex_color = texture(tex, v_tex_coords);
+    float coverage = 1.0;
+    if (radius > 0.0) {
+        vec2 half_size = size / 2.0;
+        float r = min(radius, min(half_size.x, half_size.y));
+        vec2 corner = abs(v_tex_coords * size - half_size) - half_size + vec2(r);
+        float distance = length(max(corner, 0.0)) + min(max(corner.x, corner.y), 0.0) - r;
+        coverage = clamp(0.5 - distance, 0.0, 1.0);
+    }
+    color = vec4(tex_color.rgb, tex_color.a * alpha * coverage);
+}
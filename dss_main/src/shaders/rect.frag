@@ -0,0 +1,9 @@
+#version 140
+
+in vec4 f_color;
+
+out vec4 color;
+
+void main() {
+    color = f_color;
+}
@@ -0,0 +1,18 @@
+#version 140
+
+uniform mat4 matrix;
+
+in vec2 position;
+in vec4 color;
+
+out vec4 f_color;
+
+// vertex colors are sRGB, and are converted so that they blend in linear space like sampled textures
+vec3 to_linear(vec3 srgb) {
+    return mix(srgb / 12.92, pow((srgb + 0.055) / 1.055, vec3(2.4)), step(0.04045, srgb));
+}
+
+void main() {
+    f_color = vec4(to_linear(color.rgb), color.a);
+    gl_Position = matrix * vec4(position, 0.0, 1.0);
+}
@@ -0,0 +1,22 @@
+#version 140
+
+uniform vec2 size;
+uniform float radius;
+uniform float line_width;
+
+in vec4 f_color;
+in vec2 v_coords;
+
+out vec4 color;
+
+void main() {
+    vec2 half_size = size / 2.0;
+    float r = min(radius, min(half_size.x, half_size.y));
+    vec2 corner = abs(v_coords * size - half_size) - half_size + vec2(r);
+    float distance = length(max(corner, 0.0)) + min(max(corner.x, corner.y), 0.0) - r;
+    float coverage = clamp(0.5 - distance, 0.0, 1.0);
+    if (line_width > 0.0) {
+        coverage *= clamp(0.5 + distance + line_width, 0.0, 1.0);
+    }
+    color = vec4(f_color.rgb, f_color.a * coverage);
+}
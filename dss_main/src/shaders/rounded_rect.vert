@@ -0,0 +1,20 @@
+#version 140
+
+uniform mat4 matrix;
+uniform vec4 fill;
+
+in vec2 position;
+
+out vec4 f_color;
+out vec2 v_coords;
+
+// vertex colors are sRGB, and are converted so that they blend in linear space like sampled textures
+vec3 to_linear(vec3 srgb) {
+    return mix(srgb / 12.92, pow((srgb + 0.055) / 1.055, vec3(2.4)), step(0.04045, srgb));
+}
+
+void main() {
+    f_color = vec4(to_linear(fill.rgb), fill.a);
+    v_coords = position * 0.5 + 0.5;
+    gl_Position = matrix * vec4(position, 0.0, 1.0);
+}
@@ -0,0 +1,24 @@
+#version 140
+
+uniform sampler2D tex;
+
+in vec2 v_tex_coords;
+in vec2 v_local;
+in vec4 v_tint;
+in vec2 v_size;
+in float v_radius;
+
+out vec4 color;
+
+void main() {
+    vec4 tex_color = texture(tex, v_tex_coords) * v_tint;
+    float coverage = 1.0;
+    if (v_radius > 0.0) {
+        vec2 half_size = v_size / 2.0;
+        float r = min(v_radius, min(half_size.x, half_size.y));
+        vec2 corner = abs(v_local * v_size - half_size) - half_size + vec2(r);
+        float distance = length(max(corner, 0.0)) + min(max(corner.x, corner.y), 0.0) - r;
+        coverage = clamp(0.5 - distance, 0.0, 1.0);
+    }
+    color = vec4(tex_color.rgb, tex_color.a * coverage);
+}
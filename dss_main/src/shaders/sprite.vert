@@ -0,0 +1,28 @@
+#version 140
+
+in vec2 position;
+in vec2 tex_coords;
+in vec2 local;
+in vec4 tint;
+in vec2 size;
+in float radius;
+
+out vec2 v_tex_coords;
+out vec2 v_local;
+out vec4 v_tint;
+out vec2 v_size;
+out float v_radius;
+
+// tints are sRGB, and are converted so that they blend in linear space like sampled textures
+vec3 to_linear(vec3 srgb) {
+    return mix(srgb / 12.92, pow((srgb + 0.055) / 1.055, vec3(2.4)), step(0.04045, srgb));
+}
+
+void main() {
+    v_tex_coords = tex_coords;
+    v_local = local;
+    v_tint = vec4(to_linear(tint.rgb), tint.a);
+    v_size = size;
+    v_radius = radius;
+    gl_Position = vec4(position, 0.0, 1.0);
+}
@@ -0,0 +1,210 @@
+//! The rail of the standings of each MLB division, and the full table of standings displayed in its own section.
+
+use crate::gl_utils::GlyphBrush;
+use crate::locale::Strings;
+use crate::menu::SECTION_TOP;
+use crate::rail::{self, text_scale, Caption, Rail, RailPainter, LEFT_INDENT};
+use crate::theme::Theme;
+use crate::tween::{self, Easing, Tween};
+use dss_mlb::standings::MlbDivisionStandings;
+use glyph_brush::{HorizontalAlign, Layout, Section, Text};
+
+/// The number of divisions displayed side by side in the table of standings.
+const TABLE_COLUMNS: usize = 3;
+/// The percentage of the screen taken up by each division in the table of standings.
+const TABLE_COLUMN_WIDTH: f32 = 0.3;
+/// The percentage of the screen taken up by the team name within each division of the table.
+const TABLE_TEAM_WIDTH: f32 = 0.18;
+/// The percentage of the screen taken up by the record and games back within each division of the table.
+const TABLE_RECORD_WIDTH: f32 = 0.05;
+/// The percentage of the screen taken up by each row of the table of standings.
+const TABLE_ROW_HEIGHT: f32 = 0.035;
+/// The percentage of the screen between the rows of divisions in the table of standings.
+const TABLE_ROW_GAP: f32 = 0.04;
+
+/// A container for the standings of a single division.
+struct DivisionCard {
+    standings: MlbDivisionStandings,
+    focus: Tween,
+}
+
+/// A rail with a snapshot of the standings of each division, showing its leader on the tile and every team in the
+/// caption.
+pub struct StandingsRail {
+    divisions: Vec<DivisionCard>,
+    begin_index: usize,
+    scroll: Tween,
+}
+
+impl StandingsRail {
+    /// Initializes a rail without standings.
+    pub fn new() -> Self {
+        StandingsRail {
+            divisions: Vec::new(),
+            begin_index: 0,
+            scroll: Tween::new(0.0, tween::PAGE_DURATION).with_easing(Easing::InOut),
+        }
+    }
+
+    /// Replaces the displayed standings with the given standings, in the given order.
+    pub fn set_standings(&mut self, standings: Vec<MlbDivisionStandings>) {
+        self.divisions = standings
+            .into_iter()
+            .map(|standings| DivisionCard {
+                standings,
+                focus: Tween::new(0.0, tween::FOCUS_DURATION),
+            })
+            .collect();
+        self.begin_index = self
+            .begin_index
+            .min(self.divisions.len().saturating_sub(rail::X_PAGE_SIZE));
+    }
+
+    /// Queues the full standings of every division as a table, `TABLE_COLUMNS` divisions side by side, in the colors
+    /// of the given theme.
+    pub fn queue_table(
+        &self,
+        text_brush: &mut GlyphBrush,
+        theme: &Theme,
+        strings: &Strings,
+        screen_width: f32,
+        screen_height: f32,
+        text_ratio: f32,
+    ) {
+        let scale = text_scale(28.0, text_ratio);
+        if self.divisions.is_empty() {
+            text_brush.queue(Section {
+                text: vec![Text::new(&strings.get("no-standings"))
+                    .with_color(theme.secondary_text_color)
+                    .with_scale(scale)],
+                screen_position: (LEFT_INDENT * screen_width, SECTION_TOP * screen_height),
+                ..Section::default()
+            });
+            return;
+        }
+        let mut block_top = SECTION_TOP;
+        for row in self.divisions.chunks(TABLE_COLUMNS) {
+            let mut block_height: f32 = 0.0;
+            for (column, division) in row.iter().enumerate() {
+                let standings = &division.standings;
+                let left = LEFT_INDENT + TABLE_COLUMN_WIDTH * column as f32;
+                let record_left = left + TABLE_TEAM_WIDTH;
+                let bounds = (TABLE_TEAM_WIDTH * screen_width, TABLE_ROW_HEIGHT * screen_height);
+                let record_bounds = (TABLE_RECORD_WIDTH * screen_width, bounds.1);
+                let name = text_brush.fit_text(&standings.name, scale, (TABLE_COLUMN_WIDTH * screen_width, bounds.1));
+                text_brush.queue(Section {
+                    text: vec![Text::new(&name)
+                        .with_color(theme.secondary_text_color)
+                        .with_scale(scale)],
+                    screen_position: (left * screen_width, block_top * screen_height),
+                    ..Section::default()
+                });
+                for (rank, team) in standings.teams.iter().enumerate() {
+                    let top = (block_top + TABLE_ROW_HEIGHT * (rank + 1) as f32) * screen_height;
+                    let record = format!("{}-{}", team.wins, team.losses);
+                    let name = text_brush.fit_text(&team.name, scale, bounds);
+                    text_brush.queue(Section {
+                        text: vec![Text::new(&name).with_color(theme.text_color).with_scale(scale)],
+                        screen_position: (left * screen_width, top),
+                        bounds,
+                        ..Section::default()
+                    });
+                    for (column, text) in [record.as_str(), team.games_back.as_str()].iter().enumerate() {
+                        let right = record_left + TABLE_RECORD_WIDTH * (column + 1) as f32;
+                        text_brush.queue(Section {
+                            text: vec![Text::new(text).with_color(theme.text_color).with_scale(scale)],
+                            screen_position: (right * screen_width, top),
+                            bounds: record_bounds,
+                            layout: Layout::default().h_align(HorizontalAlign::Right),
+                        });
+                    }
+                }
+                block_height = block_height.max(TABLE_ROW_HEIGHT * (standings.teams.len() + 1) as f32);
+            }
+            block_top += block_height + TABLE_ROW_GAP;
+        }
+    }
+}
+
+impl Rail for StandingsRail {
+    fn label(&self, strings: &Strings) -> String {
+        strings.get("standings-label")
+    }
+
+    fn len(&self) -> usize {
+        self.divisions.len()
+    }
+
+    fn begin_index(&self) -> usize {
+        self.begin_index
+    }
+
+    fn set_begin_index(&mut self, begin_index: usize) {
+        self.begin_index = begin_index;
+    }
+
+    fn draw(&mut self, painter: &mut RailPainter, y: f32, focused: Option<usize>) {
+        let scroll = painter.animate(&mut self.scroll, self.begin_index as f32);
+        for (index, x) in rail::visible_columns(scroll) {
+            if let Some(division) = self.divisions.get_mut(index) {
+                let focus = painter.animate(&mut division.focus, if focused == Some(index) { 1.0 } else { 0.0 });
+                painter.queue_placeholder((x, y), focus);
+            }
+        }
+    }
+
+    fn queue_text(&self, painter: &RailPainter, text_brush: &mut GlyphBrush, y: f32, _focused: Option<usize>) {
+        let scroll = self.scroll.value();
+        for (index, x) in rail::visible_columns(scroll) {
+            if let Some(division) = self.divisions.get(index) {
+                let standings = &division.standings;
+                let text = match standings.teams.first() {
+                    Some(leader) => {
+                        let args = [
+                            ("team", leader.name.clone().into()),
+                            ("wins", leader.wins.into()),
+                            ("losses", leader.losses.into()),
+                        ];
+                        format!(
+                            "{}\n{}",
+                            standings.name,
+                            painter.strings.format("standings-leader", &args)
+                        )
+                    }
+                    None => standings.name.clone(),
+                };
+                let focus = division.focus.value();
+                painter.queue_tile_text(text_brush, &text, painter.theme.text_color, (x, y), focus);
+            }
+        }
+    }
+
+    fn caption(&self, index: usize, strings: &Strings) -> Option<Caption> {
+        let standings = &self.divisions.get(index)?.standings;
+        let teams: Vec<String> = standings
+            .teams
+            .iter()
+            .map(|team| {
+                let args = [
+                    ("team", team.name.clone().into()),
+                    ("wins", team.wins.into()),
+                    ("losses", team.losses.into()),
+                    ("games-back", team.games_back.clone().into()),
+                ];
+                match team.games_back.as_str() {
+                    "-" => strings.format("standings-leader", &args),
+                    _ => strings.format("standings-team", &args),
+                }
+            })
+            .collect();
+        Some(Caption {
+            title: standings.name.clone(),
+            summary: teams.join(", "),
+            accent: false,
+        })
+    }
+
+    fn select(&self, _index: usize) -> Option<u64> {
+        None
+    }
+}
@@ -0,0 +1,109 @@
+//! The rail of recap stories of recently completed MLB games.
+
+use crate::gl_utils::GlyphBrush;
+use crate::locale::Strings;
+use crate::rail::{self, Caption, Rail, RailPainter};
+use crate::tween::{self, Easing, Tween};
+use chrono::NaiveDate;
+use dss_mlb::{MlbGameClientInfo, MlbGameState};
+
+/// The most stories displayed in the rail.
+const STORY_COUNT: usize = 10;
+
+/// A container for the recap story of a single game.
+struct Story {
+    game_pk: u64,
+    headline: String,
+    title: String,
+    date: NaiveDate,
+    focus: Tween,
+}
+
+/// A rail of the recap stories of the most recently completed games, each leading to the detail of its game.
+pub struct StoriesRail {
+    stories: Vec<Story>,
+    begin_index: usize,
+    scroll: Tween,
+}
+
+impl StoriesRail {
+    /// Initializes a rail without stories.
+    pub fn new() -> Self {
+        StoriesRail {
+            stories: Vec::new(),
+            begin_index: 0,
+            scroll: Tween::new(0.0, tween::PAGE_DURATION).with_easing(Easing::InOut),
+        }
+    }
+
+    /// Replaces the stories with the recaps of the given completed games, each paired with the day it was played, in
+    /// the given order. At most `STORY_COUNT` stories are kept.
+    pub fn set_games<'a>(&mut self, games: impl Iterator<Item = (NaiveDate, &'a MlbGameClientInfo)>) {
+        self.stories = games
+            .filter(|(_, game)| game.state == MlbGameState::Final && game.image_url.is_some())
+            .take(STORY_COUNT)
+            .map(|(date, game)| Story {
+                game_pk: game.game_pk,
+                headline: game.summary.clone(),
+                title: game.title.clone(),
+                date,
+                focus: Tween::new(0.0, tween::FOCUS_DURATION),
+            })
+            .collect();
+        self.begin_index = self
+            .begin_index
+            .min(self.stories.len().saturating_sub(rail::X_PAGE_SIZE));
+    }
+}
+
+impl Rail for StoriesRail {
+    fn label(&self, strings: &Strings) -> String {
+        strings.get("stories-label")
+    }
+
+    fn len(&self) -> usize {
+        self.stories.len()
+    }
+
+    fn begin_index(&self) -> usize {
+        self.begin_index
+    }
+
+    fn set_begin_index(&mut self, begin_index: usize) {
+        self.begin_index = begin_index;
+    }
+
+    fn draw(&mut self, painter: &mut RailPainter, y: f32, focused: Option<usize>) {
+        let scroll = painter.animate(&mut self.scroll, self.begin_index as f32);
+        for (index, x) in rail::visible_columns(scroll) {
+            if let Some(story) = self.stories.get_mut(index) {
+                let focus = painter.animate(&mut story.focus, if focused == Some(index) { 1.0 } else { 0.0 });
+                painter.queue_placeholder((x, y), focus);
+            }
+        }
+    }
+
+    fn queue_text(&self, painter: &RailPainter, text_brush: &mut GlyphBrush, y: f32, _focused: Option<usize>) {
+        let scroll = self.scroll.value();
+        for (index, x) in rail::visible_columns(scroll) {
+            if let Some(story) = self.stories.get(index) {
+                let focus = story.focus.value();
+                painter.queue_tile_text(text_brush, &story.headline, painter.theme.text_color, (x, y), focus);
+            }
+        }
+    }
+
+    fn caption(&self, index: usize, strings: &Strings) -> Option<Caption> {
+        let story = self.stories.get(index)?;
+        let date = strings.format_date(story.date, "story-date-format");
+        Some(Caption {
+            title: story.title.clone(),
+            summary: strings.format("story-summary", &[("date", date.into())]),
+            accent: false,
+        })
+    }
+
+    fn select(&self, index: usize) -> Option<u64> {
+        self.stories.get(index).map(|story| story.game_pk)
+    }
+}
@@ -0,0 +1,344 @@
+//! The colors and font of the UI, chosen from a preset and optionally overridden by the configuration file, so that the
+//! look can be branded without recompiling.
+
+use crate::gl_utils::TextEffect;
+use dss_mlb::MlbClient;
+use font_kit::family_name::FamilyName;
+use font_kit::handle::Handle;
+use font_kit::properties::Properties;
+use font_kit::source::SystemSource;
+use glyph_brush::ab_glyph::{FontArc, FontVec};
+use log::{debug, error};
+use serde_derive::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The bytes of the font used if none is configured.
+const DEFAULT_FONT: &[u8] = include_bytes!("tahoma.ttf");
+/// The families of the system fonts used for characters missing from the font if no fallback fonts are configured.
+const DEFAULT_FALLBACK_FONTS: [&str; 6] = [
+    "Noto Sans",
+    "DejaVu Sans",
+    "Segoe UI",
+    "Arial Unicode MS",
+    "Noto Color Emoji",
+    "Segoe UI Emoji",
+];
+
+/// A color as red, green, blue, and alpha components from 0 to 1.
+pub type Color = [f32; 4];
+
+/// A container for the colors and font of the UI.
+#[derive(Clone, Debug)]
+pub struct Theme {
+    /// The color of most text.
+    pub text_color: Color,
+    /// The color of secondary text, such as the date labels.
+    pub secondary_text_color: Color,
+    /// The color of the title of a game involving a favorite team.
+    pub favorite_color: Color,
+    /// The color of the parts of text matching the search.
+    pub highlight_color: Color,
+    /// The color of the border around the focused game.
+    pub border_color: Color,
+    /// The color behind the text of badges overlaid on games.
+    pub badge_color: Color,
+    /// The color behind the text of the badge overlaid on games in progress, which pulses.
+    pub live_color: Color,
+    /// The color of the tiles displayed in place of the games of a day that has not been retrieved.
+    pub placeholder_color: Color,
+    /// The color of the empty part of the paging indicator and progress bars.
+    pub track_color: Color,
+    /// The color of the filled part of the paging indicator and progress bars.
+    pub thumb_color: Color,
+    /// The color behind the background image.
+    pub clear_color: Color,
+    /// The color of the shadow or outline drawn behind text.
+    pub text_effect_color: Color,
+    /// The nominal radius in pixels of the corners of tiles and the focus border, or 0 for square corners.
+    pub corner_radius: f32,
+    /// The path of the font file, or None to use the embedded font.
+    pub font: Option<PathBuf>,
+    /// The path of the font file of bold text, or None to display bold text in the regular font.
+    pub bold_font: Option<PathBuf>,
+    /// The paths, or families of installed fonts, of the fonts displaying characters missing from the font, in order of
+    /// preference.
+    pub fallback_fonts: Vec<String>,
+    /// The path or URL of the background image, or None to use the embedded background.
+    pub background: Option<String>,
+    /// The path or URL of a video or animated image looped as the background in place of the background image, or
+    /// None to display the background image.
+    pub background_video: Option<String>,
+    /// The path of a nine-patch image stretched over the focused game in place of the focus border, or None to draw the
+    /// focus border in the border color.
+    pub focus_frame: Option<PathBuf>,
+    /// The width in pixels of the border of the focus frame image, which keeps its nominal size as the frame stretches.
+    pub focus_frame_inset: f32,
+    /// The effect drawn behind text to keep it readable over bright images.
+    pub text_effect: TextEffect,
+}
+
+impl Theme {
+    /// The theme of light text on a dark background.
+    pub fn dark() -> Self {
+        Theme {
+            text_color: [1.0, 1.0, 1.0, 1.0],
+            secondary_text_color: [0.8, 0.8, 0.8, 1.0],
+            favorite_color: [1.0, 0.84, 0.0, 1.0],
+            highlight_color: [0.4, 0.8, 1.0, 1.0],
+            border_color: [0.5, 0.5, 0.5, 1.0],
+            badge_color: [0.0, 0.0, 0.0, 0.7],
+            live_color: [0.8, 0.1, 0.1, 1.0],
+            placeholder_color: [0.3, 0.3, 0.3, 0.6],
+            track_color: [0.3, 0.3, 0.3, 1.0],
+            thumb_color: [1.0, 1.0, 1.0, 1.0],
+            clear_color: [0.0, 0.0, 0.0, 0.0],
+            text_effect_color: [0.0, 0.0, 0.0, 0.8],
+            corner_radius: 12.0,
+            font: None,
+            bold_font: None,
+            fallback_fonts: DEFAULT_FALLBACK_FONTS.iter().map(|name| (*name).to_owned()).collect(),
+            background: None,
+            background_video: None,
+            focus_frame: None,
+            focus_frame_inset: 16.0,
+            text_effect: TextEffect::None,
+        }
+    }
+
+    /// The theme of dark text on a light background.
+    pub fn light() -> Self {
+        Theme {
+            text_color: [0.05, 0.05, 0.05, 1.0],
+            secondary_text_color: [0.25, 0.25, 0.25, 1.0],
+            favorite_color: [0.7, 0.45, 0.0, 1.0],
+            highlight_color: [0.0, 0.4, 0.8, 1.0],
+            border_color: [0.2, 0.2, 0.2, 1.0],
+            badge_color: [1.0, 1.0, 1.0, 0.8],
+            live_color: [0.85, 0.15, 0.15, 1.0],
+            placeholder_color: [0.7, 0.7, 0.7, 0.6],
+            track_color: [0.7, 0.7, 0.7, 1.0],
+            thumb_color: [0.1, 0.1, 0.1, 1.0],
+            clear_color: [1.0, 1.0, 1.0, 1.0],
+            text_effect_color: [1.0, 1.0, 1.0, 0.8],
+            corner_radius: 12.0,
+            font: None,
+            bold_font: None,
+            fallback_fonts: DEFAULT_FALLBACK_FONTS.iter().map(|name| (*name).to_owned()).collect(),
+            background: None,
+            background_video: None,
+            focus_frame: None,
+            focus_frame_inset: 16.0,
+            text_effect: TextEffect::None,
+        }
+    }
+
+    /// Creates the theme described by the given configuration: its preset, with any configured colors and font
+    /// replacing those of the preset. An unknown preset is logged and the dark theme is used, as is an unknown text
+    /// effect, which is replaced by none.
+    pub fn from_config(config: &ThemeConfig) -> Self {
+        let mut theme = match config.preset.as_str() {
+            "dark" => Theme::dark(),
+            "light" => Theme::light(),
+            other => {
+                error!("Unknown theme preset {}, using dark", other);
+                Theme::dark()
+            }
+        };
+        let mut overrides = [
+            (&mut theme.text_color, config.text_color),
+            (&mut theme.secondary_text_color, config.secondary_text_color),
+            (&mut theme.favorite_color, config.favorite_color),
+            (&mut theme.highlight_color, config.highlight_color),
+            (&mut theme.border_color, config.border_color),
+            (&mut theme.badge_color, config.badge_color),
+            (&mut theme.live_color, config.live_color),
+            (&mut theme.placeholder_color, config.placeholder_color),
+            (&mut theme.track_color, config.track_color),
+            (&mut theme.thumb_color, config.thumb_color),
+            (&mut theme.clear_color, config.clear_color),
+            (&mut theme.text_effect_color, config.text_effect_color),
+        ];
+        for (color, configured) in overrides.iter_mut() {
+            if let Some(configured) = configured {
+                **color = *configured;
+            }
+        }
+        if let Some(corner_radius) = config.corner_radius {
+            theme.corner_radius = corner_radius.max(0.0);
+        }
+        if config.font.is_some() {
+            theme.font = config.font.clone();
+        }
+        theme.bold_font = config.bold_font.clone();
+        if let Some(fallback_fonts) = &config.fallback_fonts {
+            theme.fallback_fonts = fallback_fonts.clone();
+        }
+        theme.background = config.background.clone();
+        theme.background_video = config.background_video.clone();
+        theme.focus_frame = config.focus_frame.clone();
+        if let Some(focus_frame_inset) = config.focus_frame_inset {
+            theme.focus_frame_inset = focus_frame_inset.max(0.0);
+        }
+        if let Some(text_effect) = &config.text_effect {
+            theme.text_effect = match text_effect.as_str() {
+                "none" => TextEffect::None,
+                "shadow" => TextEffect::Shadow,
+                "outline" => TextEffect::Outline,
+                other => {
+                    error!("Unknown text effect {}, using none", other);
+                    TextEffect::None
+                }
+            };
+        }
+        theme
+    }
+
+    /// Loads the fonts of the theme: the regular font, the bold font, and the fallback fonts that are found. A font
+    /// file that cannot be loaded is logged and the embedded font, or for bold text the regular font, is used, while
+    /// fallback fonts that cannot be loaded are skipped.
+    ///
+    /// # Errors
+    /// Panics if the embedded font cannot be loaded.
+    pub fn load_fonts(&self) -> Vec<FontArc> {
+        let regular = self
+            .font
+            .as_ref()
+            .and_then(|path| load_font_file(path))
+            .unwrap_or_else(|| {
+                FontArc::try_from_slice(DEFAULT_FONT).unwrap_or_else(|ex| {
+                    let msg = "Could not load font";
+                    error!("{}:\n{}", msg, ex);
+                    panic!("{}.", msg);
+                })
+            });
+        let bold = self
+            .bold_font
+            .as_ref()
+            .and_then(|path| load_font_file(path))
+            .unwrap_or_else(|| regular.clone());
+        let mut fonts = vec![regular, bold];
+        fonts.extend(self.fallback_fonts.iter().filter_map(|name| load_fallback_font(name)));
+        fonts
+    }
+
+    /// Retrieves the bytes of the background image of the theme from its path or URL. Returns None if no background is
+    /// configured or it cannot be retrieved, which is logged.
+    pub async fn load_background(&self) -> Option<Vec<u8>> {
+        let source = self.background.as_ref()?;
+        let loaded = if source.starts_with("http://") || source.starts_with("https://") {
+            MlbClient::new().get_image(source).await.map(|image| image.bytes)
+        } else {
+            fs::read(source).map_err(Box::from)
+        };
+        match loaded {
+            Ok(bytes) => Some(bytes),
+            Err(ex) => {
+                error!("Could not load background {}, using default:\n{}", source, ex);
+                None
+            }
+        }
+    }
+}
+
+/// Loads the font file at the given path. Returns None if it cannot be loaded, which is logged.
+fn load_font_file(path: &Path) -> Option<FontArc> {
+    fs::read(path)
+        .map_err(|ex| ex.to_string())
+        .and_then(|bytes| FontArc::try_from_vec(bytes).map_err(|ex| ex.to_string()))
+        .map_err(|ex| error!("Could not load font {}, using default:\n{}", path.display(), ex))
+        .ok()
+}
+
+/// Loads the fallback font at the given path, or the installed font of the given family (e.g. "Noto Sans") that best
+/// matches regular text. Returns None if it cannot be found or loaded, which is logged.
+fn load_fallback_font(name: &str) -> Option<FontArc> {
+    let path = Path::new(name);
+    if path.is_file() {
+        return load_font_file(path);
+    }
+    let handle = SystemSource::new()
+        .select_best_match(&[FamilyName::Title(name.to_owned())], &Properties::new())
+        .map_err(|ex| debug!("fallback font {} not found: {:?}", name, ex))
+        .ok()?;
+    let loaded = match handle {
+        Handle::Path { path, font_index } => fs::read(&path)
+            .map_err(|ex| ex.to_string())
+            .and_then(|bytes| FontVec::try_from_vec_and_index(bytes, font_index).map_err(|ex| ex.to_string())),
+        Handle::Memory { bytes, font_index } => {
+            FontVec::try_from_vec_and_index(bytes.to_vec(), font_index).map_err(|ex| ex.to_string())
+        }
+    };
+    loaded
+        .map(FontArc::new)
+        .map_err(|ex| error!("Could not load fallback font {}:\n{}", name, ex))
+        .ok()
+}
+
+/// A container for the theme settings of the configuration file.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ThemeConfig {
+    /// The name of the preset on which the theme is based (dark or light).
+    pub preset: String,
+    pub text_color: Option<Color>,
+    pub secondary_text_color: Option<Color>,
+    pub favorite_color: Option<Color>,
+    pub highlight_color: Option<Color>,
+    pub border_color: Option<Color>,
+    pub badge_color: Option<Color>,
+    pub live_color: Option<Color>,
+    pub placeholder_color: Option<Color>,
+    pub track_color: Option<Color>,
+    pub thumb_color: Option<Color>,
+    pub clear_color: Option<Color>,
+    pub text_effect_color: Option<Color>,
+    /// The nominal radius in pixels of the corners of tiles, or 0 for square corners.
+    pub corner_radius: Option<f32>,
+    /// The path of a TrueType or OpenType font file.
+    pub font: Option<PathBuf>,
+    /// The path of a TrueType or OpenType font file for bold text, such as team names.
+    pub bold_font: Option<PathBuf>,
+    /// The paths of TrueType or OpenType font files, or the families of installed fonts (e.g. "Noto Sans"), displaying
+    /// characters missing from the font, in order of preference.
+    pub fallback_fonts: Option<Vec<String>>,
+    /// The path or http(s) URL of a background image.
+    pub background: Option<String>,
+    /// The path or URL of a video or animated image (e.g. a GIF) to loop as the background, which requires `ffmpeg`.
+    pub background_video: Option<String>,
+    /// The path of a nine-patch image (e.g. a PNG) to stretch over the focused game in place of the focus border.
+    pub focus_frame: Option<PathBuf>,
+    /// The width in pixels of the border of the focus frame image, which is not stretched.
+    pub focus_frame_inset: Option<f32>,
+    /// The effect drawn behind text to keep it readable over bright images (none, shadow, or outline).
+    pub text_effect: Option<String>,
+}
+
+impl Default for ThemeConfig {
+    fn default() -> Self {
+        ThemeConfig {
+            preset: "dark".to_owned(),
+            text_color: None,
+            secondary_text_color: None,
+            favorite_color: None,
+            highlight_color: None,
+            border_color: None,
+            badge_color: None,
+            live_color: None,
+            placeholder_color: None,
+            track_color: None,
+            thumb_color: None,
+            clear_color: None,
+            text_effect_color: None,
+            corner_radius: None,
+            font: None,
+            bold_font: None,
+            fallback_fonts: None,
+            background: None,
+            background_video: None,
+            focus_frame: None,
+            focus_frame_inset: None,
+            text_effect: None,
+        }
+    }
+}
@@ -0,0 +1,70 @@
+//! Recognition of gestures from touchscreen input, so that the UI can be driven on touch-enabled kiosks and tablets.
+
+use crate::gl_utils::FocusDirection;
+use glium::glutin::event::{Touch, TouchPhase};
+use std::collections::HashMap;
+
+/// The distance, as a percentage of the screen, that a finger must travel for a touch to be a swipe rather than a tap.
+const SWIPE_DISTANCE: f32 = 0.05;
+
+/// An enumeration of the gestures recognized from touches.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TouchGesture {
+    /// A finger was lifted near where it touched the screen, at the given point.
+    Tap(f32, f32),
+    /// A finger moved in the given direction after touching the screen at the given point.
+    Swipe(f32, f32, FocusDirection),
+}
+
+/// A tracker of the fingers touching the screen, which recognizes a gesture as each finger is lifted. Points are
+/// percentages of the screen from the top-left corner.
+#[derive(Debug, Default)]
+pub struct TouchTracker {
+    starts: HashMap<u64, (f32, f32)>,
+}
+
+impl TouchTracker {
+    /// Initializes a tracker with no fingers touching the screen.
+    pub fn new() -> Self {
+        TouchTracker::default()
+    }
+
+    /// Records the given touch on a screen of the given dimensions in pixels. Returns the recognized gesture if the
+    /// touch lifted a finger.
+    pub fn update(&mut self, touch: &Touch, screen_width: f32, screen_height: f32) -> Option<TouchGesture> {
+        let point = (
+            touch.location.x as f32 / screen_width,
+            touch.location.y as f32 / screen_height,
+        );
+        match touch.phase {
+            TouchPhase::Started => {
+                self.starts.insert(touch.id, point);
+                None
+            }
+            TouchPhase::Moved => None,
+            TouchPhase::Ended => {
+                let (start_x, start_y) = self.starts.remove(&touch.id)?;
+                let (delta_x, delta_y) = (point.0 - start_x, point.1 - start_y);
+                if delta_x.abs() < SWIPE_DISTANCE && delta_y.abs() < SWIPE_DISTANCE {
+                    return Some(TouchGesture::Tap(start_x, start_y));
+                }
+                let direction = if delta_x.abs() >= delta_y.abs() {
+                    if delta_x > 0.0 {
+                        FocusDirection::Right
+                    } else {
+                        FocusDirection::Left
+                    }
+                } else if delta_y > 0.0 {
+                    FocusDirection::Down
+                } else {
+                    FocusDirection::Up
+                };
+                Some(TouchGesture::Swipe(start_x, start_y, direction))
+            }
+            TouchPhase::Cancelled => {
+                self.starts.remove(&touch.id);
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,196 @@
+//! Interpolation of values over time, used to animate changes in the UI. Animations are advanced once per frame by the
+//! time measured by a `FrameClock` since the previous frame.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// The duration of a focus transition.
+pub const FOCUS_DURATION: Duration = Duration::from_millis(150);
+/// The duration of a row sliding to a new page.
+pub const PAGE_DURATION: Duration = Duration::from_millis(200);
+/// The duration of an image fading in over the image it replaces.
+pub const FADE_DURATION: Duration = Duration::from_millis(300);
+
+/// A clock measuring the time elapsed between frames, by which animations are advanced.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FrameClock {
+    last: Option<Instant>,
+    delta: Duration,
+}
+
+impl FrameClock {
+    /// Initializes a clock that has not measured a frame.
+    pub fn new() -> Self {
+        FrameClock::default()
+    }
+
+    /// Records a frame drawn at the given time, measuring the time elapsed since the previous frame. Nothing has
+    /// elapsed before the first frame.
+    pub fn tick(&mut self, now: Instant) {
+        self.delta = match self.last {
+            Some(last) => now.saturating_duration_since(last),
+            None => Duration::default(),
+        };
+        self.last = Some(now);
+    }
+
+    /// Returns the time elapsed between the previous frame and the current frame.
+    pub fn delta(&self) -> Duration {
+        self.delta
+    }
+}
+
+/// An enumeration of the curves along which a tween progresses.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Easing {
+    /// Start slowly and accelerate towards the target.
+    In,
+    /// Start quickly and decelerate towards the target.
+    Out,
+    /// Accelerate from the start and decelerate towards the target.
+    InOut,
+}
+
+impl Easing {
+    /// Calculates the eased fraction of the way to the target from the given fraction of the elapsed duration.
+    fn apply(self, progress: f32) -> f32 {
+        match self {
+            Easing::In => progress.powi(3),
+            Easing::Out => 1.0 - (1.0 - progress).powi(3),
+            Easing::InOut if progress < 0.5 => 4.0 * progress.powi(3),
+            Easing::InOut => 1.0 - (2.0 - 2.0 * progress).powi(3) / 2.0,
+        }
+    }
+}
+
+/// A value that can be animated by a tween.
+pub trait Interpolate: Copy + PartialEq {
+    /// Calculates the value the given fraction of the way from this value to the given value.
+    fn lerp(self, to: Self, fraction: f32) -> Self;
+}
+
+impl Interpolate for f32 {
+    fn lerp(self, to: Self, fraction: f32) -> Self {
+        self + (to - self) * fraction
+    }
+}
+
+impl Interpolate for (f32, f32) {
+    fn lerp(self, to: Self, fraction: f32) -> Self {
+        (self.0.lerp(to.0, fraction), self.1.lerp(to.1, fraction))
+    }
+}
+
+/// A container for an animation queued to follow the animation in progress.
+struct Segment<T> {
+    to: T,
+    duration: Duration,
+    easing: Easing,
+}
+
+/// A value animated from a starting value to a target value over a fixed duration, optionally followed by a chain of
+/// further animations. Listeners are notified with the final value once the animation and its chain complete.
+pub struct Tween<T: Interpolate = f32> {
+    from: T,
+    to: T,
+    elapsed: Duration,
+    duration: Duration,
+    easing: Easing,
+    /// The duration and curve of animations to new targets, which chained animations may differ from.
+    target_duration: Duration,
+    target_easing: Easing,
+    chained: VecDeque<Segment<T>>,
+    listeners: Vec<Box<dyn FnMut(T)>>,
+}
+
+impl<T: Interpolate> Tween<T> {
+    /// Initializes a tween that is settled at the given value, which animates to new targets over the given duration
+    /// with cubic ease-out.
+    pub fn new(value: T, duration: Duration) -> Self {
+        Tween {
+            from: value,
+            to: value,
+            elapsed: duration,
+            duration,
+            easing: Easing::Out,
+            target_duration: duration,
+            target_easing: Easing::Out,
+            chained: VecDeque::new(),
+            listeners: Vec::new(),
+        }
+    }
+
+    /// Sets the curve along which the tween animates to new targets.
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self.target_easing = easing;
+        self
+    }
+
+    /// Queues an animation to the given target over the given duration along the given curve, to start once the
+    /// animations before it complete.
+    pub fn then(mut self, to: T, duration: Duration, easing: Easing) -> Self {
+        self.chained.push_back(Segment { to, duration, easing });
+        self
+    }
+
+    /// Adds a listener notified with the final value each time the animation and its chain complete.
+    pub fn with_listener(mut self, listener: impl FnMut(T) + 'static) -> Self {
+        self.listeners.push(Box::new(listener));
+        self
+    }
+
+    /// Calculates the value at the current frame.
+    pub fn value(&self) -> T {
+        let progress = if self.duration.as_secs_f32() <= 0.0 {
+            1.0
+        } else {
+            (self.elapsed.as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+        };
+        self.from.lerp(self.to, self.easing.apply(progress))
+    }
+
+    /// Determines whether the animation and its chain have completed.
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration && self.chained.is_empty()
+    }
+
+    /// Animates towards the given target from the current value, so that an interrupted animation continues smoothly
+    /// from where it was, abandoning any chained animations. Does nothing if the target is unchanged.
+    pub fn set_target(&mut self, to: T) {
+        if self.to != to || !self.chained.is_empty() {
+            self.from = self.value();
+            self.to = to;
+            self.elapsed = Duration::default();
+            self.duration = self.target_duration;
+            self.easing = self.target_easing;
+            self.chained.clear();
+        }
+    }
+
+    /// Advances the animation by the given time, such as the delta of a `FrameClock`, continuing into the chained
+    /// animations as each completes. Listeners are notified if the chain completes.
+    pub fn advance(&mut self, delta: Duration) {
+        if self.is_finished() {
+            return;
+        }
+        self.elapsed += delta;
+        while self.elapsed >= self.duration {
+            let next = match self.chained.pop_front() {
+                Some(next) => next,
+                None => break,
+            };
+            self.elapsed -= self.duration;
+            self.from = self.to;
+            self.to = next.to;
+            self.duration = next.duration;
+            self.easing = next.easing;
+        }
+        if self.is_finished() {
+            let value = self.value();
+            for listener in &mut self.listeners {
+                listener(value);
+            }
+        }
+    }
+}
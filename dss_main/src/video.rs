@@ -0,0 +1,132 @@
+//! Playback of videos and animated images into a texture. Decoding is delegated to an `ffmpeg` process, which must be
+//! available on the `PATH`, writing raw frames at native speed to a worker thread. Audio is not played.
+
+use crate::diagnostics::TextureStats;
+use crate::loader::UiEvent;
+use glium::backend::Facade;
+use glium::glutin::event_loop::EventLoopProxy;
+use glium::texture::{RawImage2d, SrgbTexture2d};
+use glium::Rect;
+use log::{debug, error};
+use std::io::{self, Read};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+/// The width in pixels to which video frames are scaled by the decoder.
+const VIDEO_WIDTH: u32 = 1280;
+/// The height in pixels to which video frames are scaled by the decoder.
+const VIDEO_HEIGHT: u32 = 720;
+/// The number of decoded frames that may be waiting to be displayed before the decoder is blocked.
+const FRAME_BUFFER_SIZE: usize = 2;
+
+/// The identifier of the most recently started video player.
+static LAST_PLAYER_ID: AtomicU64 = AtomicU64::new(0);
+
+/// A video being decoded in the background, with its most recent frame available as a texture.
+pub struct VideoPlayer {
+    id: u64,
+    decoder: Child,
+    frames: Receiver<Vec<u8>>,
+    texture: Option<SrgbTexture2d>,
+}
+
+impl VideoPlayer {
+    /// Starts decoding the video at the given URL, from the start again each time it ends if looping. The event loop of
+    /// the given proxy is woken with a `VideoFrame` event for each decoded frame, and a `VideoFinished` event once the
+    /// video has ended or could not be decoded.
+    ///
+    /// # Errors
+    /// * If the decoder process cannot be started.
+    pub fn start(url: &str, looping: bool, proxy: EventLoopProxy<UiEvent>) -> io::Result<Self> {
+        debug!("starting video {}", url);
+        let id = LAST_PLAYER_ID.fetch_add(1, Ordering::SeqCst) + 1;
+        let mut decoder = Command::new("ffmpeg");
+        decoder.args(&["-loglevel", "error", "-re"]);
+        if looping {
+            decoder.args(&["-stream_loop", "-1"]);
+        }
+        let mut decoder = decoder
+            .args(&["-i", url, "-an", "-vf"])
+            .arg(format!("scale={}:{}", VIDEO_WIDTH, VIDEO_HEIGHT))
+            .args(&["-f", "rawvideo", "-pix_fmt", "rgba", "-"])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+        let mut stdout = decoder
+            .stdout
+            .take()
+            .ok_or_else(|| io::Error::from(io::ErrorKind::BrokenPipe))?;
+        let (sender, frames) = mpsc::sync_channel(FRAME_BUFFER_SIZE);
+        thread::Builder::new().name("video".to_owned()).spawn(move || {
+            loop {
+                let mut frame = vec![0; (VIDEO_WIDTH * VIDEO_HEIGHT * 4) as usize];
+                if stdout.read_exact(&mut frame).is_err() || sender.send(frame).is_err() {
+                    break;
+                }
+                if proxy.send_event(UiEvent::VideoFrame).is_err() {
+                    break;
+                }
+            }
+            let _ = proxy.send_event(UiEvent::VideoFinished(id));
+            debug!("ending video {}", id);
+        })?;
+        Ok(VideoPlayer {
+            id,
+            decoder,
+            frames,
+            texture: None,
+        })
+    }
+
+    /// Retrieves the identifier used by the events of this player.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Uploads the most recently decoded frame, if any, and retrieves the texture of the current frame. Returns `None`
+    /// until the first frame has been decoded.
+    pub fn get_texture(&mut self, display: &dyn Facade) -> Option<&SrgbTexture2d> {
+        let mut latest = None;
+        while let Ok(frame) = self.frames.try_recv() {
+            latest = Some(frame);
+        }
+        if let Some(frame) = latest {
+            let image = RawImage2d::from_raw_rgba_reversed(&frame, (VIDEO_WIDTH, VIDEO_HEIGHT));
+            match &self.texture {
+                Some(texture) => texture.write(
+                    Rect {
+                        left: 0,
+                        bottom: 0,
+                        width: VIDEO_WIDTH,
+                        height: VIDEO_HEIGHT,
+                    },
+                    image,
+                ),
+                None => match SrgbTexture2d::new(display, image) {
+                    Ok(texture) => self.texture = Some(texture),
+                    Err(ex) => error!("Could not create video texture:\n{}", ex),
+                },
+            }
+        }
+        self.texture.as_ref()
+    }
+
+    /// Counts the texture of the current frame, if any, in the given statistics.
+    pub fn count_textures(&self, stats: &mut TextureStats) {
+        if let Some(texture) = &self.texture {
+            stats.add(texture);
+        }
+    }
+}
+
+impl Drop for VideoPlayer {
+    fn drop(&mut self) {
+        if let Err(ex) = self.decoder.kill() {
+            debug!("Could not stop video decoder:\n{}", ex);
+        }
+        let _ = self.decoder.wait();
+    }
+}
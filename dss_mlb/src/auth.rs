@@ -0,0 +1,223 @@
+//! Authentication against an MLB account, required before requesting any entitled content.
+
+use crate::transport::Transport;
+use chrono::{DateTime, Duration, Utc};
+use hyper::StatusCode;
+use log::{debug, error};
+use serde_derive::{Deserialize, Serialize};
+use std::cell::RefCell;
+
+/// The URI of the MLB account token endpoint.
+const TOKEN_API: &str = "https://ids.mlb.com/oauth2/aus1m088yK07noBfh356/v1/token";
+/// The public client identifier registered for MLB account logins.
+const CLIENT_ID: &str = "0oa3e1nutA1HLzAKG356";
+/// The scopes requested for every token, including the scope required to receive a refresh token.
+const SCOPE: &str = "openid offline_access";
+/// The number of seconds before expiry at which an access token is considered stale and will be refreshed.
+const EXPIRY_MARGIN_SECS: i64 = 60;
+
+/// A container for the form sent to the token endpoint.
+#[derive(Debug, Serialize)]
+struct TokenRequest<'a> {
+    grant_type: &'a str,
+    client_id: &'a str,
+    scope: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    username: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    password: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    refresh_token: Option<&'a str>,
+}
+
+/// A container for the response of the token endpoint.
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: i64,
+    refresh_token: Option<String>,
+}
+
+/// A container for the tokens of an authenticated MLB account.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MlbTokens {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl MlbTokens {
+    /// Determines whether the access token is expired or about to expire.
+    pub fn is_expired(&self) -> bool {
+        Utc::now() + Duration::seconds(EXPIRY_MARGIN_SECS) >= self.expires_at
+    }
+}
+
+/// A hook for persisting tokens between sessions. Implementations should store tokens securely, e.g. in the
+/// platform keychain.
+pub trait TokenStore {
+    /// Retrieves the previously saved tokens, if any.
+    fn load(&self) -> Option<MlbTokens>;
+    /// Saves the given tokens, replacing any previously saved tokens.
+    fn save(&self, tokens: &MlbTokens);
+    /// Removes any previously saved tokens.
+    fn clear(&self);
+}
+
+/// A token store that only keeps tokens for the lifetime of the process.
+#[derive(Debug, Default)]
+pub struct MemoryTokenStore {
+    tokens: RefCell<Option<MlbTokens>>,
+}
+
+impl TokenStore for MemoryTokenStore {
+    fn load(&self) -> Option<MlbTokens> {
+        self.tokens.borrow().clone()
+    }
+
+    fn save(&self, tokens: &MlbTokens) {
+        *self.tokens.borrow_mut() = Some(tokens.clone());
+    }
+
+    fn clear(&self) {
+        *self.tokens.borrow_mut() = None;
+    }
+}
+
+/// The manager of an MLB account session, responsible for exchanging credentials and refreshing tokens.
+pub struct MlbAuth<S: TokenStore> {
+    store: S,
+    transport: Transport,
+}
+
+impl<S: TokenStore> MlbAuth<S> {
+    /// Initializes the session manager with the given token store. Any tokens already in the store are reused.
+    pub fn new(store: S) -> Self {
+        MlbAuth {
+            store,
+            transport: Transport::default(),
+        }
+    }
+
+    /// Determines whether the store holds tokens from a previous login.
+    pub fn is_logged_in(&self) -> bool {
+        self.store.load().is_some()
+    }
+
+    /// Exchanges the given account credentials for tokens and saves them to the store.
+    ///
+    /// # Errors
+    /// * If the token endpoint cannot be reached.
+    /// * If the credentials are rejected.
+    /// * If the response cannot be deserialized into the expected JSON object.
+    pub async fn login(&self, username: &str, password: &str) -> Result<(), Box<dyn std::error::Error>> {
+        debug!("starting login");
+        let request = TokenRequest {
+            grant_type: "password",
+            client_id: CLIENT_ID,
+            scope: SCOPE,
+            username: Some(username),
+            password: Some(password),
+            refresh_token: None,
+        };
+        let tokens = self
+            .request_tokens(&request)
+            .await?
+            .map_err(|status| format!("Login rejected with status {}", status))?;
+        self.store.save(&tokens);
+        debug!("ending login");
+        Ok(())
+    }
+
+    /// Exchanges the saved refresh token for new tokens and saves them to the store.
+    ///
+    /// # Errors
+    /// * If there is no saved refresh token.
+    /// * If the token endpoint cannot be reached or fails, in which case the saved tokens are kept to be refreshed again.
+    /// * If the refresh token is rejected, in which case the saved tokens are cleared.
+    pub async fn refresh(&self) -> Result<(), Box<dyn std::error::Error>> {
+        debug!("starting refresh");
+        let saved = self.store.load().ok_or("Not logged in")?;
+        let refresh_token = saved.refresh_token.ok_or("No refresh token available")?;
+        let request = TokenRequest {
+            grant_type: "refresh_token",
+            client_id: CLIENT_ID,
+            scope: SCOPE,
+            username: None,
+            password: None,
+            refresh_token: Some(&refresh_token),
+        };
+        match self.request_tokens(&request).await {
+            Ok(Ok(mut tokens)) => {
+                if tokens.refresh_token.is_none() {
+                    tokens.refresh_token = Some(refresh_token);
+                }
+                self.store.save(&tokens);
+                debug!("ending refresh");
+                Ok(())
+            }
+            Ok(Err(status)) => {
+                error!("Refresh token rejected with status {}, logging out", status);
+                self.store.clear();
+                Err(format!("Refresh token rejected with status {}", status).into())
+            }
+            Err(ex) => {
+                error!("Error while refreshing tokens, keeping saved tokens:\n{}", ex);
+                Err(ex)
+            }
+        }
+    }
+
+    /// Retrieves a valid access token, refreshing the saved tokens first if they have expired.
+    ///
+    /// # Errors
+    /// * If not logged in.
+    /// * If the tokens have expired and could not be refreshed.
+    pub async fn access_token(&self) -> Result<String, Box<dyn std::error::Error>> {
+        let tokens = self.store.load().ok_or("Not logged in")?;
+        if !tokens.is_expired() {
+            return Ok(tokens.access_token);
+        }
+        self.refresh().await?;
+        Ok(self.store.load().ok_or("Not logged in")?.access_token)
+    }
+
+    /// Discards the saved tokens.
+    pub fn logout(&self) {
+        self.store.clear();
+    }
+
+    /// Posts the given form to the token endpoint. Returns the issued tokens, or the status with which the endpoint
+    /// rejected the credentials or refresh token of the form.
+    ///
+    /// # Errors
+    /// * If the form cannot be encoded.
+    /// * If the token endpoint cannot be reached.
+    /// * If the token endpoint responds with any other error status.
+    /// * If the response cannot be deserialized into the expected JSON object.
+    async fn request_tokens(
+        &self,
+        form: &TokenRequest<'_>,
+    ) -> Result<Result<MlbTokens, StatusCode>, Box<dyn std::error::Error>> {
+        let body = serde_urlencoded::to_string(form)?;
+        let (status, text_buf) = self.transport.post_form(TOKEN_API, body).await?;
+        if is_rejection(status) {
+            return Ok(Err(status));
+        }
+        if !status.is_success() {
+            return Err(format!("Token request failed with status {}", status).into());
+        }
+        let response = serde_json::from_slice::<TokenResponse>(&text_buf)?;
+        Ok(Ok(MlbTokens {
+            access_token: response.access_token,
+            refresh_token: response.refresh_token,
+            expires_at: Utc::now() + Duration::seconds(response.expires_in),
+        }))
+    }
+}
+
+/// Determines whether the given status of the token endpoint rejects the credentials or refresh token sent to it, as
+/// an `invalid_grant` or `invalid_client` error, rather than reporting a failure that may not recur.
+fn is_rejection(status: StatusCode) -> bool {
+    status == StatusCode::BAD_REQUEST || status == StatusCode::UNAUTHORIZED
+}
@@ -0,0 +1,237 @@
+//! In-memory caching of retrieved days, governed by a policy that expires each class of data independently. Days can
+//! also be persisted to a directory, so that they survive restarts of the client.
+
+use crate::{MlbGameClientInfo, MlbGameState, DATE_FORMAT};
+use chrono::{DateTime, NaiveDate, Utc};
+use log::error;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// The policy governing how long each class of cached data is considered fresh. A time-to-live of None means the data
+/// never expires and is only refreshed when explicitly invalidated. Days are classified when they are retrieved.
+#[derive(Clone, Debug)]
+pub struct CachePolicy {
+    /// The time-to-live of days that were complete when retrieved: days that had already ended, or whose games were all
+    /// final, and so will not change.
+    pub complete_ttl: Option<Duration>,
+    /// The time-to-live of days that were not complete when retrieved, without any games in progress.
+    pub pending_ttl: Option<Duration>,
+    /// The time-to-live of any day with a game in progress.
+    pub live_ttl: Option<Duration>,
+}
+
+impl Default for CachePolicy {
+    fn default() -> Self {
+        CachePolicy {
+            complete_ttl: None,
+            pending_ttl: Some(Duration::from_secs(300)),
+            live_ttl: Some(Duration::from_secs(30)),
+        }
+    }
+}
+
+impl CachePolicy {
+    /// A policy under which nothing is cached.
+    pub fn disabled() -> Self {
+        CachePolicy {
+            complete_ttl: Some(Duration::from_secs(0)),
+            pending_ttl: Some(Duration::from_secs(0)),
+            live_ttl: Some(Duration::from_secs(0)),
+        }
+    }
+
+    /// Determines the time-to-live of the given cached day.
+    fn ttl(&self, cached: &CachedDay) -> Option<Duration> {
+        if cached.games.iter().any(|game| game.state == MlbGameState::Live) {
+            self.live_ttl
+        } else if cached.complete {
+            self.complete_ttl
+        } else {
+            self.pending_ttl
+        }
+    }
+}
+
+/// Determines whether the given games of the given date, retrieved on the given date, are complete and will not
+/// change: no game is in progress, and either the day had already ended or every game is final.
+fn is_complete(date: NaiveDate, today: NaiveDate, games: &[MlbGameClientInfo]) -> bool {
+    let all_final = !games.is_empty() && games.iter().all(|game| game.state == MlbGameState::Final);
+    let live = games.iter().any(|game| game.state == MlbGameState::Live);
+    !live && (date < today || all_final)
+}
+
+/// A container for the number of requests for days answered from the cache and the number that had to be retrieved.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CacheStats {
+    /// The number of requested days that were cached and still fresh.
+    pub hits: u64,
+    /// The number of requested days that were not cached or had expired.
+    pub misses: u64,
+}
+
+impl CacheStats {
+    /// Determines the fraction of requested days that were cached and still fresh, or None if no day was requested.
+    pub fn hit_rate(&self) -> Option<f32> {
+        match self.hits + self.misses {
+            0 => None,
+            total => Some(self.hits as f32 / total as f32),
+        }
+    }
+}
+
+/// A container for the games of a single cached day.
+#[derive(Debug)]
+struct CachedDay {
+    fetched_at: Instant,
+    /// Whether the day was complete when retrieved.
+    complete: bool,
+    games: Vec<MlbGameClientInfo>,
+}
+
+/// A container for the games of a single day persisted to disk.
+#[derive(Debug, Deserialize, Serialize)]
+struct PersistedDay {
+    fetched_at: DateTime<Utc>,
+    /// Whether the day was complete when retrieved, which is false for days persisted before it was recorded.
+    #[serde(default)]
+    complete: bool,
+    games: Vec<MlbGameClientInfo>,
+}
+
+/// A cache of retrieved days, shared between clones of a client.
+#[derive(Debug, Default)]
+pub(crate) struct DayCache {
+    policy: Mutex<CachePolicy>,
+    days: Mutex<BTreeMap<NaiveDate, CachedDay>>,
+    directory: Mutex<Option<PathBuf>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl DayCache {
+    /// Replaces the policy governing the freshness of cached days.
+    pub fn set_policy(&self, policy: CachePolicy) {
+        *self.policy.lock().unwrap() = policy;
+    }
+
+    /// Sets the directory to which retrieved days are persisted.
+    pub fn set_directory(&self, directory: PathBuf) {
+        *self.directory.lock().unwrap() = Some(directory);
+    }
+
+    /// Retrieves the games of the given date if they are cached and still fresh, counting the request as a hit or miss.
+    pub fn get(&self, date: NaiveDate) -> Option<Vec<MlbGameClientInfo>> {
+        let games = self.get_fresh(date);
+        let counter = if games.is_some() { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+        games
+    }
+
+    /// Determines the number of requests answered from the cache and the number that missed it.
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Retrieves the games of the given date if they are cached and still fresh. Days that are not in memory are
+    /// loaded from the cache directory, if one is set.
+    fn get_fresh(&self, date: NaiveDate) -> Option<Vec<MlbGameClientInfo>> {
+        let policy = self.policy.lock().unwrap();
+        let mut days = self.days.lock().unwrap();
+        if let Entry::Vacant(entry) = days.entry(date) {
+            let directory = self.directory.lock().unwrap();
+            entry.insert(load_day(directory.as_ref()?, date)?);
+        }
+        let cached = days.get(&date)?;
+        match policy.ttl(cached) {
+            Some(ttl) if cached.fetched_at.elapsed() >= ttl => None,
+            _ => Some(cached.games.clone()),
+        }
+    }
+
+    /// Caches the given games of the given date, retrieved on the given date, persisting them if a cache directory is
+    /// set.
+    pub fn insert(&self, date: NaiveDate, today: NaiveDate, games: Vec<MlbGameClientInfo>) {
+        let complete = is_complete(date, today, &games);
+        if let Some(directory) = self.directory.lock().unwrap().as_ref() {
+            if let Err(ex) = save_day(directory, date, complete, &games) {
+                error!("Error while persisting {}:\n{}", date, ex);
+            }
+        }
+        let cached = CachedDay {
+            fetched_at: Instant::now(),
+            complete,
+            games,
+        };
+        self.days.lock().unwrap().insert(date, cached);
+    }
+
+    /// Removes the given date from the cache, forcing it to be retrieved on the next request.
+    pub fn invalidate(&self, date: NaiveDate) {
+        if let Some(directory) = self.directory.lock().unwrap().as_ref() {
+            let _ = fs::remove_file(day_path(directory, date));
+        }
+        self.days.lock().unwrap().remove(&date);
+    }
+
+    /// Removes every date from the cache.
+    pub fn clear(&self) {
+        let mut days = self.days.lock().unwrap();
+        if let Some(directory) = self.directory.lock().unwrap().as_ref() {
+            for date in days.keys() {
+                let _ = fs::remove_file(day_path(directory, *date));
+            }
+        }
+        days.clear();
+    }
+}
+
+/// Determines the path of the file in which the given date is persisted.
+fn day_path(directory: &Path, date: NaiveDate) -> PathBuf {
+    directory.join(format!("{}.json", date.format(DATE_FORMAT)))
+}
+
+/// Loads the given date from the given directory. The time at which it was retrieved is carried over, so that the
+/// cache policy applies across restarts.
+fn load_day(directory: &Path, date: NaiveDate) -> Option<CachedDay> {
+    let bytes = fs::read(day_path(directory, date)).ok()?;
+    let persisted = serde_json::from_slice::<PersistedDay>(&bytes)
+        .map_err(|ex| error!("Error while loading {}:\n{}", date, ex))
+        .ok()?;
+    let age = (Utc::now() - persisted.fetched_at).to_std().unwrap_or_default();
+    Some(CachedDay {
+        fetched_at: Instant::now().checked_sub(age)?,
+        complete: persisted.complete,
+        games: persisted.games,
+    })
+}
+
+/// Persists the given games of the given date to the given directory, with whether the day was complete.
+///
+/// # Errors
+/// * If the directory cannot be created.
+/// * If the games cannot be serialized.
+/// * If the file cannot be written.
+fn save_day(
+    directory: &Path,
+    date: NaiveDate,
+    complete: bool,
+    games: &[MlbGameClientInfo],
+) -> Result<(), Box<dyn std::error::Error>> {
+    fs::create_dir_all(directory)?;
+    let persisted = PersistedDay {
+        fetched_at: Utc::now(),
+        complete,
+        games: games.to_vec(),
+    };
+    fs::write(day_path(directory, date), serde_json::to_vec(&persisted)?)?;
+    Ok(())
+}
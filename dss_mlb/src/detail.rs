@@ -0,0 +1,243 @@
+//! Retrieval of the recap article and line score of an MLB game, for use by a detail view.
+
+use crate::transport::Transport;
+use log::debug;
+use serde_derive::{Deserialize, Serialize};
+
+/// The URI of the MLB game content API. The game identifier needs to be substituted for `{}`.
+const CONTENT_API: &str = "https://statsapi.mlb.com/api/v1/game/{}/content";
+/// The URI of the MLB line score API. The game identifier needs to be substituted for `{}`.
+const LINESCORE_API: &str = "https://statsapi.mlb.com/api/v1/game/{}/linescore";
+/// The titles of the alternate program guide entries containing recap videos, in order of preference.
+const RECAP_VIDEO_TITLES: [&str; 2] = ["Extended Highlights", "Daily Recap"];
+/// The name of the preferred playback of a recap video.
+const RECAP_PLAYBACK_NAME: &str = "mp4Avc";
+
+/// A container for the content of an MLB game.
+#[derive(Debug, Deserialize, Serialize)]
+struct MlbGameContentDetail {
+    editorial: Option<MlbEditorialDetail>,
+    media: Option<MlbMediaDetail>,
+}
+
+/// A container for the media of an MLB game.
+#[derive(Debug, Deserialize, Serialize)]
+struct MlbMediaDetail {
+    #[serde(default)]
+    epgAlternate: Vec<MlbVideoEntry>,
+}
+
+/// A container for a group of videos in the alternate program guide of an MLB game.
+#[derive(Debug, Deserialize, Serialize)]
+struct MlbVideoEntry {
+    title: String,
+    #[serde(default)]
+    items: Vec<MlbVideoItem>,
+}
+
+/// A container for a single video in the alternate program guide of an MLB game.
+#[derive(Debug, Deserialize, Serialize)]
+struct MlbVideoItem {
+    #[serde(default)]
+    playbacks: Vec<MlbVideoPlayback>,
+}
+
+/// A container for a single encoding of a video.
+#[derive(Debug, Deserialize, Serialize)]
+struct MlbVideoPlayback {
+    name: String,
+    url: String,
+}
+
+/// A container for the editorial content of an MLB game.
+#[derive(Debug, Deserialize, Serialize)]
+struct MlbEditorialDetail {
+    recap: Option<MlbRecapDetail>,
+}
+
+/// A container for the recap articles of an MLB game.
+#[derive(Debug, Deserialize, Serialize)]
+struct MlbRecapDetail {
+    mlb: Option<MlbArticleDetail>,
+}
+
+/// A container for the text of an article pertaining to an MLB game.
+#[derive(Debug, Deserialize, Serialize)]
+struct MlbArticleDetail {
+    headline: String,
+    #[serde(default)]
+    body: String,
+}
+
+/// A container for the response of the line score API.
+#[derive(Debug, Deserialize, Serialize)]
+struct MlbLinescoreInfo {
+    #[serde(default)]
+    innings: Vec<MlbInningInfo>,
+    teams: Option<MlbLinescoreTeams>,
+}
+
+/// A container for the runs scored by each team in a single inning.
+#[derive(Debug, Deserialize, Serialize)]
+struct MlbInningInfo {
+    num: u32,
+    away: MlbLineInfo,
+    home: MlbLineInfo,
+}
+
+/// A container for the line score totals of the two teams involved in an MLB game.
+#[derive(Debug, Deserialize, Serialize)]
+struct MlbLinescoreTeams {
+    away: MlbLineInfo,
+    home: MlbLineInfo,
+}
+
+/// A container for the runs, hits, and errors of a team over an inning or a game.
+#[derive(Debug, Deserialize, Serialize)]
+struct MlbLineInfo {
+    runs: Option<u32>,
+    hits: Option<u32>,
+    errors: Option<u32>,
+}
+
+/// A container for the runs, hits, and errors of a team over a game.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct MlbLineTotals {
+    pub runs: u32,
+    pub hits: u32,
+    pub errors: u32,
+}
+
+/// A container for the runs scored by each team in a single inning. Runs are absent for half innings that have not
+/// been played.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MlbInningScore {
+    pub inning: u32,
+    pub away_runs: Option<u32>,
+    pub home_runs: Option<u32>,
+}
+
+/// A container for the line score of an MLB game.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct MlbLinescore {
+    pub innings: Vec<MlbInningScore>,
+    pub away: MlbLineTotals,
+    pub home: MlbLineTotals,
+}
+
+/// A container for information used by the client to display the details of a single MLB game.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct MlbGameDetail {
+    pub recap_headline: Option<String>,
+    pub recap_body: Option<String>,
+    pub recap_video_url: Option<String>,
+    pub linescore: Option<MlbLinescore>,
+}
+
+/// Retrieves the recap article and line score of the game with the given identifier.
+///
+/// # Errors
+/// * If either URL cannot be reached.
+/// * If data cannot be read from either GET response.
+/// * If the data cannot be deserialized into the expected JSON objects.
+pub(crate) async fn get_game_detail(
+    game_pk: u64,
+    transport: &Transport,
+) -> Result<MlbGameDetail, Box<dyn std::error::Error>> {
+    debug!("starting get_game_detail for {}", game_pk);
+    let game_pk = game_pk.to_string();
+    let (content_buf, linescore_buf) = futures::future::join(
+        transport.get(&CONTENT_API.replace("{}", &game_pk)),
+        transport.get(&LINESCORE_API.replace("{}", &game_pk)),
+    )
+    .await;
+    let content = serde_json::from_slice::<MlbGameContentDetail>(&content_buf?)?;
+    let linescore = serde_json::from_slice::<MlbLinescoreInfo>(&linescore_buf?)?;
+
+    let recap_video_url = content
+        .media
+        .and_then(|media| extract_recap_video_url(media.epgAlternate));
+    let article = content
+        .editorial
+        .and_then(|editorial| editorial.recap)
+        .and_then(|recap| recap.mlb);
+    let (recap_headline, recap_body) = match article {
+        Some(article) => (
+            Some(article.headline),
+            Some(strip_markup(&article.body)).filter(|body| !body.is_empty()),
+        ),
+        None => (None, None),
+    };
+    let innings = linescore.innings;
+    let linescore = linescore.teams.map(|teams| MlbLinescore {
+        innings: innings
+            .iter()
+            .map(|inning| MlbInningScore {
+                inning: inning.num,
+                away_runs: inning.away.runs,
+                home_runs: inning.home.runs,
+            })
+            .collect(),
+        away: line_totals(&teams.away),
+        home: line_totals(&teams.home),
+    });
+    debug!("ending get_game_detail for {}", game_pk);
+    Ok(MlbGameDetail {
+        recap_headline,
+        recap_body,
+        recap_video_url,
+        linescore,
+    })
+}
+
+/// Extracts the URL of the preferred recap video from the given program guide entries. The preferred playback is
+/// used if available, otherwise the first playback of the video.
+fn extract_recap_video_url(mut entries: Vec<MlbVideoEntry>) -> Option<String> {
+    entries.retain(|entry| RECAP_VIDEO_TITLES.contains(&entry.title.as_str()));
+    entries.sort_by_key(|entry| RECAP_VIDEO_TITLES.iter().position(|title| *title == entry.title));
+    let playbacks = entries
+        .into_iter()
+        .flat_map(|entry| entry.items)
+        .map(|item| item.playbacks)
+        .find(|playbacks| !playbacks.is_empty())?;
+    let index = playbacks
+        .iter()
+        .position(|playback| playback.name == RECAP_PLAYBACK_NAME)
+        .unwrap_or(0);
+    playbacks.into_iter().nth(index).map(|playback| playback.url)
+}
+
+/// Converts the given line information into totals, treating missing values as zero.
+fn line_totals(line: &MlbLineInfo) -> MlbLineTotals {
+    MlbLineTotals {
+        runs: line.runs.unwrap_or(0),
+        hits: line.hits.unwrap_or(0),
+        errors: line.errors.unwrap_or(0),
+    }
+}
+
+/// Removes the HTML markup from the given article body, separating paragraphs with blank lines.
+fn strip_markup(body: &str) -> String {
+    let body = body
+        .replace("</p>", "\n\n")
+        .replace("<br>", "\n")
+        .replace("<br/>", "\n");
+    let mut text = String::with_capacity(body.len());
+    let mut in_tag = false;
+    for c in body.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => (),
+        }
+    }
+    text.replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .trim()
+        .to_owned()
+}
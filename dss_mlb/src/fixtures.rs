@@ -0,0 +1,135 @@
+//! Recording and replaying of HTTP responses, so that parsing and the data layer can be exercised deterministically
+//! without reaching the live MLB API.
+
+use log::debug;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The maximum number of URL characters kept in a fixture file name, before the URL hash.
+const MAX_NAME_PREFIX: usize = 100;
+
+/// An enumeration of the ways fixtures can be used by a client.
+#[derive(Clone, Debug)]
+pub enum FixtureMode {
+    /// Responses from the live API are written to the given directory.
+    Record(PathBuf),
+    /// Responses are read from the given directory instead of the live API.
+    Replay(PathBuf),
+}
+
+/// Writes the given response of the given URL to the fixture directory.
+///
+/// # Errors
+/// If the directory or file cannot be written.
+pub(crate) fn record(dir: &Path, url: &str, bytes: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
+    fs::create_dir_all(dir)?;
+    let path = dir.join(fixture_name(url));
+    debug!("recording {} to {}", url, path.display());
+    fs::write(path, bytes)?;
+    Ok(())
+}
+
+/// Reads the recorded response of the given URL from the fixture directory.
+///
+/// # Errors
+/// If no response has been recorded for the URL.
+pub(crate) fn replay(dir: &Path, url: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let path = dir.join(fixture_name(url));
+    debug!("replaying {} from {}", url, path.display());
+    fs::read(&path).map_err(|ex| format!("No fixture recorded for {} at {}: {}", url, path.display(), ex).into())
+}
+
+/// Derives a stable file name for the given URL. The readable prefix is followed by an FNV-1a hash of the entire URL
+/// so that long URLs sharing a prefix do not collide.
+fn fixture_name(url: &str) -> String {
+    let prefix: String = url
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(MAX_NAME_PREFIX)
+        .collect();
+    let hash = url.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{}-{:016x}", prefix, hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::{MemoryTokenStore, MlbAuth};
+    use crate::media::MlbFeedAvailability;
+    use crate::{MlbClient, MlbGameState};
+    use chrono::NaiveDate;
+
+    /// Determines the directory of the fixtures recorded with the source tree.
+    fn recorded_dir() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures")
+    }
+
+    #[test]
+    fn replays_games_in_range() {
+        let client = MlbClient::new()
+            .with_lazy_images()
+            .with_fixtures(FixtureMode::Replay(recorded_dir()));
+        let start = NaiveDate::from_ymd(2020, 8, 20);
+        let end = NaiveDate::from_ymd(2020, 8, 21);
+        let days = futures::executor::block_on(client.get_games_in_range(start..=end)).unwrap();
+        assert_eq!(days.keys().copied().collect::<Vec<_>>(), vec![start, end]);
+
+        let first = &days[&start];
+        assert_eq!(first.len(), 2);
+        assert_eq!(first[0].game_pk, 631377);
+        assert_eq!(first[0].title, "New York Yankees at Tampa Bay Rays");
+        assert_eq!(first[0].state, MlbGameState::Final);
+        assert_eq!((first[0].away_score, first[0].home_score), (Some(2), Some(4)));
+        assert_eq!(first[0].summary, "Rays hold off Yankees behind bullpen");
+        assert_eq!(first[0].series_description().as_deref(), Some("Game 4 of 4"));
+        assert_eq!(
+            first[0].post_game.as_ref().and_then(|post| post.duration_minutes),
+            Some(185)
+        );
+        assert!(first[0].image.is_none() && first[0].image_url.is_some());
+        assert_eq!(first[1].detailed_state, "Postponed");
+        assert!(first[1].image_url.is_none() && first[1].summary.starts_with("Live "));
+
+        let second = &days[&end];
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].state, MlbGameState::Scheduled);
+        assert_eq!(second[0].away_pitcher.as_deref(), Some("Tyler Glasnow"));
+        assert_eq!(second[0].home_pitcher.as_deref(), Some("Dylan Bundy"));
+        assert_eq!(second[0].summary, "Rays open set in Baltimore");
+    }
+
+    #[test]
+    fn replays_media_feeds() {
+        let client = MlbClient::new().with_fixtures(FixtureMode::Replay(recorded_dir()));
+        let auth: Option<&MlbAuth<MemoryTokenStore>> = None;
+        let feeds = futures::executor::block_on(client.get_media_feeds(631377, auth)).unwrap();
+        assert_eq!(feeds.len(), 2);
+        assert_eq!(
+            (feeds[0].broadcast.as_str(), feeds[0].feed_type.as_str()),
+            ("FSSUN", "HOME")
+        );
+        assert_eq!(feeds[0].availability, MlbFeedAvailability::RequiresLogin);
+        assert!(feeds[0].requires_subscription && feeds[0].playback_url.is_none());
+        assert_eq!(feeds[1].availability, MlbFeedAvailability::Off);
+    }
+
+    #[test]
+    fn unrecorded_url_is_an_error() {
+        let client = MlbClient::new()
+            .with_lazy_images()
+            .with_fixtures(FixtureMode::Replay(recorded_dir()));
+        let day = NaiveDate::from_ymd(2020, 8, 22);
+        let result = futures::executor::block_on(client.get_games_in_range(day..=day));
+        assert!(result.unwrap_err().to_string().starts_with("No fixture recorded for"));
+    }
+}
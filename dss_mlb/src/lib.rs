@@ -2,19 +2,40 @@
 
 //! Controller that queries an MLB API and provides data to be used by a client to display MLB game editorials.
 
+pub mod auth;
+mod cache;
+pub mod detail;
+#[cfg(feature = "fixtures")]
+pub mod fixtures;
+pub mod media;
+pub mod standings;
+mod transport;
+
 use chrono::{DateTime, Duration, Local, NaiveDate, TimeZone, Utc};
-use hyper::client::HttpConnector;
-use hyper::{Body, Client};
-use hyper_tls::HttpsConnector;
+use dss_core::{ProviderError, Rail, RailEntry, ScheduleProvider};
+use futures::future::{FutureExt, LocalBoxFuture};
+use futures::stream::{Stream, StreamExt};
 use log::{debug, error};
 use serde_derive::{Deserialize, Serialize};
+use std::cell::Cell;
 use std::collections::BTreeMap;
 use std::fmt::Display;
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+use transport::Transport;
+
+pub use cache::{CachePolicy, CacheStats};
 
-/// The URI of the MLB API. A date in the format YYYY-mm-dd needs to be appended to the end of the value to
-/// obtain data.
+/// The URI of the MLB API. A `startDate` and `endDate` in the format YYYY-mm-dd need to be appended to the end of the
+/// value to obtain data.
 const GAME_API: &str =
-    "http://statsapi.mlb.com/api/v1/schedule?hydrate=game(content(editorial(recap))),decisions&sportId=1&date=";
+    "http://statsapi.mlb.com/api/v1/schedule?hydrate=game(content(editorial(preview,recap))),decisions,gameInfo,probablePitcher&sportId=1";
+/// The format of dates used by the MLB API.
+const DATE_FORMAT: &str = "%Y-%m-%d";
+/// The number of days retrieved in a single request by a stream of games.
+const STREAM_PAGE_DAYS: i64 = 7;
+/// The maximum number of pages requested concurrently by a stream of games.
+const STREAM_CONCURRENCY: usize = 4;
 
 /// A container for MLB game information over a range of dates.
 #[derive(Debug, Deserialize, Serialize)]
@@ -25,17 +46,38 @@ struct MlbGameRange {
 /// A container for information about all MLB games on a specific date.
 #[derive(Debug, Deserialize, Serialize)]
 struct MlbGameDateInfo {
+    date: String,
     games: Vec<MlbGameInfo>,
 }
 
 /// A container for information about an MLB game.
 #[derive(Debug, Deserialize, Serialize)]
 struct MlbGameInfo {
+    gamePk: u64,
     gameDate: String,
+    seriesGameNumber: Option<u32>,
+    gamesInSeries: Option<u32>,
+    status: MlbGameStatus,
+    gameInfo: Option<MlbGameDetailInfo>,
     teams: MlbGameTeams,
     content: MlbGameContent,
 }
 
+/// A container for the progress of an MLB game.
+#[derive(Debug, Deserialize, Serialize)]
+struct MlbGameStatus {
+    abstractGameState: String,
+    detailedState: String,
+}
+
+/// A container for details about the conditions of an MLB game.
+#[derive(Debug, Deserialize, Serialize)]
+struct MlbGameDetailInfo {
+    attendance: Option<u32>,
+    firstPitch: Option<String>,
+    gameDurationMinutes: Option<u32>,
+}
+
 /// A container for information about the two teams involved in an MLB game.
 #[derive(Debug, Deserialize, Serialize)]
 struct MlbGameTeams {
@@ -47,6 +89,14 @@ struct MlbGameTeams {
 #[derive(Debug, Deserialize, Serialize)]
 struct MlbGameTeamInfo {
     team: MlbTeamInfo,
+    score: Option<u32>,
+    probablePitcher: Option<MlbPersonInfo>,
+}
+
+/// A container for static information about a person, such as a player.
+#[derive(Debug, Deserialize, Serialize)]
+struct MlbPersonInfo {
+    fullName: String,
 }
 
 /// A container for static information about an MLB team.
@@ -64,7 +114,8 @@ struct MlbGameContent {
 /// A container for information about media pertaining to an MLB game.
 #[derive(Debug, Deserialize, Serialize)]
 struct MlbGameEditorial {
-    recap: MlbGameRecap,
+    preview: Option<MlbGameRecap>,
+    recap: Option<MlbGameRecap>,
 }
 
 /// A container for information about media pertaining to an MLB game.
@@ -92,100 +143,521 @@ struct MlbImageCuts {
     src: String,
 }
 
+/// An enumeration of the stages of an MLB game.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum MlbGameState {
+    Scheduled,
+    Live,
+    Final,
+}
+
+impl MlbGameState {
+    /// Converts the abstract game state reported by the MLB API.
+    fn from_abstract(state: &str) -> Self {
+        match state {
+            "Live" => MlbGameState::Live,
+            "Final" => MlbGameState::Final,
+            _ => MlbGameState::Scheduled,
+        }
+    }
+}
+
+/// An enumeration of the encodings an image of an MLB game may use.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum MlbImageFormat {
+    Jpeg,
+    Png,
+    Webp,
+    Gif,
+    Unknown,
+}
+
+impl MlbImageFormat {
+    /// Detects the format of the given image bytes from their leading magic bytes, falling back to the extension of
+    /// the given URL.
+    pub fn detect(bytes: &[u8], url: &str) -> Self {
+        if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+            MlbImageFormat::Jpeg
+        } else if bytes.starts_with(&[0x89, b'P', b'N', b'G']) {
+            MlbImageFormat::Png
+        } else if bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+            MlbImageFormat::Webp
+        } else if bytes.starts_with(b"GIF8") {
+            MlbImageFormat::Gif
+        } else {
+            let path = url.split(&['?', '#'][..]).next().unwrap_or(url).to_lowercase();
+            if path.ends_with(".jpg") || path.ends_with(".jpeg") {
+                MlbImageFormat::Jpeg
+            } else if path.ends_with(".png") {
+                MlbImageFormat::Png
+            } else if path.ends_with(".webp") {
+                MlbImageFormat::Webp
+            } else if path.ends_with(".gif") {
+                MlbImageFormat::Gif
+            } else {
+                MlbImageFormat::Unknown
+            }
+        }
+    }
+}
+
+/// A container for the raw bytes of an image and the format they are encoded in.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MlbImage {
+    pub bytes: Vec<u8>,
+    pub format: MlbImageFormat,
+}
+
+/// An enumeration of problems encountered while retrieving a single game, which left its information incomplete.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum MlbGameWarning {
+    /// The image could not be retrieved; the image can be retried with `MlbClient::retry_image`.
+    ImageUnavailable(String),
+    /// The start time of the game could not be parsed.
+    StartTimeUnavailable(String),
+}
+
+/// A container for information used by the client to summarize a completed MLB game.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MlbPostGameInfo {
+    pub attendance: Option<u32>,
+    pub duration_minutes: Option<u32>,
+    pub first_pitch: Option<DateTime<Utc>>,
+}
+
+/// A container for the progress of a retrieval of games, reported as each game's information is completed.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct MlbLoadProgress {
+    pub completed: usize,
+    pub total: usize,
+}
+
+/// A container for information used by the client to display all MLB games on a specific date.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DayGames {
+    pub date: NaiveDate,
+    pub games: Vec<MlbGameClientInfo>,
+}
+
 /// A container for information used by the client to display an MLB game entry.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct MlbGameClientInfo {
+    pub game_pk: u64,
+    pub state: MlbGameState,
+    pub detailed_state: String,
     pub title: String,
-    pub image: Option<Vec<u8>>,
+    pub away_team: String,
+    pub home_team: String,
+    pub away_score: Option<u32>,
+    pub home_score: Option<u32>,
+    pub image: Option<MlbImage>,
+    pub image_url: Option<String>,
     pub summary: String,
+    pub series_game_number: Option<u32>,
+    pub games_in_series: Option<u32>,
+    pub post_game: Option<MlbPostGameInfo>,
+    /// The announced starting pitcher of the away team, if the game is scheduled and one has been announced.
+    pub away_pitcher: Option<String>,
+    /// The announced starting pitcher of the home team, if the game is scheduled and one has been announced.
+    pub home_pitcher: Option<String>,
+    pub warnings: Vec<MlbGameWarning>,
 }
 
-/// Retrieves information about all games over a period of time.
+impl MlbGameClientInfo {
+    /// Describes the position of the game within its series (e.g. "Game 2 of 3"), if known.
+    pub fn series_description(&self) -> Option<String> {
+        match (self.series_game_number, self.games_in_series) {
+            (Some(number), Some(total)) => Some(format!("Game {} of {}", number, total)),
+            _ => None,
+        }
+    }
+
+    /// Determines whether any information about the game could not be retrieved.
+    pub fn has_warnings(&self) -> bool {
+        !self.warnings.is_empty()
+    }
+
+    /// Determines whether the game has an image that could not be retrieved and should be retried.
+    pub fn is_missing_image(&self) -> bool {
+        self.image.is_none() && self.image_url.is_some()
+    }
+}
+
+/// Retrieves information about all games over a period of time, using the default client options.
 pub async fn get_games() -> BTreeMap<NaiveDate, Vec<MlbGameClientInfo>> {
-    debug!("starting get_games");
-    let today = Local::now();
-    let timezone = today.timezone();
-    let client = Client::new();
+    MlbClient::new().get_games().await
+}
 
-    let offsets: Vec<i64> = vec![0, -1, -2];
-    let mut futures = Vec::with_capacity(offsets.len());
-    let mut results = BTreeMap::new();
-    for i in offsets {
-        let day = today + Duration::days(i);
-        futures.push(extract_day_info(day, &timezone, &client));
+/// A client of the MLB API, holding the options applied to each request. Retrieved days are cached according to the
+/// client's CachePolicy; clones of a client share the same cache.
+#[derive(Clone, Debug, Default)]
+pub struct MlbClient {
+    language: Option<String>,
+    lazy_images: bool,
+    transport: Transport,
+    cache: Arc<cache::DayCache>,
+}
+
+impl MlbClient {
+    /// Initializes a client with the default options.
+    pub fn new() -> Self {
+        MlbClient::default()
+    }
+
+    /// Sets the language (e.g. "es") in which editorial content such as recap headlines is requested. Content that
+    /// has not been translated is returned in English.
+    pub fn with_language(mut self, language: &str) -> Self {
+        self.language = Some(language.to_owned());
+        self
+    }
+
+    /// Sets the policy governing how long retrieved days are cached.
+    pub fn with_cache_policy(self, policy: CachePolicy) -> Self {
+        self.cache.set_policy(policy);
+        self
+    }
+
+    /// Persists retrieved days to the given directory, so that days which are still fresh are not retrieved again
+    /// after a restart.
+    pub fn with_cache_dir<P: Into<std::path::PathBuf>>(self, directory: P) -> Self {
+        self.cache.set_directory(directory.into());
+        self
+    }
+
+    /// Discards the cached games of the given date, so that they are retrieved again on the next request.
+    pub fn invalidate(&self, date: NaiveDate) {
+        self.cache.invalidate(date);
+    }
+
+    /// Discards the cached games of every date.
+    pub fn invalidate_all(&self) {
+        self.cache.clear();
+    }
+
+    /// Determines how many requested days were answered from the cache, shared between clones of the client.
+    pub fn cache_stats(&self) -> CacheStats {
+        self.cache.stats()
+    }
+
+    /// Sets the mode in which responses are recorded to, or replayed from, a fixture directory.
+    #[cfg(feature = "fixtures")]
+    pub fn with_fixtures(mut self, mode: fixtures::FixtureMode) -> Self {
+        self.transport.set_fixtures(mode);
+        self
+    }
+
+    /// Skips the retrieval of images along with the games, leaving `image` unset so that images can be retrieved on
+    /// demand with `get_image`.
+    pub fn with_lazy_images(mut self) -> Self {
+        self.lazy_images = true;
+        self
+    }
+
+    /// Retrieves information about all games over a period of time.
+    pub async fn get_games(&self) -> BTreeMap<NaiveDate, Vec<MlbGameClientInfo>> {
+        self.get_recent_games(3).await
+    }
+
+    /// Retrieves information about all games over the given number of days, ending with today. Errors are logged and
+    /// result in no days being returned.
+    pub async fn get_recent_games(&self, days: u32) -> BTreeMap<NaiveDate, Vec<MlbGameClientInfo>> {
+        self.get_recent_games_with_progress(days, &|_| ()).await
+    }
+
+    /// Retrieves information about all games over the given number of days, ending with today, reporting progress to
+    /// the given hook. Errors are logged and result in no days being returned.
+    pub async fn get_recent_games_with_progress(
+        &self,
+        days: u32,
+        progress: &dyn Fn(MlbLoadProgress),
+    ) -> BTreeMap<NaiveDate, Vec<MlbGameClientInfo>> {
+        debug!("starting get_games");
+        let today = Local::now();
+        let end = today.date().naive_local();
+        let start = (today - Duration::days(i64::from(days.max(1)) - 1))
+            .date()
+            .naive_local();
+
+        let results = match self.get_games_in_range_with_progress(start..=end, progress).await {
+            Ok(results) => {
+                for (day, info) in &results {
+                    debug!("extracted {} games for {}", info.len(), day);
+                }
+                results
+            }
+            Err(ex) => {
+                error!("Error while retrieving game data:\n{}", ex);
+                BTreeMap::new()
+            }
+        };
+        debug!("ending get_games");
+        results
+    }
+
+    /// Retrieves information about all games over the given span of dates. Days that are not cached or have expired
+    /// are retrieved in a single request. Every date in the span is present in the result, with days that have no
+    /// games mapped to an empty list.
+    ///
+    /// # Errors
+    /// * If the URL is malformed.
+    /// * If the URL cannot be reached.
+    /// * If data cannot be read from the GET response.
+    /// * If the data cannot be deserialized into the expected JSON object.
+    pub async fn get_games_in_range(
+        &self,
+        range: RangeInclusive<NaiveDate>,
+    ) -> Result<BTreeMap<NaiveDate, Vec<MlbGameClientInfo>>, Box<dyn std::error::Error>> {
+        self.get_games_in_range_with_progress(range, &|_| ()).await
     }
 
-    for future in futures::future::join_all(futures).await {
-        match future {
-            Ok((day, info)) => {
-                debug!("extracted {} games for {}", info.len(), day);
-                results.insert(day, info);
+    /// Retrieves information about all games over the given span of dates, reporting the progress of days that are
+    /// not cached to the given hook. See `get_games_in_range`.
+    ///
+    /// # Errors
+    /// * If the URL is malformed.
+    /// * If the URL cannot be reached.
+    /// * If data cannot be read from the GET response.
+    /// * If the data cannot be deserialized into the expected JSON object.
+    pub async fn get_games_in_range_with_progress(
+        &self,
+        range: RangeInclusive<NaiveDate>,
+        progress: &dyn Fn(MlbLoadProgress),
+    ) -> Result<BTreeMap<NaiveDate, Vec<MlbGameClientInfo>>, Box<dyn std::error::Error>> {
+        let (start, end) = range.into_inner();
+        let today = Local::today().naive_local();
+        let mut results = BTreeMap::new();
+        let mut stale = Vec::new();
+        let mut day = start;
+        while day <= end {
+            match self.cache.get(day) {
+                Some(games) => {
+                    results.insert(day, games);
+                }
+                None => stale.push(day),
+            }
+            day = day.succ();
+        }
+
+        if let (Some(first), Some(last)) = (stale.first(), stale.last()) {
+            debug!("retrieving {} through {}", first, last);
+            let language = self.language.as_deref();
+            let fetch_images = !self.lazy_images;
+            let fetched =
+                extract_range_info(*first, *last, &Local, &self.transport, language, fetch_images, progress).await?;
+            for (day, games) in fetched {
+                self.cache.insert(day, today, games.clone());
+                results.insert(day, games);
             }
-            Err(ex) => error!("Error while retrieving game data:\n{}", ex),
         }
+        Ok(results)
+    }
+
+    /// Retrieves the recap article and line score of the game with the given identifier.
+    ///
+    /// # Errors
+    /// * If the URL cannot be reached.
+    /// * If data cannot be read from the GET response.
+    /// * If the data cannot be deserialized into the expected JSON object.
+    pub async fn get_game_detail(&self, game_pk: u64) -> Result<detail::MlbGameDetail, Box<dyn std::error::Error>> {
+        detail::get_game_detail(game_pk, &self.transport).await
+    }
+
+    /// Retrieves the current standings of every division.
+    ///
+    /// # Errors
+    /// * If the URL cannot be reached.
+    /// * If data cannot be read from the GET response.
+    /// * If the data cannot be deserialized into the expected JSON object.
+    pub async fn get_standings(&self) -> Result<Vec<standings::MlbDivisionStandings>, Box<dyn std::error::Error>> {
+        standings::get_standings(&self.transport).await
+    }
+
+    /// Retrieves the video feeds of the game with the given identifier, resolving the playback manifest URL and
+    /// availability of each playable feed when an authenticated session is given.
+    ///
+    /// # Errors
+    /// * If the URL cannot be reached.
+    /// * If data cannot be read from the GET response.
+    /// * If the data cannot be deserialized into the expected JSON object.
+    pub async fn get_media_feeds<S: auth::TokenStore>(
+        &self,
+        game_pk: u64,
+        auth: Option<&auth::MlbAuth<S>>,
+    ) -> Result<Vec<media::MlbMediaFeed>, Box<dyn std::error::Error>> {
+        media::get_media_feeds(game_pk, auth, &self.transport, self.language.as_deref()).await
+    }
+
+    /// Retrieves the image at the given URL, such as the `image_url` of a game.
+    ///
+    /// # Errors
+    /// * If the URL is malformed.
+    /// * If the URL cannot be reached.
+    /// * If the response does not have a successful status.
+    /// * If data cannot be read from the GET response.
+    pub async fn get_image(&self, url: &str) -> Result<MlbImage, Box<dyn std::error::Error>> {
+        let bytes = extract_image(url, &self.transport).await?;
+        let format = MlbImageFormat::detect(&bytes, url);
+        Ok(MlbImage { bytes, format })
+    }
+
+    /// Retries the retrieval of the image of the given game if a previous attempt failed. Does nothing if the image
+    /// has already been retrieved or the game has no image.
+    ///
+    /// # Errors
+    /// * If the URL is malformed.
+    /// * If the URL cannot be reached.
+    /// * If the response does not have a successful status.
+    /// * If data cannot be read from the GET response.
+    pub async fn retry_image(&self, game: &mut MlbGameClientInfo) -> Result<(), Box<dyn std::error::Error>> {
+        fetch_missing_image(game, &self.transport).await
+    }
+
+    /// Retries the retrieval of every image that previously failed in the given games, so that entries heal after
+    /// transient failures. Failures are logged and the images are left to be retried again later.
+    pub async fn retry_missing_images(&self, games: &mut [MlbGameClientInfo]) {
+        let futures = games
+            .iter_mut()
+            .filter(|game| game.is_missing_image())
+            .map(|game| async move {
+                if let Err(ex) = self.retry_image(game).await {
+                    error!("Error while retrying image for {}:\n{}", game.title, ex);
+                }
+            });
+        futures::future::join_all(futures).await;
+    }
+
+    /// Retrieves information about all games over the given span of dates, one day at a time in chronological order.
+    /// Days are requested in pages of `STREAM_PAGE_DAYS`, with at most `STREAM_CONCURRENCY` pages in flight, so
+    /// arbitrarily long spans (e.g. an entire season) can be walked without holding every day in memory. A page that
+    /// cannot be retrieved is reported as a single error in place of its days.
+    pub fn stream_games(
+        &self,
+        range: RangeInclusive<NaiveDate>,
+    ) -> impl Stream<Item = Result<DayGames, Box<dyn std::error::Error>>> + '_ {
+        let (start, end) = range.into_inner();
+        let page_starts = std::iter::successors(Some(start).filter(|day| *day <= end), move |day| {
+            Some(*day + Duration::days(STREAM_PAGE_DAYS)).filter(|next| *next <= end)
+        });
+        futures::stream::iter(page_starts)
+            .map(move |page_start| {
+                let page_end = std::cmp::min(page_start + Duration::days(STREAM_PAGE_DAYS - 1), end);
+                self.get_games_in_range(page_start..=page_end)
+            })
+            .buffered(STREAM_CONCURRENCY)
+            .flat_map(|page| {
+                let days: Vec<Result<DayGames, Box<dyn std::error::Error>>> = match page {
+                    Ok(days) => days
+                        .into_iter()
+                        .map(|(date, games)| {
+                            debug!("streamed {} games for {}", games.len(), date);
+                            Ok(DayGames { date, games })
+                        })
+                        .collect(),
+                    Err(ex) => vec![Err(ex)],
+                };
+                futures::stream::iter(days)
+            })
     }
-    debug!("ending get_games");
-    results
 }
 
-/// Retrieves information about all games for the given day.
+/// Retrieves information about all games between the given days (inclusive), with editorial content in the given
+/// language if any, and images if requested. Every day in the span is present in the result, even if no games were
+/// scheduled.
 ///
 /// # Errors
 /// * If the URL is malformed.
 /// * If the URL cannot be reached.
 /// * If data cannot be read from the GET response.
 /// * If the data cannot be deserialized into the expected JSON object.
-async fn extract_day_info<Tz>(
-    day: DateTime<Local>,
+async fn extract_range_info<Tz>(
+    start: NaiveDate,
+    end: NaiveDate,
     timezone: &Tz,
-    client: &Client<HttpConnector, Body>,
-) -> Result<(NaiveDate, Vec<MlbGameClientInfo>), Box<dyn std::error::Error>>
+    transport: &Transport,
+    language: Option<&str>,
+    fetch_images: bool,
+    progress: &dyn Fn(MlbLoadProgress),
+) -> Result<BTreeMap<NaiveDate, Vec<MlbGameClientInfo>>, Box<dyn std::error::Error>>
 where
     Tz: TimeZone,
     Tz::Offset: Display,
 {
-    let day_api = GAME_API.to_owned() + &format!("{}", day.format("%Y-%m-%d"));
-    let day_uri = day_api.parse::<hyper::Uri>()?;
-    let get_result = client.get(day_uri).await?;
-    let text_buf = hyper::body::to_bytes(get_result).await?;
-    let day_text = String::from_utf8(text_buf.as_ref().to_vec())?;
-    let day_result = serde_json::from_str::<MlbGameRange>(&day_text)?;
+    let mut range_api = format!(
+        "{}&startDate={}&endDate={}",
+        GAME_API,
+        start.format(DATE_FORMAT),
+        end.format(DATE_FORMAT)
+    );
+    if let Some(language) = language {
+        range_api += &format!("&{}", serde_urlencoded::to_string([("language", language)])?);
+    }
+    let text_buf = transport.get(&range_api).await?;
+    let range_text = String::from_utf8(text_buf)?;
+    let range_result = serde_json::from_str::<MlbGameRange>(&range_text)?;
 
-    Ok((
-        day.date().naive_local(),
-        crate::extract_game_info(day_result, timezone).await,
-    ))
+    let mut results = BTreeMap::new();
+    let mut day = start;
+    while day <= end {
+        results.insert(day, vec![]);
+        day = day.succ();
+    }
+    let total = range_result.dates.iter().map(|game_day| game_day.games.len()).sum();
+    let completed = Cell::new(0);
+    progress(MlbLoadProgress { completed: 0, total });
+    let on_game_completed = || {
+        completed.set(completed.get() + 1);
+        progress(MlbLoadProgress {
+            completed: completed.get(),
+            total,
+        });
+    };
+    let mut futures = Vec::with_capacity(range_result.dates.len());
+    for game_day in &range_result.dates {
+        let day = NaiveDate::parse_from_str(&game_day.date, DATE_FORMAT)?;
+        let on_game_completed = &on_game_completed;
+        futures.push(async move {
+            (
+                day,
+                crate::extract_game_info(game_day, timezone, transport, fetch_images, on_game_completed).await,
+            )
+        });
+    }
+    for (day, info) in futures::future::join_all(futures).await {
+        results.insert(day, info);
+    }
+    Ok(results)
 }
 
-/// Extracts the information for each game in the given MlbGameRange.
-async fn extract_game_info<Tz>(day_results: MlbGameRange, timezone: &Tz) -> Vec<MlbGameClientInfo>
+/// Extracts the information for each game in the given MlbGameDateInfo, including images if requested, calling the
+/// given hook as each game is completed.
+async fn extract_game_info<Tz>(
+    game_day: &MlbGameDateInfo,
+    timezone: &Tz,
+    transport: &Transport,
+    fetch_images: bool,
+    on_game_completed: &dyn Fn(),
+) -> Vec<MlbGameClientInfo>
 where
     Tz: TimeZone,
     Tz::Offset: Display,
 {
-    if let Some(game_day) = day_results.dates.get(0) {
-        let client = Client::builder().build::<_, Body>(HttpsConnector::new());
-        let num_games = game_day.games.len();
-        let mut futures = Vec::with_capacity(num_games);
-        let mut results = Vec::with_capacity(num_games);
-        for game in &day_results.dates[0].games {
-            futures.push(crate::extract_client_info(game, timezone, &client));
-        }
-
-        for info in futures::future::join_all(futures).await {
-            results.push(info);
-        }
-        results
-    } else {
-        vec![]
+    let mut futures = Vec::with_capacity(game_day.games.len());
+    for game in &game_day.games {
+        futures.push(async move {
+            let info = crate::extract_client_info(game, timezone, transport, fetch_images).await;
+            on_game_completed();
+            info
+        });
     }
+    futures::future::join_all(futures).await
 }
 
-/// Extracts the client display information from the given game info.
+/// Extracts the client display information from the given game info, including its image if requested.
 async fn extract_client_info<Tz>(
     game: &MlbGameInfo,
     timezone: &Tz,
-    client: &Client<HttpsConnector<HttpConnector>, Body>,
+    transport: &Transport,
+    fetch_image: bool,
 ) -> MlbGameClientInfo
 where
     Tz: TimeZone,
@@ -193,28 +665,111 @@ where
 {
     let teams = &game.teams;
     let title = format!("{} at {}", teams.away.team.name, teams.home.team.name);
-    let time = game
-        .gameDate
-        .parse::<DateTime<Utc>>()
-        .expect("Unable to parse time")
-        .with_timezone(timezone);
-    let default_summary = format!("Live {}", time.format("%I:%M %p"));
-    let (image, summary) = if let Some(editorial) = &game.content.editorial {
-        if let Some(article) = &editorial.recap.mlb {
-            match extract_image(&article.image.cuts[0].src, client).await {
-                Ok(img_bytes) => (Some(img_bytes), article.headline.to_owned()),
+    let mut warnings = Vec::new();
+    let default_summary = match game.gameDate.parse::<DateTime<Utc>>() {
+        Ok(time) => format!("Live {}", time.with_timezone(timezone).format("%I:%M %p")),
+        Err(ex) => {
+            warnings.push(MlbGameWarning::StartTimeUnavailable(ex.to_string()));
+            "Live".to_owned()
+        }
+    };
+    let state = MlbGameState::from_abstract(&game.status.abstractGameState);
+    let article = game.content.editorial.as_ref().and_then(|editorial| {
+        let recap = editorial.recap.as_ref().and_then(|recap| recap.mlb.as_ref());
+        let preview = editorial.preview.as_ref().and_then(|preview| preview.mlb.as_ref());
+        match state {
+            MlbGameState::Scheduled => preview.or(recap),
+            _ => recap,
+        }
+    });
+    let (image_url, summary) = match article {
+        Some(article) => (
+            article.image.cuts.first().map(|cut| cut.src.to_owned()),
+            article.headline.to_owned(),
+        ),
+        None => (None, default_summary),
+    };
+    let mut info = MlbGameClientInfo {
+        game_pk: game.gamePk,
+        state,
+        detailed_state: game.status.detailedState.to_owned(),
+        title,
+        away_team: teams.away.team.name.to_owned(),
+        home_team: teams.home.team.name.to_owned(),
+        away_score: teams.away.score,
+        home_score: teams.home.score,
+        image: None,
+        image_url,
+        summary,
+        series_game_number: game.seriesGameNumber,
+        games_in_series: game.gamesInSeries,
+        post_game: extract_post_game_info(game),
+        away_pitcher: extract_probable_pitcher(&teams.away, state),
+        home_pitcher: extract_probable_pitcher(&teams.home, state),
+        warnings,
+    };
+    if fetch_image {
+        if let Err(ex) = fetch_missing_image(&mut info, transport).await {
+            error!("Error while retrieving image for {}:\n{}", info.title, ex);
+        }
+    }
+    info
+}
+
+/// Extracts the name of the probable starting pitcher of the given team, if the game is in the given state in which it
+/// has not yet started.
+fn extract_probable_pitcher(team: &MlbGameTeamInfo, state: MlbGameState) -> Option<String> {
+    if state != MlbGameState::Scheduled {
+        return None;
+    }
+    team.probablePitcher.as_ref().map(|pitcher| pitcher.fullName.to_owned())
+}
+
+/// Extracts the attendance, duration, and first pitch time of the given game, if it has been completed.
+fn extract_post_game_info(game: &MlbGameInfo) -> Option<MlbPostGameInfo> {
+    if MlbGameState::from_abstract(&game.status.abstractGameState) != MlbGameState::Final {
+        return None;
+    }
+    let detail = game.gameInfo.as_ref()?;
+    Some(MlbPostGameInfo {
+        attendance: detail.attendance,
+        duration_minutes: detail.gameDurationMinutes,
+        first_pitch: detail
+            .firstPitch
+            .as_ref()
+            .and_then(|time| time.parse::<DateTime<Utc>>().ok()),
+    })
+}
+
+/// Retrieves the image of the given game if it has not yet been retrieved, recording the outcome in the warnings of
+/// the game.
+///
+/// # Errors
+/// See `extract_image`.
+async fn fetch_missing_image(
+    info: &mut MlbGameClientInfo,
+    transport: &Transport,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if info.image.is_none() {
+        if let Some(url) = &info.image_url {
+            info.warnings
+                .retain(|warning| !matches!(warning, MlbGameWarning::ImageUnavailable(_)));
+            match extract_image(url, transport).await {
+                Ok(img_bytes) => {
+                    let format = MlbImageFormat::detect(&img_bytes, url);
+                    info.image = Some(MlbImage {
+                        bytes: img_bytes,
+                        format,
+                    });
+                }
                 Err(ex) => {
-                    error!("Error while retrieving image for {}:\n{}", title, ex);
-                    (None, default_summary)
+                    info.warnings.push(MlbGameWarning::ImageUnavailable(ex.to_string()));
+                    return Err(ex);
                 }
             }
-        } else {
-            (None, default_summary)
         }
-    } else {
-        (None, default_summary)
-    };
-    MlbGameClientInfo { title, image, summary }
+    }
+    Ok(())
 }
 
 /// Extracts the raw bytes of an image at the given URL.
@@ -224,12 +779,40 @@ where
 /// * If the URL cannot be reached.
 /// * If data cannot be read from the GET response.
 ///
-async fn extract_image(
-    img_url: &str,
-    client: &Client<HttpsConnector<HttpConnector>, Body>,
-) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
-    let img_uri = img_url.parse::<hyper::Uri>()?;
-    let get_result = client.get(img_uri).await?;
-    let img_bytes = hyper::body::to_bytes(get_result).await?;
-    Ok(img_bytes.as_ref().to_vec())
+async fn extract_image(img_url: &str, transport: &Transport) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    transport.get(img_url).await
+}
+
+/// A schedule provider that supplies a rail for each day of MLB games, most recent first.
+pub struct MlbProvider;
+
+impl ScheduleProvider for MlbProvider {
+    fn name(&self) -> &str {
+        "MLB"
+    }
+
+    fn fetch(&self) -> LocalBoxFuture<'_, Result<Vec<Rail>, ProviderError>> {
+        async {
+            let days = get_games().await;
+            if days.is_empty() {
+                return Err("no MLB schedule data could be retrieved".into());
+            }
+            Ok(days
+                .into_iter()
+                .rev()
+                .map(|(day, games)| Rail {
+                    label: day.format("%A, %B %-d").to_string(),
+                    entries: games
+                        .into_iter()
+                        .map(|game| RailEntry {
+                            title: game.title,
+                            image: game.image.map(|image| image.bytes),
+                            summary: game.summary,
+                        })
+                        .collect(),
+                })
+                .collect())
+        }
+        .boxed_local()
+    }
 }
@@ -0,0 +1,213 @@
+//! Resolution of the media feeds available for an MLB game, for use by a video player front end.
+
+use crate::auth::{MlbAuth, TokenStore};
+use crate::transport::Transport;
+use hyper::StatusCode;
+use log::{debug, error};
+use serde_derive::{Deserialize, Serialize};
+
+/// The URI of the MLB game content API. The game identifier needs to be substituted for `{}`.
+const CONTENT_API: &str = "https://statsapi.mlb.com/api/v1/game/{}/content";
+/// The URI of the MLB media playback API. The media identifier needs to be substituted for `{}`.
+const PLAYBACK_API: &str = "https://edge.svcs.mlb.com/media/{}/scenarios/browser~csai";
+/// The media type accepted by the MLB media playback API.
+const PLAYBACK_ACCEPT: &str = "application/vnd.media-service+json; version=2";
+/// The title of the electronic program guide entry containing video feeds.
+const VIDEO_EPG_TITLE: &str = "MLBTV";
+
+/// A container for the content of an MLB game.
+#[derive(Debug, Deserialize, Serialize)]
+struct MlbGameContentDetail {
+    media: Option<MlbGameMedia>,
+}
+
+/// A container for the media of an MLB game.
+#[derive(Debug, Deserialize, Serialize)]
+struct MlbGameMedia {
+    #[serde(default)]
+    epg: Vec<MlbEpgEntry>,
+}
+
+/// A container for a group of feeds in the electronic program guide of an MLB game.
+#[derive(Debug, Deserialize, Serialize)]
+struct MlbEpgEntry {
+    title: String,
+    #[serde(default)]
+    items: Vec<MlbEpgItem>,
+}
+
+/// A container for a single feed in the electronic program guide of an MLB game.
+#[derive(Debug, Deserialize, Serialize)]
+struct MlbEpgItem {
+    mediaId: String,
+    #[serde(default)]
+    mediaFeedType: String,
+    #[serde(default)]
+    callLetters: String,
+    #[serde(default)]
+    mediaState: String,
+    #[serde(default)]
+    freeGame: bool,
+}
+
+/// A container for the response of the media playback API.
+#[derive(Debug, Deserialize, Serialize)]
+struct MlbPlaybackInfo {
+    stream: Option<MlbPlaybackStream>,
+    #[serde(default)]
+    errors: Vec<MlbPlaybackError>,
+}
+
+/// A container for an error reported by the media playback API.
+#[derive(Debug, Deserialize, Serialize)]
+struct MlbPlaybackError {
+    #[serde(default)]
+    code: String,
+}
+
+/// A container for the stream information of the media playback API.
+#[derive(Debug, Deserialize, Serialize)]
+struct MlbPlaybackStream {
+    complete: String,
+}
+
+/// An enumeration of reasons a media feed can or cannot be watched.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum MlbFeedAvailability {
+    /// The feed can be played with the resolved playback URL.
+    Available,
+    /// The feed is not airing and has no replay.
+    Off,
+    /// The feed requires an authenticated MLB account.
+    RequiresLogin,
+    /// The account does not have the entitlement required by the feed.
+    NotEntitled,
+    /// The feed is blacked out in the viewer's location.
+    Blackout,
+    /// The availability of the feed could not be determined.
+    Unknown,
+}
+
+/// A container for information used by the client to present a media feed of an MLB game.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MlbMediaFeed {
+    pub media_id: String,
+    pub broadcast: String,
+    pub feed_type: String,
+    pub media_state: String,
+    pub requires_subscription: bool,
+    pub availability: MlbFeedAvailability,
+    pub playback_url: Option<String>,
+}
+
+impl MlbMediaFeed {
+    /// Determines whether the feed is currently airing or available for replay.
+    pub fn is_playable(&self) -> bool {
+        self.media_state == "MEDIA_ON" || self.media_state == "MEDIA_ARCHIVE"
+    }
+}
+
+/// Retrieves the video feeds of the game with the given identifier. When an authenticated session is given, the
+/// playback manifest URL is resolved for each playable feed the account is entitled to, and the availability of each
+/// feed is reported so that unwatchable feeds can be presented as such before playback is attempted.
+///
+/// # Errors
+/// * If the URL cannot be reached.
+/// * If data cannot be read from the GET response.
+/// * If the data cannot be deserialized into the expected JSON object.
+pub(crate) async fn get_media_feeds<S: TokenStore>(
+    game_pk: u64,
+    auth: Option<&MlbAuth<S>>,
+    transport: &Transport,
+    language: Option<&str>,
+) -> Result<Vec<MlbMediaFeed>, Box<dyn std::error::Error>> {
+    debug!("starting get_media_feeds for {}", game_pk);
+    let mut content_api = CONTENT_API.replace("{}", &game_pk.to_string());
+    if let Some(language) = language {
+        content_api += &format!("?{}", serde_urlencoded::to_string([("language", language)])?);
+    }
+    let text_buf = transport.get(&content_api).await?;
+    let content = serde_json::from_slice::<MlbGameContentDetail>(&text_buf)?;
+
+    let items = content
+        .media
+        .map(|media| media.epg)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|entry| entry.title == VIDEO_EPG_TITLE)
+        .flat_map(|entry| entry.items);
+    let mut feeds = Vec::new();
+    for item in items {
+        let mut feed = MlbMediaFeed {
+            media_id: item.mediaId,
+            broadcast: item.callLetters,
+            feed_type: item.mediaFeedType,
+            media_state: item.mediaState,
+            requires_subscription: !item.freeGame,
+            availability: MlbFeedAvailability::Off,
+            playback_url: None,
+        };
+        if feed.is_playable() {
+            feed.availability = match auth {
+                Some(auth) => match resolve_playback_url(&feed.media_id, auth, transport).await {
+                    Ok((availability, url)) => {
+                        feed.playback_url = url;
+                        availability
+                    }
+                    Err(ex) => {
+                        error!("Error while resolving playback for {}:\n{}", feed.media_id, ex);
+                        MlbFeedAvailability::Unknown
+                    }
+                },
+                None => MlbFeedAvailability::RequiresLogin,
+            };
+        }
+        feeds.push(feed);
+    }
+    debug!("ending get_media_feeds for {}", game_pk);
+    Ok(feeds)
+}
+
+/// Resolves the availability and playback manifest URL of the media with the given identifier.
+///
+/// # Errors
+/// * If an access token cannot be obtained.
+/// * If the URL cannot be reached.
+/// * If the data cannot be deserialized into the expected JSON object.
+async fn resolve_playback_url<S: TokenStore>(
+    media_id: &str,
+    auth: &MlbAuth<S>,
+    transport: &Transport,
+) -> Result<(MlbFeedAvailability, Option<String>), Box<dyn std::error::Error>> {
+    let token = auth.access_token().await?;
+    let authorization = format!("Bearer {}", token);
+    let headers = [("Accept", PLAYBACK_ACCEPT), ("Authorization", authorization.as_str())];
+    let (status, text_buf) = transport
+        .get_with_headers(&PLAYBACK_API.replace("{}", media_id), &headers)
+        .await?;
+    let playback = match serde_json::from_slice::<MlbPlaybackInfo>(&text_buf) {
+        Ok(playback) => playback,
+        Err(ex) if status.is_success() => return Err(ex.into()),
+        Err(_) => MlbPlaybackInfo {
+            stream: None,
+            errors: vec![],
+        },
+    };
+    if let Some(stream) = playback.stream {
+        return Ok((MlbFeedAvailability::Available, Some(stream.complete)));
+    }
+    let availability = if playback
+        .errors
+        .iter()
+        .any(|error| error.code.to_uppercase().contains("BLACKOUT"))
+    {
+        MlbFeedAvailability::Blackout
+    } else if status == StatusCode::UNAUTHORIZED {
+        MlbFeedAvailability::RequiresLogin
+    } else if status == StatusCode::FORBIDDEN || !playback.errors.is_empty() {
+        MlbFeedAvailability::NotEntitled
+    } else {
+        MlbFeedAvailability::Unknown
+    };
+    Ok((availability, None))
+}
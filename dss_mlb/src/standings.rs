@@ -0,0 +1,96 @@
+//! Retrieval of the standings of each MLB division, for use by a standings snapshot.
+
+use crate::transport::Transport;
+use log::debug;
+use serde_derive::{Deserialize, Serialize};
+
+/// The URI of the MLB standings API for the American and National Leagues, with the names of their divisions.
+const STANDINGS_API: &str = "https://statsapi.mlb.com/api/v1/standings?leagueId=103,104&hydrate=division";
+
+/// A container for the response of the standings API.
+#[derive(Debug, Deserialize, Serialize)]
+struct MlbStandingsInfo {
+    #[serde(default)]
+    records: Vec<MlbDivisionRecord>,
+}
+
+/// A container for the standings of a single division.
+#[derive(Debug, Deserialize, Serialize)]
+struct MlbDivisionRecord {
+    division: MlbDivisionInfo,
+    #[serde(default)]
+    teamRecords: Vec<MlbTeamRecord>,
+}
+
+/// A container for static information about an MLB division.
+#[derive(Debug, Deserialize, Serialize)]
+struct MlbDivisionInfo {
+    name: Option<String>,
+    nameShort: Option<String>,
+}
+
+/// A container for the record of a team within the standings of its division.
+#[derive(Debug, Deserialize, Serialize)]
+struct MlbTeamRecord {
+    team: MlbStandingsTeamInfo,
+    wins: u32,
+    losses: u32,
+    gamesBack: String,
+}
+
+/// A container for static information about an MLB team within the standings.
+#[derive(Debug, Deserialize, Serialize)]
+struct MlbStandingsTeamInfo {
+    name: String,
+}
+
+/// A container for the record of a team, used by the client to display standings.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MlbTeamStanding {
+    pub name: String,
+    pub wins: u32,
+    pub losses: u32,
+    /// The number of games behind the leader of the division (e.g. "2.5"), or "-" for the leader.
+    pub games_back: String,
+}
+
+/// A container for the standings of a single division, used by the client to display standings.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MlbDivisionStandings {
+    pub name: String,
+    /// The teams of the division, in order of their rank.
+    pub teams: Vec<MlbTeamStanding>,
+}
+
+/// Retrieves the current standings of every division.
+///
+/// # Errors
+/// * If the URL cannot be reached.
+/// * If data cannot be read from the GET response.
+/// * If the data cannot be deserialized into the expected JSON object.
+pub(crate) async fn get_standings(
+    transport: &Transport,
+) -> Result<Vec<MlbDivisionStandings>, Box<dyn std::error::Error>> {
+    debug!("starting get_standings");
+    let buf = transport.get(STANDINGS_API).await?;
+    let standings = serde_json::from_slice::<MlbStandingsInfo>(&buf)?;
+    let divisions = standings
+        .records
+        .into_iter()
+        .map(|record| MlbDivisionStandings {
+            name: record.division.nameShort.or(record.division.name).unwrap_or_default(),
+            teams: record
+                .teamRecords
+                .into_iter()
+                .map(|team| MlbTeamStanding {
+                    name: team.team.name,
+                    wins: team.wins,
+                    losses: team.losses,
+                    games_back: team.gamesBack,
+                })
+                .collect(),
+        })
+        .collect();
+    debug!("ending get_standings");
+    Ok(divisions)
+}
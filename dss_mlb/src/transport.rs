@@ -0,0 +1,244 @@
+//! The HTTP transport used to retrieve data from the MLB API. Requests are made with hyper by default, or with
+//! reqwest when the `reqwest` feature is enabled.
+
+#[cfg(feature = "fixtures")]
+use crate::fixtures::{self, FixtureMode};
+#[cfg(not(feature = "reqwest"))]
+use hyper::client::HttpConnector;
+#[cfg(not(feature = "reqwest"))]
+use hyper::header::CONTENT_TYPE;
+use hyper::StatusCode;
+#[cfg(not(feature = "reqwest"))]
+use hyper::{Body, Client, Method, Request};
+#[cfg(not(feature = "reqwest"))]
+use hyper_tls::HttpsConnector;
+
+/// The content type of a URL-encoded form body.
+const FORM_CONTENT_TYPE: &str = "application/x-www-form-urlencoded";
+
+/// The client of the HTTP backend selected by the enabled features.
+#[cfg(not(feature = "reqwest"))]
+type HttpClient = Client<HttpsConnector<HttpConnector>, Body>;
+/// The client of the HTTP backend selected by the enabled features.
+#[cfg(feature = "reqwest")]
+type HttpClient = reqwest::Client;
+
+/// A shared connection to the MLB API, optionally recording or replaying responses.
+#[derive(Clone, Debug)]
+pub(crate) struct Transport {
+    client: HttpClient,
+    #[cfg(feature = "fixtures")]
+    fixtures: Option<FixtureMode>,
+}
+
+impl Default for Transport {
+    fn default() -> Self {
+        #[cfg(not(feature = "reqwest"))]
+        let client = Client::builder().build::<_, Body>(HttpsConnector::new());
+        #[cfg(feature = "reqwest")]
+        let client = reqwest::Client::new();
+        Transport {
+            client,
+            #[cfg(feature = "fixtures")]
+            fixtures: None,
+        }
+    }
+}
+
+impl Transport {
+    /// Sets the mode used to record or replay responses.
+    #[cfg(feature = "fixtures")]
+    pub fn set_fixtures(&mut self, mode: FixtureMode) {
+        self.fixtures = Some(mode);
+    }
+
+    /// Retrieves the raw bytes of the successful response at the given URL.
+    ///
+    /// # Errors
+    /// * If the URL is malformed.
+    /// * If the URL cannot be reached.
+    /// * If the response does not have a successful status, so that error pages are never taken for data.
+    /// * If data cannot be read from the GET response.
+    /// * If a fixture cannot be recorded or replayed.
+    pub async fn get(&self, url: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        #[cfg(feature = "fixtures")]
+        {
+            if let Some(FixtureMode::Replay(dir)) = &self.fixtures {
+                return fixtures::replay(dir, url);
+            }
+        }
+        let (status, bytes) = self.get_with_headers(url, &[]).await?;
+        if !status.is_success() {
+            return Err(format!("Request for {} failed with status {}", url, status).into());
+        }
+        #[cfg(feature = "fixtures")]
+        {
+            if let Some(FixtureMode::Record(dir)) = &self.fixtures {
+                fixtures::record(dir, url, &bytes)?;
+            }
+        }
+        Ok(bytes)
+    }
+
+    /// Retrieves the status and raw bytes of the response at the given URL, sending the given headers. Responses are
+    /// never recorded or replayed, as they may be specific to an account.
+    ///
+    /// # Errors
+    /// * If the URL or headers are malformed.
+    /// * If the URL cannot be reached.
+    /// * If data cannot be read from the GET response.
+    #[cfg(not(feature = "reqwest"))]
+    pub async fn get_with_headers(
+        &self,
+        url: &str,
+        headers: &[(&str, &str)],
+    ) -> Result<(StatusCode, Vec<u8>), Box<dyn std::error::Error>> {
+        let mut request = Request::get(url);
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        let response = self.client.request(request.body(Body::empty())?).await?;
+        let status = response.status();
+        Ok((status, hyper::body::to_bytes(response).await?.to_vec()))
+    }
+
+    /// Retrieves the status and raw bytes of the response at the given URL, sending the given headers. Responses are
+    /// never recorded or replayed, as they may be specific to an account.
+    ///
+    /// # Errors
+    /// * If the URL or headers are malformed.
+    /// * If the URL cannot be reached.
+    /// * If data cannot be read from the GET response.
+    #[cfg(feature = "reqwest")]
+    pub async fn get_with_headers(
+        &self,
+        url: &str,
+        headers: &[(&str, &str)],
+    ) -> Result<(StatusCode, Vec<u8>), Box<dyn std::error::Error>> {
+        let mut request = self.client.get(url);
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        let response = request.send().await?;
+        let status = response.status();
+        Ok((status, response.bytes().await?.to_vec()))
+    }
+
+    /// Posts the given URL-encoded form to the given URL, returning the status and raw bytes of the response.
+    ///
+    /// # Errors
+    /// * If the URL is malformed.
+    /// * If the URL cannot be reached.
+    /// * If data cannot be read from the POST response.
+    #[cfg(not(feature = "reqwest"))]
+    pub async fn post_form(
+        &self,
+        url: &str,
+        form: String,
+    ) -> Result<(StatusCode, Vec<u8>), Box<dyn std::error::Error>> {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri(url)
+            .header(CONTENT_TYPE, FORM_CONTENT_TYPE)
+            .body(Body::from(form))?;
+        let response = self.client.request(request).await?;
+        let status = response.status();
+        Ok((status, hyper::body::to_bytes(response).await?.to_vec()))
+    }
+
+    /// Posts the given URL-encoded form to the given URL, returning the status and raw bytes of the response.
+    ///
+    /// # Errors
+    /// * If the URL is malformed.
+    /// * If the URL cannot be reached.
+    /// * If data cannot be read from the POST response.
+    #[cfg(feature = "reqwest")]
+    pub async fn post_form(
+        &self,
+        url: &str,
+        form: String,
+    ) -> Result<(StatusCode, Vec<u8>), Box<dyn std::error::Error>> {
+        let response = self
+            .client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, FORM_CONTENT_TYPE)
+            .body(form)
+            .send()
+            .await?;
+        let status = response.status();
+        Ok((status, response.bytes().await?.to_vec()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MlbGameClientInfo, MlbGameState, MlbGameWarning};
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Starts a server on a local port that answers a single request with the given status line and body, returning
+    /// the URL of the server.
+    fn serve_once(status: &'static str, body: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/image.jpg", listener.local_addr().unwrap());
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0; 1024];
+            let _ = stream.read(&mut request);
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+        });
+        url
+    }
+
+    #[tokio::test]
+    async fn unsuccessful_status_is_an_error() {
+        let url = serve_once("404 Not Found", "not found");
+        let result = Transport::default().get(&url).await;
+        assert!(result.unwrap_err().to_string().contains("404"));
+    }
+
+    #[tokio::test]
+    async fn successful_status_returns_body() {
+        let url = serve_once("200 OK", "image");
+        assert_eq!(Transport::default().get(&url).await.unwrap(), b"image".to_vec());
+    }
+
+    #[tokio::test]
+    async fn failed_image_is_unavailable() {
+        let mut game = MlbGameClientInfo {
+            game_pk: 1,
+            state: MlbGameState::Final,
+            detailed_state: "Final".to_owned(),
+            title: "New York Yankees at Tampa Bay Rays".to_owned(),
+            away_team: "New York Yankees".to_owned(),
+            home_team: "Tampa Bay Rays".to_owned(),
+            away_score: Some(2),
+            home_score: Some(4),
+            image: None,
+            image_url: Some(serve_once("500 Internal Server Error", "error")),
+            summary: String::new(),
+            series_game_number: None,
+            games_in_series: None,
+            post_game: None,
+            away_pitcher: None,
+            home_pitcher: None,
+            warnings: Vec::new(),
+        };
+        assert!(crate::fetch_missing_image(&mut game, &Transport::default())
+            .await
+            .is_err());
+        assert!(game.is_missing_image());
+        assert!(matches!(
+            game.warnings.as_slice(),
+            [MlbGameWarning::ImageUnavailable(_)]
+        ));
+    }
+}
@@ -0,0 +1,417 @@
+#![allow(non_snake_case)]
+
+//! Controller that queries an NFL API and provides data to be used by a client to display NFL game editorials.
+//!
+//! The NFL schedule is organized by week rather than by day, so each week maps onto a single row of games. Teams
+//! that are not playing in a given week are reported as on a bye for that week's row.
+
+use chrono::{DateTime, Local, Utc};
+use dss_core::{ProviderError, Rail, RailEntry, ScheduleProvider};
+use futures::future::{FutureExt, LocalBoxFuture};
+use hyper::client::HttpConnector;
+use hyper::{Body, Client};
+use hyper_tls::HttpsConnector;
+use log::{debug, error};
+use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The URI of the NFL scoreboard API. Without query parameters, the current week is returned.
+const SCOREBOARD_API: &str = "https://site.api.espn.com/apis/site/v2/sports/football/nfl/scoreboard";
+/// The number of weeks (including the current week) to retrieve.
+const WEEK_COUNT: u32 = 3;
+/// The season type of the preseason.
+const PRESEASON_TYPE: u32 = 1;
+/// The season type of the postseason.
+const POSTSEASON_TYPE: u32 = 3;
+/// The season type of the offseason, which has no games.
+const OFFSEASON_TYPE: u32 = 4;
+
+/// A container for NFL game information over a single week.
+#[derive(Debug, Deserialize, Serialize)]
+struct NflScoreboard {
+    #[serde(default)]
+    leagues: Vec<NflLeagueInfo>,
+    season: NflSeasonInfo,
+    week: NflWeekMeta,
+    events: Vec<NflEventInfo>,
+}
+
+/// A container for information about the league a scoreboard belongs to.
+#[derive(Debug, Deserialize, Serialize)]
+struct NflLeagueInfo {
+    #[serde(default)]
+    calendar: Vec<NflCalendarSeason>,
+}
+
+/// A container for the weeks of a single season type in the league calendar.
+#[derive(Debug, Deserialize, Serialize)]
+struct NflCalendarSeason {
+    value: String,
+    #[serde(default)]
+    entries: Vec<NflCalendarWeek>,
+}
+
+/// A container for a single week in the league calendar.
+#[derive(Debug, Deserialize, Serialize)]
+struct NflCalendarWeek {
+    value: String,
+}
+
+/// A container for information about the season a scoreboard belongs to.
+#[derive(Debug, Deserialize, Serialize)]
+struct NflSeasonInfo {
+    #[serde(rename = "type")]
+    season_type: u32,
+    year: i32,
+}
+
+/// A container for information about the week a scoreboard belongs to.
+#[derive(Debug, Deserialize, Serialize)]
+struct NflWeekMeta {
+    number: u32,
+    #[serde(default)]
+    teamsOnBye: Vec<NflTeamInfo>,
+}
+
+/// A container for information about an NFL game.
+#[derive(Debug, Deserialize, Serialize)]
+struct NflEventInfo {
+    date: String,
+    competitions: Vec<NflCompetitionInfo>,
+}
+
+/// A container for information about the teams and media involved in an NFL game.
+#[derive(Debug, Deserialize, Serialize)]
+struct NflCompetitionInfo {
+    competitors: Vec<NflCompetitorInfo>,
+    status: NflStatusInfo,
+    #[serde(default)]
+    headlines: Vec<NflHeadlineInfo>,
+}
+
+/// A container for information about an NFL team involved in a game.
+#[derive(Debug, Deserialize, Serialize)]
+struct NflCompetitorInfo {
+    homeAway: String,
+    team: NflTeamInfo,
+    score: Option<String>,
+}
+
+/// A container for static information about an NFL team.
+#[derive(Debug, Deserialize, Serialize)]
+struct NflTeamInfo {
+    displayName: String,
+}
+
+/// A container for the progress of an NFL game.
+#[derive(Debug, Deserialize, Serialize)]
+struct NflStatusInfo {
+    #[serde(rename = "type")]
+    status_type: NflStatusType,
+}
+
+/// A container for the progress of an NFL game.
+#[derive(Debug, Deserialize, Serialize)]
+struct NflStatusType {
+    state: String,
+    shortDetail: String,
+}
+
+/// A container for editorial information pertaining to an NFL game.
+#[derive(Debug, Deserialize, Serialize)]
+struct NflHeadlineInfo {
+    shortLinkText: Option<String>,
+    #[serde(default)]
+    video: Vec<NflVideoInfo>,
+}
+
+/// A container for information about a video pertaining to an NFL game.
+#[derive(Debug, Deserialize, Serialize)]
+struct NflVideoInfo {
+    thumbnail: Option<String>,
+}
+
+/// A container for information used by the client to display an NFL game entry.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NflGameClientInfo {
+    pub title: String,
+    pub image: Option<Vec<u8>>,
+    pub summary: String,
+}
+
+/// A container for information used by the client to display a single week of NFL games.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NflWeekClientInfo {
+    pub games: Vec<NflGameClientInfo>,
+    pub bye_teams: Vec<String>,
+}
+
+/// Retrieves information about all games over the current and previous weeks, keyed by season type and week number.
+/// The previous weeks continue into the final weeks of the previous season type when the current season type has
+/// fewer.
+pub async fn get_games() -> BTreeMap<(u32, u32), NflWeekClientInfo> {
+    debug!("starting get_games");
+    let client = Client::builder().build::<_, Body>(HttpsConnector::new());
+    let mut results = BTreeMap::new();
+
+    let current = match extract_scoreboard(SCOREBOARD_API, &client).await {
+        Ok(current) => current,
+        Err(ex) => {
+            error!("Error while retrieving current week:\n{}", ex);
+            return results;
+        }
+    };
+    let season_type = current.season.season_type;
+    let year = current.season.year;
+    let current_week = current.week.number;
+
+    let calendar = current.leagues.first().map_or(&[][..], |league| &league.calendar[..]);
+    let weeks = previous_weeks(calendar, season_type, current_week);
+
+    let client_ref = &client;
+    let mut futures = Vec::with_capacity(WEEK_COUNT as usize - 1);
+    for (season_type, week) in weeks.into_iter().take(WEEK_COUNT as usize - 1) {
+        let week_api = format!(
+            "{}?seasontype={}&week={}&dates={}",
+            SCOREBOARD_API, season_type, week, year
+        );
+        futures.push(async move { ((season_type, week), extract_scoreboard(&week_api, client_ref).await) });
+    }
+
+    results.insert((season_type, current_week), extract_week_info(current, &client).await);
+    for (key, future) in futures::future::join_all(futures).await {
+        match future {
+            Ok(scoreboard) => {
+                let info = extract_week_info(scoreboard, &client).await;
+                debug!("extracted {} games for {}", info.games.len(), week_label(key.0, key.1));
+                results.insert(key, info);
+            }
+            Err(ex) => error!(
+                "Error while retrieving game data for {}:\n{}",
+                week_label(key.0, key.1),
+                ex
+            ),
+        }
+    }
+    debug!("ending get_games");
+    results
+}
+
+/// Determines the season type and number of each week before the given week of the given season type, most recent
+/// first. Using the league calendar, the weeks continue into the final weeks of the previous season types; without
+/// it, only the previous weeks of the given season type are known.
+fn previous_weeks(calendar: &[NflCalendarSeason], season_type: u32, week: u32) -> Vec<(u32, u32)> {
+    let calendar_weeks: Vec<(u32, u32)> = calendar
+        .iter()
+        .filter_map(|season| Some((season.value.parse::<u32>().ok()?, season)))
+        .filter(|(calendar_type, _)| *calendar_type != OFFSEASON_TYPE)
+        .flat_map(|(calendar_type, season)| {
+            season
+                .entries
+                .iter()
+                .filter_map(move |entry| Some((calendar_type, entry.value.parse::<u32>().ok()?)))
+        })
+        .collect();
+    match calendar_weeks.iter().position(|&key| key == (season_type, week)) {
+        Some(index) => calendar_weeks[..index].iter().rev().copied().collect(),
+        None => (1..week).rev().map(|previous| (season_type, previous)).collect(),
+    }
+}
+
+/// Determines the label of the given week of the given season type.
+fn week_label(season_type: u32, week: u32) -> String {
+    match season_type {
+        PRESEASON_TYPE => format!("Preseason Week {}", week),
+        POSTSEASON_TYPE => format!("Postseason Week {}", week),
+        _ => format!("Week {}", week),
+    }
+}
+
+/// Retrieves the scoreboard at the given URL.
+///
+/// # Errors
+/// * If the URL is malformed.
+/// * If the URL cannot be reached.
+/// * If data cannot be read from the GET response.
+/// * If the data cannot be deserialized into the expected JSON object.
+async fn extract_scoreboard(
+    url: &str,
+    client: &Client<HttpsConnector<HttpConnector>, Body>,
+) -> Result<NflScoreboard, Box<dyn std::error::Error>> {
+    let uri = url.parse::<hyper::Uri>()?;
+    let get_result = client.get(uri).await?;
+    let text_buf = hyper::body::to_bytes(get_result).await?;
+    let text = String::from_utf8(text_buf.as_ref().to_vec())?;
+    Ok(serde_json::from_str::<NflScoreboard>(&text)?)
+}
+
+/// Extracts the information for each game and bye in the given NflScoreboard.
+async fn extract_week_info(
+    scoreboard: NflScoreboard,
+    client: &Client<HttpsConnector<HttpConnector>, Body>,
+) -> NflWeekClientInfo {
+    let mut futures = Vec::with_capacity(scoreboard.events.len());
+    for event in &scoreboard.events {
+        futures.push(crate::extract_client_info(event, client));
+    }
+    let games = futures::future::join_all(futures).await.into_iter().flatten().collect();
+    let bye_teams = scoreboard
+        .week
+        .teamsOnBye
+        .into_iter()
+        .map(|team| team.displayName)
+        .collect();
+    NflWeekClientInfo { games, bye_teams }
+}
+
+/// Extracts the client display information from the given event info. Returns None if the event has no competition.
+async fn extract_client_info(
+    event: &NflEventInfo,
+    client: &Client<HttpsConnector<HttpConnector>, Body>,
+) -> Option<NflGameClientInfo> {
+    let competition = event.competitions.first()?;
+    let away = competition.competitors.iter().find(|c| c.homeAway == "away")?;
+    let home = competition.competitors.iter().find(|c| c.homeAway == "home")?;
+    let title = format!("{} at {}", away.team.displayName, home.team.displayName);
+
+    let status = &competition.status.status_type;
+    let default_summary = match status.state.as_str() {
+        "pre" => match event.date.parse::<DateTime<Utc>>() {
+            Ok(time) => format!("Kickoff {}", time.with_timezone(&Local).format("%a %I:%M %p")),
+            Err(_) => status.shortDetail.to_owned(),
+        },
+        _ => format!(
+            "{} {} - {}",
+            status.shortDetail,
+            away.score.as_deref().unwrap_or("0"),
+            home.score.as_deref().unwrap_or("0"),
+        ),
+    };
+
+    let headline = competition.headlines.first();
+    let summary = headline
+        .and_then(|h| h.shortLinkText.to_owned())
+        .unwrap_or(default_summary);
+    let thumbnail = headline
+        .and_then(|h| h.video.first())
+        .and_then(|v| v.thumbnail.as_ref());
+    let image = match thumbnail {
+        Some(url) => match extract_image(url, client).await {
+            Ok(img_bytes) => Some(img_bytes),
+            Err(ex) => {
+                error!("Error while retrieving image for {}:\n{}", title, ex);
+                None
+            }
+        },
+        None => None,
+    };
+    Some(NflGameClientInfo { title, image, summary })
+}
+
+/// Extracts the raw bytes of an image at the given URL.
+///
+/// # Errors
+/// * If the URL is malformed.
+/// * If the URL cannot be reached.
+/// * If data cannot be read from the GET response.
+///
+async fn extract_image(
+    img_url: &str,
+    client: &Client<HttpsConnector<HttpConnector>, Body>,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let img_uri = img_url.parse::<hyper::Uri>()?;
+    let get_result = client.get(img_uri).await?;
+    let img_bytes = hyper::body::to_bytes(get_result).await?;
+    Ok(img_bytes.as_ref().to_vec())
+}
+
+/// A schedule provider that supplies a rail for each week of NFL games, most recent first. Teams on a bye are
+/// appended to the end of their week's rail.
+pub struct NflProvider;
+
+impl ScheduleProvider for NflProvider {
+    fn name(&self) -> &str {
+        "NFL"
+    }
+
+    fn fetch(&self) -> LocalBoxFuture<'_, Result<Vec<Rail>, ProviderError>> {
+        async {
+            let weeks = get_games().await;
+            if weeks.is_empty() {
+                return Err("no NFL schedule data could be retrieved".into());
+            }
+            Ok(weeks
+                .into_iter()
+                .rev()
+                .map(|((season_type, week), info)| {
+                    let mut entries: Vec<RailEntry> = info
+                        .games
+                        .into_iter()
+                        .map(|game| RailEntry {
+                            title: game.title,
+                            image: game.image,
+                            summary: game.summary,
+                        })
+                        .collect();
+                    entries.extend(info.bye_teams.into_iter().map(|team| RailEntry {
+                        title: team,
+                        image: None,
+                        summary: "Bye".to_owned(),
+                    }));
+                    Rail {
+                        label: week_label(season_type, week),
+                        entries,
+                    }
+                })
+                .collect())
+        }
+        .boxed_local()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates a calendar with the given number of weeks in each of the preseason, regular season and postseason,
+    /// followed by the offseason.
+    fn calendar(week_counts: [u32; 3]) -> Vec<NflCalendarSeason> {
+        let mut calendar: Vec<NflCalendarSeason> = week_counts
+            .iter()
+            .zip(PRESEASON_TYPE..)
+            .map(|(&count, season_type)| NflCalendarSeason {
+                value: season_type.to_string(),
+                entries: (1..=count)
+                    .map(|week| NflCalendarWeek {
+                        value: week.to_string(),
+                    })
+                    .collect(),
+            })
+            .collect();
+        calendar.push(NflCalendarSeason {
+            value: OFFSEASON_TYPE.to_string(),
+            entries: vec![NflCalendarWeek { value: "1".to_owned() }],
+        });
+        calendar
+    }
+
+    #[test]
+    fn previous_weeks_stay_within_season_type() {
+        let weeks = previous_weeks(&calendar([4, 18, 5]), 2, 10);
+        assert_eq!(&weeks[..2], &[(2, 9), (2, 8)]);
+    }
+
+    #[test]
+    fn previous_weeks_continue_into_previous_season_type() {
+        let weeks = previous_weeks(&calendar([4, 18, 5]), POSTSEASON_TYPE, 1);
+        assert_eq!(&weeks[..2], &[(2, 18), (2, 17)]);
+        let weeks = previous_weeks(&calendar([4, 18, 5]), 2, 1);
+        assert_eq!(&weeks[..2], &[(PRESEASON_TYPE, 4), (PRESEASON_TYPE, 3)]);
+    }
+
+    #[test]
+    fn previous_weeks_without_calendar() {
+        assert_eq!(previous_weeks(&[], 2, 3), vec![(2, 2), (2, 1)]);
+        assert!(previous_weeks(&[], POSTSEASON_TYPE, 1).is_empty());
+    }
+}